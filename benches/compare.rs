@@ -0,0 +1,486 @@
+#![allow(non_snake_case)]
+
+//! Side-by-side benchmarks of the `assoc`, `stream` and `typeparam`
+//! designs, along with the equivalent functionality from `rand`
+//! itself (`original`).
+//!
+//! After running, the results are collected into the Markdown
+//! comparison table used in the crate documentation, which is printed
+//! and written to `target/criterion/comparison.md`.
+
+extern crate criterion;
+extern crate rand;
+extern crate rand_sketch;
+extern crate serde_json;
+
+use criterion::{black_box, BenchmarkGroup, Criterion};
+use criterion::measurement::WallTime;
+use rand::{ChaChaRng, OsRng, Rng, XorShiftRng};
+use rand_sketch::pcg::Pcg32;
+use rand_sketch::stream::RandStream;
+
+use std::env;
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::path::PathBuf;
+
+/// The columns of the comparison table.
+const DESIGNS: &[&str] = &["assoc", "stream", "typeparam", "original"];
+
+/// The benchmark runner, along with the groups run so far, which form
+/// the rows of the comparison table.
+struct Suite {
+    c: Criterion,
+    rows: Vec<String>,
+}
+
+impl Suite {
+    fn group<S: Into<String>>(&mut self, name: S) -> BenchmarkGroup<'_, WallTime> {
+        let name = name.into();
+        self.rows.push(name.clone());
+        self.c.benchmark_group(name)
+    }
+}
+
+/// Benchmark `$body` as `$group/<design>` for each of the crate's
+/// designs, with `gen` and `gen_iter` referring to that design's
+/// functions.
+macro_rules! compare {
+    ($group: expr, $body: expr) => {
+        compare!($group, $body; assoc, stream, typeparam)
+    };
+    ($group: expr, $body: expr; $($design: ident),*) => {$({
+        #[allow(unused_imports)]
+        use rand_sketch::$design::{gen, gen_iter};
+        $group.bench_function(stringify!($design), |b| b.iter(|| $body));
+    })*};
+}
+
+fn iter(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("iter");
+
+    compare!(group, for x in black_box(gen_iter::<u32, _, _>(rng.clone(), ..).take(100)) {
+        black_box(x);
+    });
+    group.bench_function("original", |b| b.iter(|| {
+        for x in black_box(rng.clone().gen_iter::<u32>().take(100)) {
+            black_box(x);
+        }
+    }));
+    group.finish();
+}
+
+fn iter__noiterbb(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("iter__noiterbb");
+
+    compare!(group, for x in gen_iter::<u32, _, _>(rng.clone(), ..).take(100) {
+        black_box(x);
+    });
+    group.bench_function("original", |b| b.iter(|| {
+        for x in rng.clone().gen_iter::<u32>().take(100) {
+            black_box(x);
+        }
+    }));
+    group.finish();
+}
+
+fn range_iter__bb(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_iter__bb");
+
+    compare!(group, for x in black_box(gen_iter::<u32, _, _>(rng.clone(), black_box(4..321)).take(100)) {
+        black_box(x);
+    });
+    group.finish();
+}
+
+fn range_iter(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_iter");
+
+    compare!(group, for x in black_box(gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100)) {
+        black_box(x);
+    });
+    group.finish();
+}
+
+fn range_iter__noiterbb(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_iter__noiterbb");
+
+    compare!(group, for x in gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100) {
+        black_box(x);
+    });
+    group.finish();
+}
+
+fn gen_(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("gen_");
+
+    compare!(group, for _ in 4..321 {
+        black_box(gen::<u32, _, _>(&mut rng, ..));
+    });
+    group.bench_function("original", |b| b.iter(|| {
+        for _ in 4..321 {
+            black_box(rng.gen::<u32>());
+        }
+    }));
+    group.finish();
+}
+
+fn range_gen__bb(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_gen__bb");
+
+    compare!(group, for _ in 4..321 {
+        black_box(gen::<u32, _, _>(&mut rng, black_box(4..321)));
+    });
+    group.finish();
+}
+
+fn range_gen(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_gen");
+
+    compare!(group, for _ in 4..321 {
+        black_box(gen::<u32, _, _>(&mut rng, 4..321));
+    });
+    group.finish();
+}
+
+/// `range_gen` with the bounds as constants, which only `stream`
+/// supports.
+fn range_gen__const(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_gen__const");
+
+    compare!(group, for _ in 4..321 {
+        black_box(gen::<u32, _, _>(&mut rng, rand_sketch::stream::ConstRange::<4, 321>));
+    }; stream);
+    group.finish();
+}
+
+/// `range_gen` with the stream built once by `cached_stream!`, which
+/// only `stream` supports.
+fn range_gen__cached(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_gen__cached");
+
+    compare!(group, for _ in 4..321 {
+        black_box(rand_sketch::cached_stream!(u32, 4..321).next(&mut rng));
+    }; stream);
+    group.finish();
+}
+
+/// `range_gen` with the stream looked up by `gen_memo`, which only
+/// `stream` supports.
+fn range_gen__memo(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_gen__memo");
+
+    compare!(group, for _ in 4..321 {
+        black_box(rand_sketch::stream::gen_memo::<u32, _, _>(&mut rng, 4..321));
+    }; stream);
+    group.finish();
+}
+
+/// `range_iter`, appending the values to a vector that's reused, with
+/// `sample_n_into`, which only `stream` supports.
+fn range_iter__into(s: &mut Suite) {
+    let mut rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_iter__into");
+    let mut values = Vec::with_capacity(100);
+
+    compare!(group, {
+        values.clear();
+        rand_sketch::stream::sample_n_into(&<u32 as rand_sketch::stream::Rand<_>>::rand(black_box(4..321)),
+                                           &mut rng, &mut values, 100);
+        black_box(&values);
+    }; stream);
+    group.finish();
+}
+
+/// `range_iter` for a power-of-two range of `u64`, which never rejects
+/// a draw.
+fn range_iter__pow2_u64(s: &mut Suite) {
+    let rng: XorShiftRng = rand::random();
+    let mut group = s.group("range_iter__pow2_u64");
+
+    compare!(group, for x in black_box(gen_iter::<u64, _, _>(rng.clone(), 0u64..1 << 40).take(100)) {
+        black_box(x);
+    });
+    group.finish();
+}
+
+/// `gen` and `gen_iter` for `$t` with the full range, a `$narrow`
+/// range and a `$wide` one (where rejection is more likely), as the
+/// groups `<bench>__<type>`, e.g. `range_gen__wide_u8`.
+///
+/// (The ranges for `u64` and `i64` need explicit types, since `assoc`
+/// uses the same constraint type for both.)
+macro_rules! matrix {
+    ($($name: ident: $t: ident, $narrow: expr, $wide: expr;)*) => {$(
+        fn $name(s: &mut Suite) {
+            let mut rng: XorShiftRng = rand::random();
+            let t = stringify!($t);
+
+            let mut group = s.group(format!("gen__{}", t));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<$t, _, _>(&mut rng, ..));
+            });
+            group.bench_function("original", |b| b.iter(|| {
+                for _ in 4..321 {
+                    black_box(rng.gen::<$t>());
+                }
+            }));
+            group.finish();
+
+            let mut group = s.group(format!("range_gen__{}", t));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<$t, _, _>(&mut rng, $narrow));
+            });
+            group.finish();
+
+            let mut group = s.group(format!("range_gen__wide_{}", t));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<$t, _, _>(&mut rng, $wide));
+            });
+            group.finish();
+
+            let mut group = s.group(format!("iter__{}", t));
+            compare!(group, for x in black_box(gen_iter::<$t, _, _>(rng.clone(), ..).take(100)) {
+                black_box(x);
+            });
+            group.bench_function("original", |b| b.iter(|| {
+                for x in black_box(rng.clone().gen_iter::<$t>().take(100)) {
+                    black_box(x);
+                }
+            }));
+            group.finish();
+
+            let mut group = s.group(format!("range_iter__{}", t));
+            compare!(group, for x in black_box(gen_iter::<$t, _, _>(rng.clone(), $narrow).take(100)) {
+                black_box(x);
+            });
+            group.finish();
+
+            let mut group = s.group(format!("range_iter__wide_{}", t));
+            compare!(group, for x in black_box(gen_iter::<$t, _, _>(rng.clone(), $wide).take(100)) {
+                black_box(x);
+            });
+            group.finish();
+        }
+    )*}
+}
+
+matrix! {
+    matrix_u8: u8, 4..21, 0..192;
+    matrix_u64: u64, 4u64..321, 0u64..3 << 62;
+    matrix_i64: i64, -160i64..161, i64::MIN / 2..i64::MAX;
+    matrix_f32: f32, 4.0..321.0, -1e30..1e30;
+    matrix_f64: f64, 4.0..321.0, -1e300..1e300;
+}
+
+/// `gen` and `gen_iter` for `$t` with a `$range` whose width is just
+/// above a power of two, which is the worst case for rejection
+/// sampling, as the groups `<bench>__worst_<type>`.
+macro_rules! worst_case {
+    ($($name: ident: $t: ident, $range: expr;)*) => {$(
+        fn $name(s: &mut Suite) {
+            let mut rng: XorShiftRng = rand::random();
+            let t = stringify!($t);
+
+            let mut group = s.group(format!("range_gen__worst_{}", t));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<$t, _, _>(&mut rng, $range));
+            });
+            group.finish();
+
+            let mut group = s.group(format!("range_iter__worst_{}", t));
+            compare!(group, for x in black_box(gen_iter::<$t, _, _>(rng.clone(), $range).take(100)) {
+                black_box(x);
+            });
+            group.finish();
+        }
+    )*}
+}
+
+worst_case! {
+    worst_u8: u8, 0..129;
+    worst_u32: u32, 0..(1 << 31) + 1;
+    worst_u64: u64, 0u64..(1 << 63) + 1;
+    worst_i64: i64, i64::MIN..1;
+}
+
+/// The `u32` benchmarks with RNG `$rng` as the groups
+/// `<bench>__<rng>`, e.g. `range_iter__chacha`, to see how the designs
+/// compare when the underlying generator is faster or slower.
+///
+/// The iterators borrow the RNG, since not all of them are `Clone`.
+macro_rules! backends {
+    ($($name: ident: $label: expr, $rng: expr;)*) => {$(
+        fn $name(s: &mut Suite) {
+            let mut rng = $rng;
+
+            let mut group = s.group(format!("gen__{}", $label));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<u32, _, _>(&mut rng, ..));
+            });
+            group.bench_function("original", |b| b.iter(|| {
+                for _ in 4..321 {
+                    black_box(rng.gen::<u32>());
+                }
+            }));
+            group.finish();
+
+            let mut group = s.group(format!("range_gen__{}", $label));
+            compare!(group, for _ in 4..321 {
+                black_box(gen::<u32, _, _>(&mut rng, 4..321));
+            });
+            group.finish();
+
+            let mut group = s.group(format!("iter__{}", $label));
+            compare!(group, for x in black_box(gen_iter::<u32, _, _>(&mut rng, ..).take(100)) {
+                black_box(x);
+            });
+            group.bench_function("original", |b| b.iter(|| {
+                for x in black_box(rng.gen_iter::<u32>().take(100)) {
+                    black_box(x);
+                }
+            }));
+            group.finish();
+
+            let mut group = s.group(format!("range_iter__{}", $label));
+            compare!(group, for x in black_box(gen_iter::<u32, _, _>(&mut rng, 4..321).take(100)) {
+                black_box(x);
+            });
+            group.finish();
+        }
+    )*}
+}
+
+backends! {
+    backend_xorshift: "xorshift", rand::random::<XorShiftRng>();
+    backend_pcg: "pcg", rand::random::<Pcg32>();
+    backend_chacha: "chacha", rand::random::<ChaChaRng>();
+    backend_os: "os", OsRng::new().unwrap();
+}
+
+type Bench = fn(&mut Suite);
+
+/// The benchmarks, in the order they appear in the table.
+const BENCHES: &[Bench] = &[
+    gen_,
+    iter,
+    iter__noiterbb,
+    range_gen,
+    range_gen__const,
+    range_gen__cached,
+    range_gen__memo,
+    range_gen__bb,
+    range_iter,
+    range_iter__bb,
+    range_iter__noiterbb,
+    range_iter__into,
+    range_iter__pow2_u64,
+    matrix_u8,
+    matrix_u64,
+    matrix_i64,
+    matrix_f32,
+    matrix_f64,
+    worst_u8,
+    worst_u32,
+    worst_u64,
+    worst_i64,
+    backend_xorshift,
+    backend_pcg,
+    backend_chacha,
+    backend_os,
+];
+
+/// Where criterion stores its results, following the same rules as
+/// criterion itself (minus asking `cargo metadata`).
+fn criterion_home() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        PathBuf::from(home)
+    } else if let Some(target) = env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(target).join("criterion")
+    } else {
+        PathBuf::from("target/criterion")
+    }
+}
+
+/// The mean time (in ns) of `group/design`, along with the upper
+/// bound of its confidence interval.
+fn estimate(group: &str, design: &str) -> Option<(f64, f64)> {
+    let path = criterion_home().join(group).join(design).join("new/estimates.json");
+    let contents = fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&contents).ok()?;
+
+    let mean = &json["mean"];
+    Some((mean["point_estimate"].as_f64()?,
+          mean["confidence_interval"]["upper_bound"].as_f64()?))
+}
+
+/// Render the results as a Markdown table, with the fastest design(s)
+/// for each benchmark in bold: those that are indistinguishable from
+/// the fastest, i.e. within its confidence interval.
+///
+/// Returns `None` if nothing has been measured.
+fn table(rows: &[String]) -> Option<String> {
+    let mut out = String::new();
+    let mut any = false;
+
+    writeln!(out, "| bench| {} |", DESIGNS.join(" | ")).unwrap();
+    writeln!(out, "|---|{}", "---|".repeat(DESIGNS.len())).unwrap();
+    for name in rows {
+        let row = DESIGNS.iter().map(|d| estimate(name, d)).collect::<Vec<_>>();
+        let best = row.iter()
+                      .filter_map(|e| *e)
+                      .fold(None, |best: Option<(f64, f64)>, e| match best {
+                          Some(b) if b.0 <= e.0 => Some(b),
+                          _ => Some(e),
+                      });
+
+        write!(out, "| `{}` |", name).unwrap();
+        for e in &row {
+            match (*e, best) {
+                (Some((mean, _)), Some((_, upper))) => {
+                    any = true;
+                    if mean <= upper {
+                        write!(out, " **{:.0}** |", mean).unwrap();
+                    } else {
+                        write!(out, " {:.0} |", mean).unwrap();
+                    }
+                }
+                _ => out.push_str("  |"),
+            }
+        }
+        out.push('\n');
+    }
+
+    if any { Some(out) } else { None }
+}
+
+fn main() {
+    let mut suite = Suite {
+        c: Criterion::default().configure_from_args(),
+        rows: vec![],
+    };
+    for bench in BENCHES {
+        bench(&mut suite);
+    }
+    suite.c.final_summary();
+
+    if let Some(table) = table(&suite.rows) {
+        let path = criterion_home().join("comparison.md");
+        println!("\n{}", table);
+        match fs::write(&path, table) {
+            Ok(()) => println!("wrote {}", path.display()),
+            Err(e) => println!("could not write {}: {}", path.display(), e),
+        }
+    }
+}
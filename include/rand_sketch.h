@@ -0,0 +1,41 @@
+/* The C interface from `src/ffi.rs`, built with the `ffi` feature. */
+
+#ifndef RAND_SKETCH_H
+#define RAND_SKETCH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct rand_sketch_rng rand_sketch_rng;
+typedef struct rand_sketch_u32_range rand_sketch_u32_range;
+typedef struct rand_sketch_i64_range rand_sketch_i64_range;
+typedef struct rand_sketch_f64_range rand_sketch_f64_range;
+
+rand_sketch_rng *rand_sketch_rng_new(uint64_t state, uint64_t stream);
+void rand_sketch_rng_free(rand_sketch_rng *rng);
+
+/* Each returns null if `low >= high` (or, for doubles, either isn't finite). */
+rand_sketch_u32_range *rand_sketch_u32_range_new(uint32_t low, uint32_t high);
+void rand_sketch_u32_range_fill(const rand_sketch_u32_range *stream, rand_sketch_rng *rng,
+                                uint32_t *out, size_t len);
+void rand_sketch_u32_range_free(rand_sketch_u32_range *stream);
+
+rand_sketch_i64_range *rand_sketch_i64_range_new(int64_t low, int64_t high);
+void rand_sketch_i64_range_fill(const rand_sketch_i64_range *stream, rand_sketch_rng *rng,
+                                int64_t *out, size_t len);
+void rand_sketch_i64_range_free(rand_sketch_i64_range *stream);
+
+rand_sketch_f64_range *rand_sketch_f64_range_new(double low, double high);
+void rand_sketch_f64_range_fill(const rand_sketch_f64_range *stream, rand_sketch_rng *rng,
+                                double *out, size_t len);
+void rand_sketch_f64_range_free(rand_sketch_f64_range *stream);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
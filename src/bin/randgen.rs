@@ -0,0 +1,181 @@
+//! Random values from the command line, one per line, e.g.
+//!
+//! ```text
+//! randgen u32 4..321 --count 1000 --seed abc
+//! ```
+//!
+//! The type is one of `u8`, `u32`, `u64`, `i64`, `f32` or `f64`, and
+//! the constraint (by default `..`) one of `..`, `low..high`, `low..`,
+//! `..high` or `..=high`, as for the `stream` design (so floats don't
+//! support `low..`). With `--seed`, the values come from a `Pcg32`
+//! seeded with the FNV-1a hash of the seed, so are reproducible;
+//! without, from `thread_rng`.
+//!
+//! With `--bytes <n>` (and no type or constraint), it writes `n` bytes
+//! of the generator's raw output instead, e.g. to pipe into an
+//! external test suite (see `testing::emit_bytes`).
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use rand_sketch::pcg::Pcg32;
+use rand_sketch::stream::{Rand, RandStream};
+use rand_sketch::testing::emit_bytes;
+use rand_sketch::thread::thread_rng;
+use std::env;
+use std::fmt::Display;
+use std::io::{self, BufWriter, Write};
+use std::process;
+use std::str::FromStr;
+
+const USAGE: &str = "usage: randgen <type> [<constraint>] [--count <n>] [--seed <string>]\n       \
+                     randgen --bytes <n> [--seed <string>]";
+
+struct Args {
+    /// The number of raw bytes to write, instead of values.
+    bytes: Option<u64>,
+    ty: String,
+    constraint: String,
+    count: u64,
+    seed: Option<String>,
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut positional = vec![];
+    let mut count = 10;
+    let mut seed = None;
+    let mut bytes = None;
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--count" => {
+                let n = args.next().ok_or("--count needs a value")?;
+                count = n.parse().map_err(|_| format!("invalid count `{}`", n))?;
+            }
+            "--seed" => seed = Some(args.next().ok_or("--seed needs a value")?),
+            "--bytes" => {
+                let n = args.next().ok_or("--bytes needs a value")?;
+                bytes = Some(n.parse().map_err(|_| format!("invalid byte count `{}`", n))?);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+            _ => positional.push(arg),
+        }
+    }
+    let mut positional = positional.into_iter();
+    if bytes.is_some() {
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument `{}` with --bytes", extra))
+        }
+        return Ok(Args { bytes, ty: String::new(), constraint: String::new(), count, seed })
+    }
+    let ty = positional.next().ok_or("no type given")?;
+    let constraint = positional.next().unwrap_or_else(|| "..".to_string());
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument `{}`", extra))
+    }
+    Ok(Args { bytes, ty, constraint, count, seed })
+}
+
+/// The forms of constraint the command line accepts.
+enum Constraint<T> {
+    Full,
+    Range(T, T),
+    From(T),
+    To(T),
+    ToInclusive(T),
+}
+
+fn parse_constraint<T: FromStr + PartialOrd>(s: &str) -> Result<Constraint<T>, String> {
+    let parse = |x: &str| x.parse().map_err(|_| format!("invalid bound `{}`", x));
+    let (start, end) = match s.find("..") {
+        Some(i) => (&s[..i], &s[i + 2..]),
+        None => return Err(format!("invalid constraint `{}`", s)),
+    };
+    Ok(match (start, end) {
+        ("", "") => Constraint::Full,
+        ("", end) if end.starts_with('=') => Constraint::ToInclusive(parse(&end[1..])?),
+        ("", end) => Constraint::To(parse(end)?),
+        (start, "") => Constraint::From(parse(start)?),
+        (start, end) => {
+            let (start, end) = (parse(start)?, parse(end)?);
+            if start >= end {
+                return Err(format!("empty range `{}`", s))
+            }
+            Constraint::Range(start, end)
+        }
+    })
+}
+
+fn print<T: Display, S: RandStream<T>, R: Rng>(stream: S, rng: &mut R, count: u64) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    for _ in 0..count {
+        writeln!(out, "{}", stream.next(rng))?;
+    }
+    out.flush()
+}
+
+/// Print the values, for a type supporting all the constraints.
+macro_rules! integer {
+    ($t: ident, $args: expr, $rng: expr) => {
+        match parse_constraint::<$t>(&$args.constraint)? {
+            Constraint::Full => print($t::rand(..), $rng, $args.count),
+            Constraint::Range(start, end) => print($t::rand(start..end), $rng, $args.count),
+            Constraint::From(start) => print($t::rand(start..), $rng, $args.count),
+            Constraint::To(end) => print($t::rand(..end), $rng, $args.count),
+            Constraint::ToInclusive(end) => print($t::rand(..=end), $rng, $args.count),
+        }
+    }
+}
+
+/// Print the values, for a float type, which has no `low..`.
+macro_rules! float {
+    ($t: ident, $args: expr, $rng: expr) => {
+        match parse_constraint::<$t>(&$args.constraint)? {
+            Constraint::Full => print($t::rand(..), $rng, $args.count),
+            Constraint::Range(start, end) => print($t::rand(start..end), $rng, $args.count),
+            Constraint::From(_) => return Err(format!("`low..` isn't supported for {}", $args.ty)),
+            Constraint::To(end) => print($t::rand(..end), $rng, $args.count),
+            Constraint::ToInclusive(end) => print($t::rand(..=end), $rng, $args.count),
+        }
+    }
+}
+
+fn generate<R: Rng>(args: &Args, rng: &mut R) -> Result<(), String> {
+    let written = if let Some(n) = args.bytes {
+        let stdout = io::stdout();
+        emit_bytes(rng, stdout.lock(), n)
+    } else {
+        match &*args.ty {
+            "u8" => integer!(u8, args, rng),
+            "u32" => integer!(u32, args, rng),
+            "u64" => integer!(u64, args, rng),
+            "i64" => integer!(i64, args, rng),
+            "f32" => float!(f32, args, rng),
+            "f64" => float!(f64, args, rng),
+            ty => return Err(format!("unsupported type `{}`", ty)),
+        }
+    };
+    match written {
+        // E.g. piped into `head`, which is fine.
+        Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(format!("could not write: {}", e)),
+        Ok(()) => Ok(()),
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x100000001b3))
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(|args| match args.seed {
+        Some(ref seed) => generate(&args, &mut Pcg32::new(fnv1a(seed.as_bytes()), 0)),
+        None => generate(&args, &mut thread_rng()),
+    });
+    if let Err(e) = result {
+        eprintln!("randgen: {}\n{}", e, USAGE);
+        process::exit(2);
+    }
+}
@@ -0,0 +1,66 @@
+//! Random colours, as `[red, green, blue]` bytes, e.g. for the series
+//! of a plot.
+
+use float::Float;
+use rand::Rng;
+
+/// The fraction of a turn the hue moves between `Distinct` colours:
+/// `1 / φ`, which spreads any number of hues about evenly around the
+/// wheel, never repeating.
+const GOLDEN_TURN: f64 = 0.618_033_988_749_894_8;
+
+/// The lightnesses `Distinct` cycles through, so colours whose hues
+/// end up close still differ.
+const LIGHTNESSES: [f64; 3] = [0.5, 0.35, 0.65];
+
+/// A uniformly random colour.
+pub fn rgb<R: Rng>(rng: &mut R) -> [u8; 3] {
+    let mut rgb = [0; 3];
+    rng.fill_bytes(&mut rgb);
+    rgb
+}
+
+/// The colour with hue `hue` (in turns, i.e. `[0, 1)` for the whole
+/// wheel), and saturation and lightness `saturation` and `lightness`
+/// (in `[0, 1]`).
+pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let a = saturation * lightness.min(1.0 - lightness);
+    let channel = |n: f64| {
+        let k = (n + hue * 12.0) % 12.0;
+        let k = if k < 0.0 { k + 12.0 } else { k };
+        let value = lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        (value * 255.0 + 0.5) as u8
+    };
+    [channel(0.0), channel(8.0), channel(4.0)]
+}
+
+/// An endless sequence of colours that are easy to tell apart, from a
+/// random starting hue, e.g. `color::distinct(rng).take(n)` for `n`
+/// series.
+pub fn distinct<R: Rng>(rng: &mut R) -> Distinct {
+    Distinct {
+        hue: f64::closed_open01(rng),
+        index: 0,
+    }
+}
+
+/// The iterator returned by `distinct`.
+///
+/// Each hue is a fixed step around the wheel from the last, with the
+/// saturation fixed, and the lightness cycling through three levels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distinct {
+    hue: f64,
+    index: usize,
+}
+
+impl Iterator for Distinct {
+    type Item = [u8; 3];
+
+    fn next(&mut self) -> Option<[u8; 3]> {
+        let color = hsl(self.hue, 0.7, LIGHTNESSES[self.index % LIGHTNESSES.len()]);
+        self.hue = (self.hue + GOLDEN_TURN) % 1.0;
+        self.index = self.index.wrapping_add(1);
+        Some(color)
+    }
+}
@@ -0,0 +1,86 @@
+//! Dates and times, from `chrono`, e.g. for fuzzing date-handling
+//! code.
+//!
+//! Each range is converted to a whole number of days (for dates) or
+//! seconds (for times) when the stream is created, so generating is
+//! just an integer range and an addition.
+
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
+use rand::Rng;
+use std::ops::Range;
+use stream::{IntegerStreamBounded, Rand, RandStream};
+
+/// Values `start` plus a whole number of days or seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffsetStream<T> {
+    start: T,
+    offset: IntegerStreamBounded<u64>,
+}
+
+/// The number of multiples of `unit` below `width`, i.e. `ceil(width
+/// / unit)`: the offsets from the start of a range that come before
+/// its end.
+fn steps(width: TimeDelta, unit: TimeDelta) -> u64 {
+    assert!(width > TimeDelta::zero(), "range must be non-empty");
+    let whole = width.num_seconds() / unit.num_seconds();
+    let partial = width > TimeDelta::seconds(whole * unit.num_seconds());
+    (whole + partial as i64) as u64
+}
+
+/// Days in `start..end`, e.g. `NaiveDate::rand(start..end)`.
+impl Rand<Range<NaiveDate>> for NaiveDate {
+    type Stream = OffsetStream<NaiveDate>;
+    fn rand(dist: Range<NaiveDate>) -> OffsetStream<NaiveDate> {
+        let days = steps(dist.end - dist.start, TimeDelta::days(1));
+        OffsetStream {
+            start: dist.start,
+            offset: u64::rand(0..days),
+        }
+    }
+}
+
+impl RandStream<NaiveDate> for OffsetStream<NaiveDate> {
+    fn next<R: Rng>(&self, rng: &mut R) -> NaiveDate {
+        self.start + Days::new(self.offset.next(rng))
+    }
+}
+
+/// Times in `start..end`, a whole number of seconds after `start`
+/// (so with its fraction of a second).
+impl Rand<Range<NaiveDateTime>> for NaiveDateTime {
+    type Stream = OffsetStream<NaiveDateTime>;
+    fn rand(dist: Range<NaiveDateTime>) -> OffsetStream<NaiveDateTime> {
+        let seconds = steps(dist.end - dist.start, TimeDelta::seconds(1));
+        OffsetStream {
+            start: dist.start,
+            offset: u64::rand(0..seconds),
+        }
+    }
+}
+
+impl RandStream<NaiveDateTime> for OffsetStream<NaiveDateTime> {
+    fn next<R: Rng>(&self, rng: &mut R) -> NaiveDateTime {
+        self.start + TimeDelta::seconds(self.offset.next(rng) as i64)
+    }
+}
+
+/// Instants in `start..end`, a whole number of seconds after `start`,
+/// in `start`'s time zone. The seconds are real elapsed seconds, so
+/// each instant is equally likely, however the local time jumps
+/// around daylight saving changes.
+impl<Tz: TimeZone> Rand<Range<DateTime<Tz>>> for DateTime<Tz> {
+    type Stream = OffsetStream<DateTime<Tz>>;
+    fn rand(dist: Range<DateTime<Tz>>) -> OffsetStream<DateTime<Tz>> {
+        let seconds = steps(dist.end.signed_duration_since(&dist.start), TimeDelta::seconds(1));
+        OffsetStream {
+            start: dist.start,
+            offset: u64::rand(0..seconds),
+        }
+    }
+}
+
+impl<Tz: TimeZone> RandStream<DateTime<Tz>> for OffsetStream<DateTime<Tz>> {
+    fn next<R: Rng>(&self, rng: &mut R) -> DateTime<Tz> {
+        self.start.clone() + TimeDelta::seconds(self.offset.next(rng) as i64)
+    }
+}
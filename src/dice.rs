@@ -0,0 +1,160 @@
+//! Tabletop dice rolls described by standard notation, e.g. `"3d6+2"`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use stream::{IntegerStreamCompact, Rand, RandStream};
+
+/// A sum of dice rolls and constants, parsed from notation such as
+/// `"3d6+2"` (three six-sided dice plus two), `"d20"`, `"2d8-1d4"` or
+/// `"d%"` (a hundred-sided die).
+///
+/// Each term is an optional count (default one) then `d` and the
+/// number of sides (or `%` for 100), or a constant, and terms are
+/// joined by `+` or `-`. The first may have a sign, and whitespace
+/// between them is ignored.
+///
+/// Parsing does all the precomputation, so `Dice` is its own stream:
+/// `"3d6+2".parse::<Dice>()?.next(rng)` or `rng.random::<i64,
+/// _>(dice)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dice {
+    groups: Vec<Group>,
+    constant: i64,
+}
+
+/// `count` dice with values `1..=faces.width()`, added or subtracted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Group {
+    count: u32,
+    faces: IntegerStreamCompact<u32>,
+    negative: bool,
+}
+
+impl Dice {
+    /// The smallest possible total.
+    pub fn min(&self) -> i64 {
+        self.groups.iter().fold(self.constant, |total, group| {
+            let (low, high) = (i64::from(group.count), group.max());
+            total + if group.negative { -high } else { low }
+        })
+    }
+
+    /// The largest possible total.
+    pub fn max(&self) -> i64 {
+        self.groups.iter().fold(self.constant, |total, group| {
+            let (low, high) = (i64::from(group.count), group.max());
+            total + if group.negative { -low } else { high }
+        })
+    }
+}
+
+impl Group {
+    fn max(&self) -> i64 {
+        i64::from(self.count) * i64::from(self.faces.width())
+    }
+}
+
+impl FromStr for Dice {
+    type Err = ParseDiceError;
+
+    fn from_str(s: &str) -> Result<Dice, ParseDiceError> {
+        let mut dice = Dice { groups: Vec::new(), constant: 0 };
+        let mut rest = s.trim_start();
+        if rest.is_empty() {
+            return Err(ParseDiceError(ErrorKind::Empty))
+        }
+        loop {
+            let mut negative = false;
+            if let Some(r) = rest.strip_prefix('-') {
+                negative = !negative;
+                rest = r.trim_start();
+            } else if let Some(r) = rest.strip_prefix('+') {
+                rest = r.trim_start();
+            }
+
+            let (count, r) = number(rest)?;
+            rest = r;
+            if let Some(r) = rest.strip_prefix(|c| c == 'd' || c == 'D') {
+                let (sides, r) = match r.strip_prefix('%') {
+                    Some(r) => (Some(100), r),
+                    None => number(r)?,
+                };
+                rest = r;
+                let sides = sides.ok_or(ParseDiceError(ErrorKind::Invalid))?;
+                if sides == 0 {
+                    return Err(ParseDiceError(ErrorKind::NoSides))
+                }
+                dice.groups.push(Group {
+                    count: count.unwrap_or(1),
+                    faces: u32::rand(0..sides),
+                    negative,
+                });
+            } else {
+                let constant = i64::from(count.ok_or(ParseDiceError(ErrorKind::Invalid))?);
+                dice.constant += if negative { -constant } else { constant };
+            }
+
+            rest = rest.trim_start();
+            match rest.chars().next() {
+                None => return Ok(dice),
+                Some('+') | Some('-') => {}
+                Some(_) => return Err(ParseDiceError(ErrorKind::Invalid)),
+            }
+        }
+    }
+}
+
+/// The leading decimal number of `s`, if any, and what's after it.
+fn number(s: &str) -> Result<(Option<u32>, &str), ParseDiceError> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return Ok((None, s))
+    }
+    let n = s[..end].parse().map_err(|_| ParseDiceError(ErrorKind::TooLarge))?;
+    Ok((Some(n), &s[end..]))
+}
+
+/// Why dice notation couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDiceError(ErrorKind);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ErrorKind {
+    Empty,
+    Invalid,
+    NoSides,
+    TooLarge,
+}
+
+impl fmt::Display for ParseDiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self.0 {
+            ErrorKind::Empty => "no dice given",
+            ErrorKind::Invalid => "invalid dice notation",
+            ErrorKind::NoSides => "dice must have at least one side",
+            ErrorKind::TooLarge => "number too large in dice notation",
+        })
+    }
+}
+
+impl Error for ParseDiceError {}
+
+impl Rand<Dice> for i64 {
+    type Stream = Dice;
+    fn rand(dist: Dice) -> Dice {
+        dist
+    }
+}
+
+impl RandStream<i64> for Dice {
+    fn next<R: Rng>(&self, rng: &mut R) -> i64 {
+        self.groups.iter().fold(self.constant, |total, group| {
+            let sum = (0..group.count).map(|_| i64::from(group.faces.next(rng)) + 1).sum::<i64>();
+            total + if group.negative { -sum } else { sum }
+        })
+    }
+}
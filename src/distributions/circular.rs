@@ -0,0 +1,74 @@
+//! Angles, in radians in `[0, 2π)`, for headings and other directions.
+//!
+//! Each is sampled on the real line and then wrapped around the
+//! circle, so a range or spread crossing zero needs no special case.
+//! For uniform angles around the whole circle, see `rotation::Angle`.
+
+use distributions::normal::standard_normal;
+use float::Float;
+use rand::Rng;
+use std::f64::consts::TAU;
+use stream::{Rand, RandStream};
+
+/// `angle` wrapped into `[0, 2π)`.
+fn wrap(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(TAU);
+    // A tiny negative angle rounds up to `2π` itself.
+    if wrapped == TAU { 0.0 } else { wrapped }
+}
+
+/// Angles uniformly distributed along the arc of `width` radians
+/// anticlockwise from `start`, e.g. `CircularUniform { start: 350f64
+/// .to_radians(), width: 20f64.to_radians() }` for within ten degrees
+/// of north. This needs no precomputation, so it's its own stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CircularUniform {
+    pub start: f64,
+    pub width: f64,
+}
+
+impl Rand<CircularUniform> for f64 {
+    type Stream = CircularUniform;
+    /// # Panics
+    ///
+    /// If `start` isn't finite, or `width` isn't in `(0, 2π]`.
+    fn rand(dist: CircularUniform) -> CircularUniform {
+        assert!(dist.start.is_finite(), "start must be finite");
+        assert!(0.0 < dist.width && dist.width <= TAU, "width must be in (0, 2π]");
+        dist
+    }
+}
+
+impl RandStream<f64> for CircularUniform {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        wrap(self.start + self.width * f64::closed_open01(rng))
+    }
+}
+
+/// Normally distributed angles, with mean `mean` and standard
+/// deviation `sd` (both in radians) before wrapping, e.g. for a
+/// heading with noise. With `sd` much above `π`, this is close to
+/// uniform. This needs no precomputation, so it's its own stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WrappedNormal {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl Rand<WrappedNormal> for f64 {
+    type Stream = WrappedNormal;
+    /// # Panics
+    ///
+    /// If `mean` isn't finite, or `sd` isn't positive and finite.
+    fn rand(dist: WrappedNormal) -> WrappedNormal {
+        assert!(dist.mean.is_finite(), "mean must be finite");
+        assert!(dist.sd > 0.0 && dist.sd.is_finite(), "standard deviation must be positive and finite");
+        dist
+    }
+}
+
+impl RandStream<f64> for WrappedNormal {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        wrap(self.mean + self.sd * standard_normal(rng))
+    }
+}
@@ -0,0 +1,162 @@
+//! Correlated values: pairs of normals, or any marginal distributions
+//! joined by a Gaussian copula.
+
+use distributions::normal::{normal_cdf, standard_normal};
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// Pairs of standard normals with correlation `rho`, e.g.
+/// `<(f64, f64)>::rand(correlated_normals(0.8))`: the first is a
+/// normal `z1`, and the second `rho z1 + sqrt(1 - rho²) z2` for an
+/// independent `z2`. (Scale and shift each for other means and
+/// standard deviations; `GaussianCopula` is the general version.)
+///
+/// # Panics
+///
+/// If `rho` isn't in `[-1, 1]`.
+pub fn correlated_normals(rho: f64) -> CorrelatedNormals {
+    assert!((-1.0..=1.0).contains(&rho), "correlation must be in [-1, 1]");
+    CorrelatedNormals {
+        rho,
+        complement: (1.0 - rho * rho).sqrt(),
+    }
+}
+
+/// The constraint returned by `correlated_normals`, which is its own
+/// stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CorrelatedNormals {
+    rho: f64,
+    /// `sqrt(1 - rho²)`.
+    complement: f64,
+}
+
+impl Rand<CorrelatedNormals> for (f64, f64) {
+    type Stream = CorrelatedNormals;
+    fn rand(dist: CorrelatedNormals) -> CorrelatedNormals {
+        dist
+    }
+}
+
+impl RandStream<(f64, f64)> for CorrelatedNormals {
+    fn next<R: Rng>(&self, rng: &mut R) -> (f64, f64) {
+        let (z1, z2) = (standard_normal(rng), standard_normal(rng));
+        (z1, self.rho * z1 + self.complement * z2)
+    }
+}
+
+/// Arrays of `N` values whose `i`th element comes from the stream
+/// `marginals[i]`, with the dependence between them of normals with
+/// the correlation matrix `correlation`, e.g. for losses that tend to
+/// come together.
+///
+/// Each array starts as `N` standard normals, correlated by
+/// multiplying by the Cholesky factor of `correlation` (computed when
+/// the stream is created). Each is mapped to a uniform `u` in `[0, 1]`
+/// by the normal CDF, and then to a value of its marginal by drawing
+/// from its stream with `u` (as a 64-bit fraction) as the first draw
+/// from the generator.
+///
+/// So the values have the marginal distributions exactly, and the
+/// dependence of the copula exactly, for streams whose values are a
+/// non-decreasing function of their first draw: float ranges,
+/// `Distribution::from_quantile` and `Empirical::interpolated`, say.
+/// Other streams (such as integer ranges, which reject some draws)
+/// still give their own distributions, but with the dependence
+/// weakened. The correlation is of the underlying normals: the values'
+/// rank correlation is `6/π asin(ρ/2)`, a little less.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianCopula<S, const N: usize> {
+    pub correlation: [[f64; N]; N],
+    pub marginals: [S; N],
+}
+
+/// The Cholesky factor of the correlation matrix, and the marginal
+/// streams.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianCopulaStream<S, const N: usize> {
+    /// Lower triangular, with `lower * lower^T = correlation`.
+    lower: [[f64; N]; N],
+    marginals: [S; N],
+}
+
+impl<S: RandStream<f64>, const N: usize> Rand<GaussianCopula<S, N>> for [f64; N] {
+    type Stream = GaussianCopulaStream<S, N>;
+    /// # Panics
+    ///
+    /// If `correlation` isn't a correlation matrix: symmetric and
+    /// positive definite, with ones on the diagonal.
+    fn rand(dist: GaussianCopula<S, N>) -> GaussianCopulaStream<S, N> {
+        let GaussianCopula { correlation, marginals } = dist;
+        for (i, row) in correlation.iter().enumerate() {
+            assert!(row[i] == 1.0, "correlations must have ones on the diagonal");
+            for (j, &c) in row[..i].iter().enumerate() {
+                assert!(c == correlation[j][i], "correlations must be symmetric");
+                assert!((-1.0..=1.0).contains(&c), "correlations must be in [-1, 1]");
+            }
+        }
+        GaussianCopulaStream {
+            lower: cholesky(&correlation),
+            marginals,
+        }
+    }
+}
+
+/// The lower triangular `L` with `L L^T = matrix`, by the
+/// Cholesky-Banachiewicz algorithm.
+fn cholesky<const N: usize>(matrix: &[[f64; N]; N]) -> [[f64; N]; N] {
+    let mut lower = [[0.0; N]; N];
+    for i in 0..N {
+        for j in 0..=i {
+            let dot = (0..j).map(|k| lower[i][k] * lower[j][k]).sum::<f64>();
+            if i == j {
+                let square = matrix[i][i] - dot;
+                assert!(square > 0.0, "correlations must be positive definite");
+                lower[i][i] = square.sqrt();
+            } else {
+                lower[i][j] = (matrix[i][j] - dot) / lower[j][j];
+            }
+        }
+    }
+    lower
+}
+
+impl<S: RandStream<f64>, const N: usize> RandStream<[f64; N]> for GaussianCopulaStream<S, N> {
+    fn next<R: Rng>(&self, rng: &mut R) -> [f64; N] {
+        let mut normals = [0.0; N];
+        for z in &mut normals {
+            *z = standard_normal(rng);
+        }
+        let mut values = [0.0; N];
+        for ((value, row), marginal) in values.iter_mut().zip(&self.lower).zip(&self.marginals) {
+            let x = row.iter().zip(&normals).map(|(l, z)| l * z).sum::<f64>();
+            // `2^64 u`, saturating at `u = 1`.
+            let draw = (normal_cdf(x) * 18446744073709551616.0) as u64;
+            *value = marginal.next(&mut Prefixed { draw: Some(draw), rng: &mut *rng });
+        }
+        values
+    }
+}
+
+/// Gives `draw` first (its top half, for a 32-bit draw), then passes
+/// through to `rng`.
+struct Prefixed<'a, R: 'a> {
+    draw: Option<u64>,
+    rng: &'a mut R,
+}
+
+impl<'a, R: Rng> Rng for Prefixed<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        match self.draw.take() {
+            Some(draw) => (draw >> 32) as u32,
+            None => self.rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.draw.take() {
+            Some(draw) => draw,
+            None => self.rng.next_u64(),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Resampling measured data, without fitting a model to it.
+
+use distributions::normal::standard_normal;
+use float::Float;
+use rand::Rng;
+use seq::gen_index;
+use stream::{Rand, RandStream};
+
+/// Values drawn from a set of samples, e.g. measured latencies or
+/// sizes, by inverting their empirical CDF. The samples are sorted
+/// once, when this is built, so it's its own stream.
+///
+/// `from_samples` gives the samples themselves, each equally likely
+/// (like bootstrapping), while `interpolated` gives values anywhere
+/// between the smallest and largest, by interpolating linearly between
+/// consecutive samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Empirical {
+    sorted: Vec<f64>,
+    interpolate: bool,
+}
+
+impl Empirical {
+    /// Each of `samples`, equally likely (so repeated values are
+    /// proportionally more likely).
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is empty, or any is NaN.
+    pub fn from_samples(samples: &[f64]) -> Empirical {
+        Empirical {
+            sorted: sorted(samples),
+            interpolate: false,
+        }
+    }
+
+    /// Values from the CDF that rises linearly between consecutive
+    /// sorted samples, by `1 / (n - 1)` between each pair: the `i`th
+    /// smallest of `n` samples is the `i / (n - 1)` quantile.
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is empty, or any is NaN or infinite.
+    pub fn interpolated(samples: &[f64]) -> Empirical {
+        assert!(samples.iter().all(|x| x.is_finite()), "samples must be finite");
+        Empirical {
+            sorted: sorted(samples),
+            interpolate: true,
+        }
+    }
+
+    /// The samples, in increasing order.
+    pub fn samples(&self) -> &[f64] {
+        &self.sorted
+    }
+}
+
+/// `samples`, sorted.
+fn sorted(samples: &[f64]) -> Vec<f64> {
+    assert!(!samples.is_empty(), "need at least one sample");
+    assert!(!samples.iter().any(|x| x.is_nan()), "samples must not be NaN");
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+impl Rand<Empirical> for f64 {
+    type Stream = Empirical;
+    fn rand(dist: Empirical) -> Empirical {
+        dist
+    }
+}
+
+impl RandStream<f64> for Empirical {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        let n = self.sorted.len();
+        if !self.interpolate || n == 1 {
+            return self.sorted[gen_index(n, rng)];
+        }
+        let position = f64::closed_open01(rng) * (n - 1) as f64;
+        let i = position as usize;
+        let (low, high) = (self.sorted[i], self.sorted[i + 1]);
+        low + (position - i as f64) * (high - low)
+    }
+}
+
+/// Values from a kernel density estimate of a set of samples, with a
+/// normal kernel: a uniformly random sample, plus normal noise with
+/// standard deviation `bandwidth`. This smooths `Empirical`'s
+/// resampling, giving values near the samples rather than only the
+/// samples themselves, with a variance larger by `bandwidth^2`.
+///
+/// It needs no precomputation, so it's its own stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kde {
+    samples: Vec<f64>,
+    bandwidth: f64,
+}
+
+impl Kde {
+    /// The estimate from `samples`, smoothed by `bandwidth`.
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is empty, or any isn't finite, or `bandwidth` isn't
+    /// positive and finite.
+    pub fn new(samples: &[f64], bandwidth: f64) -> Kde {
+        assert!(!samples.is_empty(), "need at least one sample");
+        assert!(samples.iter().all(|x| x.is_finite()), "samples must be finite");
+        assert!(bandwidth > 0.0 && bandwidth.is_finite(), "bandwidth must be positive and finite");
+        Kde {
+            samples: samples.to_vec(),
+            bandwidth,
+        }
+    }
+}
+
+impl Rand<Kde> for f64 {
+    type Stream = Kde;
+    fn rand(dist: Kde) -> Kde {
+        dist
+    }
+}
+
+impl RandStream<f64> for Kde {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        let sample = self.samples[gen_index(self.samples.len(), rng)];
+        sample + self.bandwidth * standard_normal(rng)
+    }
+}
@@ -0,0 +1,84 @@
+//! The generalized extreme value distribution, of the maxima of many
+//! values, e.g. yearly peak floods or the strongest of a batch of
+//! components.
+
+use float::Float;
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// Generalized extreme value (GEV) values, with location `location`,
+/// scale `scale` and shape `shape`, by inverting the CDF `exp(-(1 +
+/// shape (x - location) / scale)^(-1 / shape))`.
+///
+/// The shape picks one of three families, decided when the stream is
+/// created:
+///
+/// - `shape == 0` is the Gumbel distribution, unbounded, with
+///   exponentially light tails;
+/// - `shape > 0` is the Fréchet distribution, bounded below at
+///   `location - scale / shape`, with a heavy upper tail (only moments
+///   below `1 / shape` exist);
+/// - `shape < 0` is the (reversed) Weibull distribution, bounded above
+///   at `location - scale / shape`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gev {
+    pub location: f64,
+    pub scale: f64,
+    pub shape: f64,
+}
+
+/// The parameters, and the family.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GevStream {
+    location: f64,
+    scale: f64,
+    shape: f64,
+    family: Family,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Family {
+    Gumbel,
+    /// Values are clamped to the bound, which rounding could otherwise
+    /// cross.
+    Frechet { low: f64 },
+    Weibull { high: f64 },
+}
+
+impl Rand<Gev> for f64 {
+    type Stream = GevStream;
+    /// # Panics
+    ///
+    /// If `location` or `shape` isn't finite, or `scale` isn't
+    /// positive and finite.
+    fn rand(dist: Gev) -> GevStream {
+        let Gev { location, scale, shape } = dist;
+        assert!(location.is_finite(), "location must be finite");
+        assert!(scale > 0.0 && scale.is_finite(), "scale must be positive and finite");
+        assert!(shape.is_finite(), "shape must be finite");
+        let bound = location - scale / shape;
+        let family = if shape == 0.0 {
+            Family::Gumbel
+        } else if shape > 0.0 {
+            Family::Frechet { low: bound }
+        } else {
+            Family::Weibull { high: bound }
+        };
+        GevStream { location, scale, shape, family }
+    }
+}
+
+impl RandStream<f64> for GevStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        // `ln(-ln u)`, the log of a standard exponential value.
+        let t = (-f64::open01(rng).ln()).ln();
+        // `((-ln u)^(-shape) - 1) / shape`, without cancelling for
+        // small shapes.
+        let x = |shape: f64| self.location + self.scale * (-shape * t).exp_m1() / shape;
+        match self.family {
+            Family::Gumbel => self.location - self.scale * t,
+            Family::Frechet { low } => x(self.shape).max(low),
+            Family::Weibull { high } => x(self.shape).min(high),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Continuous distributions over `f64`, for simulation and statistics,
+//! e.g. `f64::rand(Normal { mean: 10.0, sd: 2.0 })`.
+//!
+//! Each validates its parameters, and precomputes what it can, when
+//! its stream is created.
+
+mod circular;
+mod copula;
+mod empirical;
+mod extreme;
+mod gamma;
+mod normal;
+mod order;
+mod piecewise;
+mod quantile;
+mod simplex;
+mod stable;
+
+pub use self::circular::{CircularUniform, WrappedNormal};
+pub use self::copula::{correlated_normals, CorrelatedNormals, GaussianCopula, GaussianCopulaStream};
+pub use self::empirical::{Empirical, Kde};
+pub use self::extreme::{Gev, GevStream};
+pub use self::normal::{LogitNormal, Normal, SkewNormal, SkewNormalStream, TruncatedNormal, TruncatedNormalStream};
+pub use self::order::{OrderStatistic, OrderStatisticStream};
+pub use self::piecewise::{PiecewiseConstant, PiecewiseLinear, PiecewiseStream};
+pub use self::quantile::Distribution;
+pub use self::simplex::{random_probability_vector, random_stochastic_matrix, Dirichlet, DirichletStream};
+pub use self::stable::{Stable, StableStream};
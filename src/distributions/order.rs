@@ -0,0 +1,52 @@
+//! Order statistics of uniform values, without generating the values.
+
+use distributions::gamma::StandardGamma;
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// The `k`th smallest of `n` independent values uniform in `[0, 1]`,
+/// e.g. `OrderStatistic { n: 1000, k: 1000 }` for the largest of a
+/// thousand, for simulating records and extremes.
+///
+/// Rather than generating `n` values and sorting them, this draws one
+/// value from the `Beta(k, n - k + 1)` distribution, which is the
+/// distribution of that order statistic, as `x / (x + y)` for gamma
+/// values `x` and `y` with shapes `k` and `n - k + 1`. So it takes the
+/// same time for any `n`. (For another distribution, apply its
+/// quantile function to the result: it's non-decreasing, so keeps the
+/// order.)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderStatistic {
+    pub n: u64,
+    pub k: u64,
+}
+
+/// The gamma distributions for the values below and above the order
+/// statistic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrderStatisticStream {
+    below: StandardGamma,
+    above: StandardGamma,
+}
+
+impl Rand<OrderStatistic> for f64 {
+    type Stream = OrderStatisticStream;
+    /// # Panics
+    ///
+    /// If `k` isn't in `1..=n`.
+    fn rand(dist: OrderStatistic) -> OrderStatisticStream {
+        let OrderStatistic { n, k } = dist;
+        assert!(1 <= k && k <= n, "k must be from 1 to n");
+        OrderStatisticStream {
+            below: StandardGamma::new(k as f64),
+            above: StandardGamma::new((n - k + 1) as f64),
+        }
+    }
+}
+
+impl RandStream<f64> for OrderStatisticStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        let x = self.below.sample(rng);
+        x / (x + self.above.sample(rng))
+    }
+}
@@ -0,0 +1,115 @@
+//! Densities drawn by hand: piecewise constant or linear over a list
+//! of points, for any one-dimensional shape without a formula.
+
+use float::Float;
+use rand::Rng;
+use stream::{Rand, RandStream};
+use weighted::AliasTable;
+
+/// Values in the bins between consecutive `bin_edges`, with the
+/// probability of each bin proportional to its entry in `weights`,
+/// and uniform within it, e.g. `PiecewiseConstant { bin_edges:
+/// vec![0.0, 1.0, 10.0], weights: vec![3.0, 1.0] }` for values below
+/// `1` three times as often as values above.
+///
+/// Note that the weights are of the bins, not the density: a wider bin
+/// with the same weight has a lower density.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseConstant {
+    pub bin_edges: Vec<f64>,
+    pub weights: Vec<f64>,
+}
+
+/// Values between consecutive `points`, with the density at each point
+/// proportional to its entry in `densities`, and linear in between,
+/// e.g. `PiecewiseLinear { points: vec![0.0, 1.0, 3.0], densities:
+/// vec![0.0, 2.0, 0.0] }` for a triangle peaking at `1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseLinear {
+    pub points: Vec<f64>,
+    pub densities: Vec<f64>,
+}
+
+/// The pieces, chosen in proportion to their areas by an alias table,
+/// built when the stream is created, and then sampled within.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseStream {
+    points: Vec<f64>,
+    /// The density at each end of each piece, or `None` where it's
+    /// constant.
+    densities: Option<Vec<f64>>,
+    pieces: AliasTable,
+}
+
+/// Panics unless there are at least two `points`, finite and strictly
+/// increasing, and `len` `values`, finite and non-negative.
+fn check_pieces(points: &[f64], values: &[f64], len: usize) {
+    assert!(points.len() >= 2, "need at least two points");
+    assert_eq!(values.len(), len, "wrong number of weights or densities");
+    assert!(points.iter().all(|x| x.is_finite()), "points must be finite");
+    assert!(points.windows(2).all(|w| w[0] < w[1]), "points must be strictly increasing");
+    assert!(values.iter().all(|&v| v >= 0.0 && v.is_finite()), "values must be non-negative and finite");
+}
+
+impl Rand<PiecewiseConstant> for f64 {
+    type Stream = PiecewiseStream;
+    /// # Panics
+    ///
+    /// If there are fewer than two edges, they aren't finite and
+    /// strictly increasing, there isn't a weight for each bin, or the
+    /// weights aren't non-negative and finite, with a positive sum.
+    fn rand(dist: PiecewiseConstant) -> PiecewiseStream {
+        let PiecewiseConstant { bin_edges, weights } = dist;
+        check_pieces(&bin_edges, &weights, bin_edges.len().saturating_sub(1));
+        PiecewiseStream {
+            pieces: AliasTable::new(&weights),
+            points: bin_edges,
+            densities: None,
+        }
+    }
+}
+
+impl Rand<PiecewiseLinear> for f64 {
+    type Stream = PiecewiseStream;
+    /// # Panics
+    ///
+    /// If there are fewer than two points, they aren't finite and
+    /// strictly increasing, there isn't a density for each point, or
+    /// the densities aren't non-negative and finite, with some piece
+    /// having a positive area.
+    fn rand(dist: PiecewiseLinear) -> PiecewiseStream {
+        let PiecewiseLinear { points, densities } = dist;
+        check_pieces(&points, &densities, points.len());
+        let areas = points.windows(2).zip(densities.windows(2)).map(|(x, d)| {
+            (x[1] - x[0]) * (d[0] + d[1]) / 2.0
+        }).collect::<Vec<_>>();
+        PiecewiseStream {
+            pieces: AliasTable::new(&areas),
+            points,
+            densities: Some(densities),
+        }
+    }
+}
+
+impl RandStream<f64> for PiecewiseStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        let i = self.pieces.sample(rng);
+        let (low, high) = (self.points[i], self.points[i + 1]);
+        let u = f64::closed_open01(rng);
+        let t = match self.densities {
+            None => u,
+            Some(ref densities) => {
+                // Inverting the CDF `(d0 t + (d1 - d0) t^2 / 2) / ((d0
+                // + d1) / 2)` of the linear density, in the form that
+                // doesn't divide by `d1 - d0`.
+                // It's zero only at `u = 0` with `d0 = 0`, where `t` is
+                // too.
+                let (d0, d1) = (densities[i], densities[i + 1]);
+                let denominator = d0 + (d0 * d0 + u * (d1 * d1 - d0 * d0)).sqrt();
+                if denominator > 0.0 { u * (d0 + d1) / denominator } else { 0.0 }
+            }
+        };
+        // The top of a piece (rounding aside) belongs to the next.
+        (low + t * (high - low)).min(high.next_down())
+    }
+}
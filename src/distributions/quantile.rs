@@ -0,0 +1,39 @@
+//! Distributions given by their quantile functions.
+
+use float::Float;
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// Values `quantile(u)` for `u` uniform in `(0, 1)`, e.g.
+/// `f64::rand(Distribution::from_quantile(|u: f64| -u.ln()))` for the
+/// exponential distribution: inverse transform sampling, for any
+/// distribution this crate doesn't have, given its inverse CDF.
+///
+/// `u` is never `0` or `1`, so quantile functions that are infinite
+/// there (as for any unbounded distribution) needn't check for them.
+/// The closure needs no precomputation, so this is its own stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Distribution<F> {
+    quantile: F,
+}
+
+impl<F: Fn(f64) -> f64> Distribution<F> {
+    /// The distribution with quantile function (inverse CDF)
+    /// `quantile`, which should be non-decreasing on `(0, 1)`.
+    pub fn from_quantile(quantile: F) -> Distribution<F> {
+        Distribution { quantile }
+    }
+}
+
+impl<F: Fn(f64) -> f64> Rand<Distribution<F>> for f64 {
+    type Stream = Distribution<F>;
+    fn rand(dist: Distribution<F>) -> Distribution<F> {
+        dist
+    }
+}
+
+impl<F: Fn(f64) -> f64> RandStream<f64> for Distribution<F> {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        (self.quantile)(f64::open01(rng))
+    }
+}
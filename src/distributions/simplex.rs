@@ -0,0 +1,94 @@
+//! Points on the probability simplex, `n` non-negative values summing
+//! to one, e.g. for mixture weights, and matrices of them, for Markov
+//! chains.
+
+use distributions::gamma::StandardGamma;
+use float::Float;
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// A uniformly random probability vector of `n` values: non-negative,
+/// summing to one, with every such vector equally likely, e.g. for
+/// the weights of an `n`-component mixture.
+///
+/// The values are the gaps between `n - 1` sorted uniform values in
+/// `[0, 1]` (and the ends), which is the same as `Dirichlet(vec![1.0;
+/// n])` but needs no gamma values.
+///
+/// # Panics
+///
+/// If `n` is zero.
+pub fn random_probability_vector<R: Rng>(n: usize, rng: &mut R) -> Vec<f64> {
+    assert!(n > 0, "probability vectors need at least one value");
+    let mut cuts = (1..n).map(|_| f64::closed_open01(rng)).collect::<Vec<_>>();
+    cuts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    cuts.push(1.0);
+    let mut last = 0.0;
+    cuts.into_iter().map(|cut| {
+        let gap = cut - last;
+        last = cut;
+        gap
+    }).collect()
+}
+
+/// A random `n` by `n` row-stochastic matrix, each row a uniformly
+/// random probability vector, e.g. for the initial transitions of a
+/// `markov::MarkovChain` to fit.
+///
+/// # Panics
+///
+/// If `n` is zero.
+pub fn random_stochastic_matrix<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    (0..n).map(|_| random_probability_vector(n, rng)).collect()
+}
+
+/// Probability vectors from the Dirichlet distribution with
+/// concentrations `alphas`, one value per concentration, e.g.
+/// `Vec::<f64>::rand(Dirichlet(vec![2.0, 1.0, 1.0]))` for weights
+/// whose first averages a half.
+///
+/// The `i`th value averages `alphas[i] / Σ alphas`. Larger
+/// concentrations keep the values nearer to their averages, while
+/// concentrations below one push most of the weight onto a few values.
+/// Each vector is `n` gamma values with the concentrations as shapes,
+/// divided by their sum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dirichlet(pub Vec<f64>);
+
+/// A gamma distribution for each value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirichletStream {
+    gammas: Vec<StandardGamma>,
+}
+
+impl Rand<Dirichlet> for Vec<f64> {
+    type Stream = DirichletStream;
+    /// # Panics
+    ///
+    /// If there are no concentrations, or any isn't positive and
+    /// finite.
+    fn rand(dist: Dirichlet) -> DirichletStream {
+        assert!(!dist.0.is_empty(), "need at least one concentration");
+        assert!(dist.0.iter().all(|&a| a > 0.0 && a.is_finite()), "concentrations must be positive and finite");
+        DirichletStream {
+            gammas: dist.0.iter().map(|&a| StandardGamma::new(a)).collect(),
+        }
+    }
+}
+
+impl RandStream<Vec<f64>> for DirichletStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        // Scaled by the largest in log space, so tiny concentrations
+        // can't underflow every value to zero.
+        let mut values = self.gammas.iter().map(|g| g.ln_sample(rng)).collect::<Vec<_>>();
+        let largest = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        for value in &mut values {
+            *value = (*value - largest).exp();
+        }
+        let total = values.iter().sum::<f64>();
+        for value in &mut values {
+            *value /= total;
+        }
+        values
+    }
+}
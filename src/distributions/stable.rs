@@ -0,0 +1,121 @@
+//! Lévy alpha-stable distributions, the heavy-tailed limits of sums of
+//! many values, e.g. for returns in finance.
+
+use distributions::normal::standard_normal;
+use float::Float;
+use rand::Rng;
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
+use stream::{Rand, RandStream};
+
+/// Stable values with stability `alpha`, skewness `beta`, scale
+/// `scale` and location `location`, in the usual parameterization
+/// (Samorodnitsky and Taqqu's `S(α, β, σ, μ)`), by the
+/// Chambers-Mallows-Stuck method (1976): a trigonometric function of
+/// a uniform angle and an exponential value.
+///
+/// `alpha` in `(0, 2]` sets the tails, which fall off like `|x|^-(1 +
+/// alpha)` (so there's no variance below 2, and no mean at or below 1),
+/// and `beta` in `[-1, 1]` skews them right or left. The cases with
+/// simpler forms are sampled directly:
+///
+/// - `alpha == 2` is the normal distribution with mean `location` and
+///   standard deviation `scale sqrt(2)`, whatever `beta` is;
+/// - `alpha == 1` and `beta == 0` is the Cauchy distribution, with
+///   median `location` and half-width `scale`;
+/// - `alpha == 0.5` and `beta == ±1` is the Lévy distribution, starting
+///   at `location` (and reflected for `-1`), as `location + scale /
+///   z^2` for standard normals `z`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stable {
+    pub alpha: f64,
+    pub beta: f64,
+    pub scale: f64,
+    pub location: f64,
+}
+
+/// The location and scale, and how to sample the standardized value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StableStream {
+    location: f64,
+    scale: f64,
+    method: Method,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Method {
+    Normal,
+    Cauchy,
+    /// `sign` is `beta`.
+    Levy { sign: f64 },
+    /// `alpha == 1`, `beta != 0`, with the shift `2/π beta ln(scale)`
+    /// (which this parameterization has only at `alpha == 1`) folded
+    /// into the location.
+    Unit { beta: f64 },
+    /// `alpha != 1`, with `shift = atan(beta tan(πα/2))`, and `factor =
+    /// (1 + beta^2 tan^2(πα/2))^(1/(2α))`.
+    General { alpha: f64, shift: f64, factor: f64 },
+}
+
+impl Rand<Stable> for f64 {
+    type Stream = StableStream;
+    /// # Panics
+    ///
+    /// If `alpha` isn't in `(0, 2]`, `beta` isn't in `[-1, 1]`,
+    /// `scale` isn't positive and finite, or `location` isn't finite.
+    fn rand(dist: Stable) -> StableStream {
+        let Stable { alpha, beta, scale, location } = dist;
+        assert!(0.0 < alpha && alpha <= 2.0, "alpha must be in (0, 2]");
+        assert!((-1.0..=1.0).contains(&beta), "beta must be in [-1, 1]");
+        assert!(scale > 0.0 && scale.is_finite(), "scale must be positive and finite");
+        assert!(location.is_finite(), "location must be finite");
+        let (location, method) = if alpha == 2.0 {
+            (location, Method::Normal)
+        } else if alpha == 1.0 && beta == 0.0 {
+            (location, Method::Cauchy)
+        } else if alpha == 0.5 && beta.abs() == 1.0 {
+            (location, Method::Levy { sign: beta })
+        } else if alpha == 1.0 {
+            (location + 2.0 / PI * beta * scale * scale.ln(), Method::Unit { beta })
+        } else {
+            let tan = beta * (PI * alpha / 2.0).tan();
+            let method = Method::General {
+                alpha,
+                shift: tan.atan(),
+                factor: (1.0 + tan * tan).powf(1.0 / (2.0 * alpha)),
+            };
+            (location, method)
+        };
+        StableStream { location, scale, method }
+    }
+}
+
+impl RandStream<f64> for StableStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        let x = match self.method {
+            Method::Normal => SQRT_2 * standard_normal(rng),
+            Method::Cauchy => (PI * (f64::open01(rng) - 0.5)).tan(),
+            Method::Levy { sign } => {
+                let z = standard_normal(rng);
+                sign / (z * z)
+            }
+            Method::Unit { beta } => {
+                let (v, w) = angle_and_exponential(rng);
+                let weighted = FRAC_PI_2 + beta * v;
+                2.0 / PI * (weighted * v.tan() - beta * (FRAC_PI_2 * w * v.cos() / weighted).ln())
+            }
+            Method::General { alpha, shift, factor } => {
+                let (v, w) = angle_and_exponential(rng);
+                let turned = alpha * v + shift;
+                factor * turned.sin() / v.cos().powf(1.0 / alpha)
+                    * ((v - turned).cos() / w).powf((1.0 - alpha) / alpha)
+            }
+        };
+        self.location + self.scale * x
+    }
+}
+
+/// A uniform angle in `(-π/2, π/2)`, and a standard exponential value.
+fn angle_and_exponential<R: Rng>(rng: &mut R) -> (f64, f64) {
+    let v = PI * (f64::open01(rng) - 0.5);
+    (v, -f64::open01(rng).ln())
+}
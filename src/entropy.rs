@@ -0,0 +1,105 @@
+//! Seeding generators from a source of entropy other than the
+//! operating system, e.g. a hardware random number generator
+//! peripheral on a microcontroller, for `no_std` targets.
+
+use pcg::Pcg32;
+use splitmix::SplitMix64;
+#[cfg(feature = "std")]
+use rand::{OsRng, Rng};
+use rand::{ChaChaRng, SeedableRng, XorShiftRng};
+#[cfg(feature = "std")]
+use std::convert::Infallible;
+
+/// Something that can fill a buffer with unpredictable bytes, such as
+/// a hardware random number generator, which may fail (e.g. a
+/// peripheral reporting a health-check failure).
+pub trait EntropySource {
+    type Error;
+
+    /// Fill all of `dest`, or fail.
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+impl<E: EntropySource + ?Sized> EntropySource for &mut E {
+    type Error = E::Error;
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), E::Error> {
+        (**self).fill(dest)
+    }
+}
+
+#[cfg(feature = "std")]
+impl EntropySource for OsRng {
+    type Error = Infallible;
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), Infallible> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The browser's `crypto.getRandomValues`, for
+/// `wasm32-unknown-unknown` (with the `wasm` feature), where there's
+/// no operating system to provide an `OsRng`.
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Clone, Copy, Debug)]
+pub struct WebCrypto;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+impl EntropySource for WebCrypto {
+    type Error = getrandom::Error;
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), getrandom::Error> {
+        getrandom::getrandom(dest)
+    }
+}
+
+/// Generators that can be seeded from an `EntropySource`, e.g.
+/// `Pcg32::from_entropy(&mut trng)`.
+pub trait FromEntropy: Sized {
+    fn from_entropy<E: EntropySource>(source: &mut E) -> Result<Self, E::Error>;
+}
+
+/// `N` little-endian words from `source`.
+fn words<const N: usize, E: EntropySource>(source: &mut E) -> Result<[u32; N], E::Error> {
+    let mut words = [0; N];
+    for word in words.iter_mut() {
+        let mut bytes = [0; 4];
+        source.fill(&mut bytes)?;
+        *word = u32::from_le_bytes(bytes);
+    }
+    Ok(words)
+}
+
+impl FromEntropy for Pcg32 {
+    fn from_entropy<E: EntropySource>(source: &mut E) -> Result<Pcg32, E::Error> {
+        let (mut state, mut stream) = ([0; 8], [0; 8]);
+        source.fill(&mut state)?;
+        source.fill(&mut stream)?;
+        Ok(Pcg32::new(u64::from_le_bytes(state), u64::from_le_bytes(stream)))
+    }
+}
+
+impl FromEntropy for SplitMix64 {
+    fn from_entropy<E: EntropySource>(source: &mut E) -> Result<SplitMix64, E::Error> {
+        let mut state = [0; 8];
+        source.fill(&mut state)?;
+        Ok(SplitMix64::new(u64::from_le_bytes(state)))
+    }
+}
+
+impl FromEntropy for XorShiftRng {
+    /// The all-zero state is invalid, so this draws again until it
+    /// gets another.
+    fn from_entropy<E: EntropySource>(source: &mut E) -> Result<XorShiftRng, E::Error> {
+        loop {
+            let seed = words::<4, _>(source)?;
+            if seed != [0; 4] {
+                return Ok(XorShiftRng::from_seed(seed))
+            }
+        }
+    }
+}
+
+impl FromEntropy for ChaChaRng {
+    fn from_entropy<E: EntropySource>(source: &mut E) -> Result<ChaChaRng, E::Error> {
+        Ok(ChaChaRng::from_seed(&words::<8, _>(source)?))
+    }
+}
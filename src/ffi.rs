@@ -0,0 +1,91 @@
+//! A C interface to seeded generators and range streams, so C and C++
+//! code can share the crate's sampling (declared in
+//! `include/rand_sketch.h`).
+//!
+//! Build a library to link against with e.g. `cargo rustc --release
+//! --features ffi --crate-type staticlib`.
+//!
+//! Everything is behind an opaque pointer from a `_new` function,
+//! which must be passed to the matching `_free` function exactly once.
+//! Constructors return null for invalid arguments, rather than
+//! panicking across the language boundary.
+
+use pcg::Pcg32;
+use std::ops::Range;
+use std::ptr;
+use std::slice;
+use stream::{Rand, RandStream};
+
+/// A `Pcg32` from `state` and `stream`, as for `Pcg32::new`.
+#[no_mangle]
+pub extern "C" fn rand_sketch_rng_new(state: u64, stream: u64) -> *mut Pcg32 {
+    Box::into_raw(Box::new(Pcg32::new(state, stream)))
+}
+
+/// # Safety
+///
+/// `rng` must be null, or from `rand_sketch_rng_new` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn rand_sketch_rng_free(rng: *mut Pcg32) {
+    if !rng.is_null() {
+        drop(Box::from_raw(rng));
+    }
+}
+
+/// For each `$t` (named `$name` in C), `rand_sketch_$name_range_new`,
+/// `_fill` and `_free`, for a stream of `$t`s in `low..high`, where
+/// `$valid` says whether `low` and `high` are acceptable.
+macro_rules! ffi_ranges {
+    ($($t: ident, $new: ident, $fill: ident, $free: ident, $valid: expr;)*) => {$(
+        /// A stream of values in `[low, high)`, or null if that's
+        /// empty (or, for floats, not finite).
+        #[no_mangle]
+        pub extern "C" fn $new(low: $t, high: $t) -> *mut <$t as Rand<Range<$t>>>::Stream {
+            let valid: fn($t, $t) -> bool = $valid;
+            if valid(low, high) {
+                Box::into_raw(Box::new($t::rand(low..high)))
+            } else {
+                ptr::null_mut()
+            }
+        }
+
+        /// Write `len` values from `stream` to `out`, using `rng`.
+        ///
+        /// # Safety
+        ///
+        /// `stream` and `rng` must be live values from the matching
+        /// `_new` functions, not in use elsewhere, and `out` must be
+        /// valid for `len` writes (or anything, if `len` is zero).
+        #[no_mangle]
+        pub unsafe extern "C" fn $fill(stream: *const <$t as Rand<Range<$t>>>::Stream,
+                                       rng: *mut Pcg32, out: *mut $t, len: usize) {
+            if len == 0 {
+                return
+            }
+            let (stream, rng) = (&*stream, &mut *rng);
+            for x in slice::from_raw_parts_mut(out, len) {
+                *x = stream.next(rng);
+            }
+        }
+
+        /// # Safety
+        ///
+        /// `stream` must be null, or from the matching `_new` function
+        /// and not yet freed.
+        #[no_mangle]
+        pub unsafe extern "C" fn $free(stream: *mut <$t as Rand<Range<$t>>>::Stream) {
+            if !stream.is_null() {
+                drop(Box::from_raw(stream));
+            }
+        }
+    )*}
+}
+
+ffi_ranges! {
+    u32, rand_sketch_u32_range_new, rand_sketch_u32_range_fill, rand_sketch_u32_range_free,
+        |low, high| low < high;
+    i64, rand_sketch_i64_range_new, rand_sketch_i64_range_fill, rand_sketch_i64_range_free,
+        |low, high| low < high;
+    f64, rand_sketch_f64_range_new, rand_sketch_f64_range_fill, rand_sketch_f64_range_free,
+        |low, high| low.is_finite() && high.is_finite() && low < high;
+}
@@ -0,0 +1,164 @@
+//! Sampling floats from the unit interval, shared by the designs.
+//!
+//! The intervals are built from the top `PRECISION` bits `m` of a
+//! single draw from the generator, where `PRECISION` is the number of
+//! significant bits of the type (24 for `f32`, 53 for `f64`), so
+//! exactly which endpoints can appear is known:
+//!
+//! - `[0, 1)` (`RangeFull`) is `m / 2^PRECISION`,
+//! - `(0, 1)` (`Open01`) is `(m' + 1/2) / 2^(PRECISION - 1)`, with one
+//!   bit fewer so that the `+ 1/2` is exact,
+//! - `(0, 1]` (`OpenClosed01`) is `1 - m / 2^PRECISION`,
+//! - `[0, 1]` (`Closed01`) is `m / (2^PRECISION - 1)`,
+//!
+//! all of which are exact except the last, which rounds to nearest
+//! (but hits both `0` and `1`).
+//!
+//! These are evenly spaced, so values near zero are much coarser than
+//! the type allows (e.g. `[0, 1)` never gives anything in `(0,
+//! 2^-53)` for `f64`). `HighPrecision01` instead picks the exponent
+//! with the right probability (by counting leading zeros) and then
+//! fills every bit of the mantissa, so each float in `[0, 1)` is
+//! chosen with probability equal to the width of the interval of
+//! reals that round down to it.
+//!
+//! Scaling to a range `start..end` as `start + x * width` has two
+//! problems: `width = end - start` can overflow to infinity (e.g. for
+//! `-MAX..MAX`), and the result can round up to `end`. `half_open`
+//! avoids the first by halving everything (exactly) when necessary,
+//! and the second by shrinking the width just enough that the largest
+//! `x` stays below `end`.
+
+use rand::Rng;
+
+pub trait Float: Copy {
+    /// Sample from `[0, 1)`.
+    fn closed_open01<R: Rng>(rng: &mut R) -> Self;
+    /// Sample from `(0, 1)`.
+    fn open01<R: Rng>(rng: &mut R) -> Self;
+    /// Sample from `(0, 1]`.
+    fn open_closed01<R: Rng>(rng: &mut R) -> Self;
+    /// Sample from `[0, 1]`.
+    fn closed01<R: Rng>(rng: &mut R) -> Self;
+    /// Sample from `[0, 1)`, using every representable value.
+    fn high_precision01<R: Rng>(rng: &mut R) -> Self;
+
+    /// The `(low, width, scale)` such that `(low + x * width) * scale`
+    /// lies in `start..end` (so is never `end`) for every `x` from
+    /// `closed_open01`.
+    ///
+    /// # Panics
+    ///
+    /// If `start` or `end` isn't finite, or `start >= end`.
+    fn half_open(start: Self, end: Self) -> (Self, Self, Self);
+
+    /// The width of `[0, end]`, i.e. `end`, to scale `closed01` by for
+    /// `..=end`.
+    ///
+    /// # Panics
+    ///
+    /// If `end` isn't finite, or is negative.
+    fn closed_width(end: Self) -> Self;
+}
+
+macro_rules! float_impls {
+    ($($t: ident, $bits: ident, $next: ident, $mantissa: expr, $bias: expr;)*) => {$(
+        impl Float for $t {
+            #[inline]
+            fn closed_open01<R: Rng>(rng: &mut R) -> $t {
+                const PRECISION: u32 = $mantissa + 1;
+                let m = rng.$next() >> ($bits::BITS - PRECISION);
+                m as $t / ((1 as $bits) << PRECISION) as $t
+            }
+
+            #[inline]
+            fn open01<R: Rng>(rng: &mut R) -> $t {
+                let m = rng.$next() >> ($bits::BITS - $mantissa);
+                (m as $t + 0.5) / ((1 as $bits) << $mantissa) as $t
+            }
+
+            #[inline]
+            fn open_closed01<R: Rng>(rng: &mut R) -> $t {
+                1.0 - <$t>::closed_open01(rng)
+            }
+
+            #[inline]
+            fn closed01<R: Rng>(rng: &mut R) -> $t {
+                const PRECISION: u32 = $mantissa + 1;
+                let max = ((1 as $bits) << PRECISION) - 1;
+                (rng.$next() >> ($bits::BITS - PRECISION)) as $t / max as $t
+            }
+
+            fn high_precision01<R: Rng>(rng: &mut R) -> $t {
+                // The value is `2^exponent * 1.mantissa`, where each
+                // leading zero bit of an infinite random binary
+                // fraction halves it.
+                let mut exponent = -1;
+                loop {
+                    let bits = rng.$next();
+                    if bits != 0 {
+                        exponent -= bits.leading_zeros() as i32;
+                        break
+                    }
+                    exponent -= $bits::BITS as i32;
+                    if exponent < -($bias + $mantissa) {
+                        // Below the smallest subnormal.
+                        return 0.0
+                    }
+                }
+                let mantissa = rng.$next() >> ($bits::BITS - $mantissa);
+
+                let min_exponent = 1 - $bias;
+                if exponent >= min_exponent {
+                    let biased = (exponent + $bias) as $bits;
+                    $t::from_bits(biased << $mantissa | mantissa)
+                } else {
+                    // Subnormal: shift in the implicit leading one,
+                    // rounding down.
+                    let shift = (min_exponent - exponent) as u32;
+                    let significand = (1 as $bits) << $mantissa | mantissa;
+                    $t::from_bits(significand.checked_shr(shift).unwrap_or(0))
+                }
+            }
+
+            fn half_open(start: $t, end: $t) -> ($t, $t, $t) {
+                assert!(start.is_finite() && end.is_finite(), "float range must be finite");
+                assert!(start < end, "float range must be non-empty");
+
+                let scale = if (end - start).is_finite() { 1.0 } else { 2.0 };
+                let low = start / scale;
+                let width = end / scale - low;
+
+                const PRECISION: u32 = $mantissa + 1;
+                let max = 1.0 - 1.0 / ((1 as $bits) << PRECISION) as $t;
+                let fits = |width: $t| (low + width * max) * scale < end;
+                if fits(width) {
+                    return (low, width, scale)
+                }
+                // Non-negative floats are ordered like their bits, so
+                // binary search those for the widest that fits (zero
+                // always does).
+                let (mut fit, mut unfit) = (0, width.to_bits());
+                while unfit - fit > 1 {
+                    let mid = fit + (unfit - fit) / 2;
+                    if fits($t::from_bits(mid)) {
+                        fit = mid
+                    } else {
+                        unfit = mid
+                    }
+                }
+                (low, $t::from_bits(fit), scale)
+            }
+
+            fn closed_width(end: $t) -> $t {
+                assert!(end.is_finite(), "float range must be finite");
+                assert!(0.0 <= end, "float range must be non-negative");
+                end
+            }
+        }
+    )*}
+}
+float_impls! {
+    f32, u32, next_u32, 23, 127;
+    f64, u64, next_u64, 52, 1023;
+}
@@ -0,0 +1,145 @@
+//! Structured values from a fuzzer's input, in the style of the
+//! `arbitrary` crate: the input bytes are the draws, so a
+//! coverage-guided fuzzer steers any stream by mutating them, e.g.
+//! `gen::<u32, _, _>(&mut Budgeted::new(data), 4..321)`.
+
+use rand::Rng;
+use seq::gen_index;
+use splitmix::SplitMix64;
+use std::convert::TryInto;
+use std::ops::Range;
+
+/// Draws read from `bytes`, little-endian, so the same input always
+/// gives the same values.
+///
+/// Once there are too few bytes left for a draw, the rest come from
+/// a `SplitMix64` seeded with 0, rather than (say) zeros: some
+/// streams reject draws and retry, and would retry a constant
+/// forever.
+#[derive(Clone, Debug)]
+pub struct SliceRng<'a> {
+    bytes: &'a [u8],
+    tail: SplitMix64,
+}
+
+impl<'a> SliceRng<'a> {
+    pub fn new(bytes: &'a [u8]) -> SliceRng<'a> {
+        SliceRng {
+            bytes,
+            tail: SplitMix64::new(0),
+        }
+    }
+
+    /// The number of input bytes not yet drawn.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether draws now come from the fallback generator.
+    pub fn is_exhausted(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The next `N` input bytes, or `None` (using up the input) if
+    /// there are fewer.
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        if self.bytes.len() < N {
+            self.bytes = &[];
+            return None;
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        head.try_into().ok()
+    }
+}
+
+impl<'a> Rng for SliceRng<'a> {
+    fn next_u32(&mut self) -> u32 {
+        match self.take() {
+            Some(bytes) => u32::from_le_bytes(bytes),
+            None => self.tail.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.take() {
+            Some(bytes) => u64::from_le_bytes(bytes),
+            None => self.tail.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let n = dest.len().min(self.bytes.len());
+        let (head, rest) = dest.split_at_mut(n);
+        head.copy_from_slice(&self.bytes[..n]);
+        self.bytes = &self.bytes[n..];
+        self.tail.fill_bytes(rest);
+    }
+}
+
+/// A `SliceRng` that also chooses collection sizes from what's left
+/// of the input, so a short input can't describe a huge value.
+///
+/// Draws pass through unchanged, and `len` gives the sizes: the
+/// largest size allowed falls in proportion to the fraction of the
+/// input used so far, down to the smallest once it's all used. Code
+/// generating a structure calls `len` for each collection, and the
+/// crate's streams for the elements.
+#[derive(Clone, Debug)]
+pub struct Budgeted<'a> {
+    rng: SliceRng<'a>,
+    initial: usize,
+}
+
+impl<'a> Budgeted<'a> {
+    pub fn new(bytes: &'a [u8]) -> Budgeted<'a> {
+        Budgeted {
+            rng: SliceRng::new(bytes),
+            initial: bytes.len(),
+        }
+    }
+
+    /// The number of input bytes not yet drawn.
+    pub fn remaining(&self) -> usize {
+        self.rng.remaining()
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.rng.is_exhausted()
+    }
+
+    /// A collection size in `range`, uniformly up to a limit: the
+    /// whole range with all the input left, scaling down linearly to
+    /// just `range.start` with none (which draws nothing).
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty.
+    pub fn len(&mut self, range: Range<usize>) -> usize {
+        assert!(range.start < range.end, "range of lengths must be non-empty");
+        let width = (range.end - range.start - 1) as u128;
+        let allowed = match self.initial {
+            0 => 0,
+            initial => (width * self.remaining() as u128 / initial as u128) as usize,
+        };
+        if allowed == 0 {
+            range.start
+        } else {
+            range.start + gen_index(allowed + 1, self)
+        }
+    }
+}
+
+impl<'a> Rng for Budgeted<'a> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+}
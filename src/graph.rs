@@ -0,0 +1,80 @@
+//! Random graphs, as lists of undirected edges between the vertices
+//! `0..n`.
+//!
+//! Each edge `(u, v)` has `u < v`, and appears once.
+
+use float::Float;
+use rand::Rng;
+use seq::gen_index;
+
+/// The Erdős–Rényi graph `G(n, p)`: each of the `n (n - 1) / 2`
+/// possible edges is present independently with probability `p`.
+///
+/// Like `seq::bernoulli_iter`, this draws the number of edges skipped
+/// between those present (Batagelj and Brandes' method), so it takes
+/// time proportional to `n` plus the number of edges, rather than to
+/// the number of possible edges. The edges are ordered by their
+/// larger vertex, then their smaller.
+///
+/// # Panics
+///
+/// If `p` isn't in `[0, 1]`.
+pub fn gnp<R: Rng>(n: u32, p: f64, rng: &mut R) -> Vec<(u32, u32)> {
+    assert!((0.0..=1.0).contains(&p), "probability must be in [0, 1]");
+    let mut edges = Vec::new();
+    if p == 0.0 {
+        return edges
+    }
+    let ln_q = (-p).ln_1p();
+    // The candidate edge is `(w, v)`, for `w < v`, where `w` counts
+    // from `-1` so the first skip lands on `(0, 1)`.
+    let (n, mut v, mut w) = (i64::from(n), 1, -1i64);
+    while v < n {
+        let u = f64::open_closed01(rng);
+        let skip = (u.ln() / ln_q).floor() as i64;
+        w = w.saturating_add(1).saturating_add(skip);
+        while w >= v && v < n {
+            w -= v;
+            v += 1;
+        }
+        if v < n {
+            edges.push((w as u32, v as u32));
+        }
+    }
+    edges
+}
+
+/// A Barabási–Albert preferential-attachment graph on `n` vertices.
+///
+/// Vertex `m` is joined to each of `0..m`, then each later vertex is
+/// joined to `m` distinct earlier ones, chosen with probability
+/// proportional to their degree. That gives `m (n - m)` edges, and a
+/// degree distribution with a power-law tail. The edges are ordered
+/// by their larger vertex, i.e. in the order they're added.
+///
+/// # Panics
+///
+/// If `m` is zero or not less than `n`.
+pub fn barabasi_albert<R: Rng>(n: u32, m: u32, rng: &mut R) -> Vec<(u32, u32)> {
+    assert!(m >= 1 && m < n, "need 1 <= m < n");
+    let mut edges = Vec::with_capacity(m as usize * (n - m) as usize);
+    // Each vertex appears once per edge it's in, so choosing uniformly
+    // from this chooses in proportion to degree.
+    let mut endpoints = Vec::with_capacity(2 * edges.capacity());
+    let mut targets = (0..m).collect::<Vec<_>>();
+    for v in m..n {
+        for &t in &targets {
+            edges.push((t, v));
+            endpoints.push(t);
+            endpoints.push(v);
+        }
+        targets.clear();
+        while targets.len() < m as usize {
+            let t = endpoints[gen_index(endpoints.len(), rng)];
+            if !targets.contains(&t) {
+                targets.push(t);
+            }
+        }
+    }
+    edges
+}
@@ -0,0 +1,111 @@
+//! Random identifiers: RFC 4122 version 4 UUIDs and ULIDs, as raw
+//! bytes or in their usual text forms.
+//!
+//! UUIDs are often used where guessing one would matter (e.g. as
+//! session or resource identifiers), so they're only drawn from a
+//! `CryptoRng`. ULIDs expose their creation time anyway, so they can
+//! use any generator.
+
+use rand::Rng;
+use secure::CryptoRng;
+use std::time::{SystemTime, UNIX_EPOCH};
+use stream::{Rand, RandStream};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+/// Crockford's base 32, which skips `I`, `L`, `O` and `U`.
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// A version 4 (random) UUID: 122 random bits, plus the version and
+/// variant bits.
+///
+/// This isn't a `Rand` impl, since streams accept any generator.
+pub fn uuid_v4<R: CryptoRng>(rng: &mut R) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    rng.fill_bytes(&mut bytes);
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    bytes
+}
+
+/// A version 4 UUID (see `uuid_v4`) in the hyphenated lowercase form,
+/// `"xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx"`.
+pub fn uuid_v4_string<R: CryptoRng>(rng: &mut R) -> String {
+    let mut s = String::with_capacity(36);
+    for (i, b) in uuid_v4(rng).iter().enumerate() {
+        if i == 4 || i == 6 || i == 8 || i == 10 {
+            s.push('-');
+        }
+        s.push(HEX[usize::from(b >> 4)] as char);
+        s.push(HEX[usize::from(b & 0xf)] as char);
+    }
+    s
+}
+
+/// ULIDs: a 48-bit timestamp in milliseconds since the Unix epoch,
+/// then 80 random bits, so they sort by time of creation (to the
+/// millisecond). E.g. `<[u8; 16]>::rand(Ulid::now())`, or
+/// `String::rand(Ulid::now())` for the 26-character Crockford base 32
+/// form.
+///
+/// Identifiers from the same millisecond are in random order: this
+/// doesn't implement the spec's optional monotonic mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ulid {
+    /// The fixed timestamp, or `None` to read the clock for each one.
+    millis: Option<u64>,
+}
+
+impl Ulid {
+    /// ULIDs timestamped when each is generated.
+    pub fn now() -> Ulid {
+        Ulid { millis: None }
+    }
+
+    /// ULIDs all timestamped `millis` milliseconds after the Unix
+    /// epoch, e.g. for reproducible tests.
+    ///
+    /// # Panics
+    ///
+    /// If `millis` doesn't fit in 48 bits.
+    pub fn at(millis: u64) -> Ulid {
+        assert!(millis >> 48 == 0, "ULID timestamp must fit in 48 bits");
+        Ulid { millis: Some(millis) }
+    }
+}
+
+// These need no precomputation, so `Ulid` is its own stream.
+impl Rand<Ulid> for [u8; 16] {
+    type Stream = Ulid;
+    fn rand(dist: Ulid) -> Ulid {
+        dist
+    }
+}
+impl Rand<Ulid> for String {
+    type Stream = Ulid;
+    fn rand(dist: Ulid) -> Ulid {
+        dist
+    }
+}
+
+impl RandStream<[u8; 16]> for Ulid {
+    fn next<R: Rng>(&self, rng: &mut R) -> [u8; 16] {
+        let millis = self.millis.unwrap_or_else(|| {
+            let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)
+                .expect("system clock is before the Unix epoch");
+            elapsed.as_millis() as u64 & ((1 << 48) - 1)
+        });
+        let mut bytes = [0; 16];
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+        rng.fill_bytes(&mut bytes[6..]);
+        bytes
+    }
+}
+impl RandStream<String> for Ulid {
+    fn next<R: Rng>(&self, rng: &mut R) -> String {
+        let bytes: [u8; 16] = self.next(rng);
+        let value = u128::from_be_bytes(bytes);
+        // 26 digits of 5 bits is 130 bits, so the first digit only
+        // holds the top 3.
+        (0..26).rev().map(|i| CROCKFORD[(value >> (5 * i)) as usize & 31] as char).collect()
+    }
+}
@@ -0,0 +1,78 @@
+//! Interleaving several streams of the same type, e.g. for a mixed
+//! workload of reads and writes with their own key ranges.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+use std::marker;
+use stream::RandStream;
+use weighted::AliasTable;
+
+/// Values from each of `streams` in turn, as `(index, value)` pairs
+/// with the index of the stream each came from: the first value of
+/// the first stream, then the first of the second, and so on, looping
+/// back around forever.
+///
+/// # Panics
+///
+/// If `streams` is empty.
+pub fn round_robin<T, S: RandStream<T>, R: Rng>(rng: R, streams: Vec<S>) -> Interleave<T, S, R> {
+    assert!(!streams.is_empty(), "need at least one stream");
+    Interleave {
+        streams,
+        order: Order::RoundRobin { next: 0 },
+        rng,
+        _marker: marker::PhantomData,
+    }
+}
+
+/// Values from `weighted`'s streams, each chosen independently with
+/// probability proportional to its weight, as `(index, value)` pairs
+/// with the index of the stream, e.g. `weighted(rng, vec![(u32::rand(
+/// 0..1_000_000), 0.9), (u32::rand(0..1_000), 0.1)])` for 90% reads
+/// of keys in `0..1M` (index 0) and 10% writes in `0..1K` (index 1).
+///
+/// # Panics
+///
+/// If any weight is negative or isn't finite, or they're all zero (or
+/// there are none).
+pub fn weighted<T, S: RandStream<T>, R: Rng>(rng: R, weighted: Vec<(S, f64)>) -> Interleave<T, S, R> {
+    let weights = weighted.iter().map(|&(_, w)| w).collect::<Vec<_>>();
+    let table = AliasTable::new(&weights);
+    Interleave {
+        streams: weighted.into_iter().map(|(s, _)| s).collect(),
+        order: Order::Weighted(table),
+        rng,
+        _marker: marker::PhantomData,
+    }
+}
+
+/// The iterator returned by `round_robin` and `weighted`.
+pub struct Interleave<T, S, R> {
+    streams: Vec<S>,
+    order: Order,
+    rng: R,
+    _marker: marker::PhantomData<fn() -> T>,
+}
+
+/// How the next stream is chosen.
+enum Order {
+    RoundRobin { next: usize },
+    Weighted(AliasTable),
+}
+
+impl<T, S: RandStream<T>, R: Rng> Iterator for Interleave<T, S, R> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        let index = match self.order {
+            Order::RoundRobin { ref mut next } => {
+                let index = *next;
+                *next = (index + 1) % self.streams.len();
+                index
+            }
+            Order::Weighted(ref table) => table.sample(&mut self.rng),
+        };
+        Some((index, self.streams[index].next(&mut self.rng)))
+    }
+}
@@ -0,0 +1,80 @@
+//! Randomized delays for retrying with exponential backoff, so that
+//! clients that failed together don't all retry together.
+//!
+//! These are the strategies from the AWS Architecture Blog's
+//! "Exponential Backoff And Jitter": full and equal jitter randomize
+//! the delay `min(cap, base * 2^attempt)`, while decorrelated jitter
+//! grows each delay from the last.
+
+use float::Float;
+use rand::Rng;
+use std::time::Duration;
+
+/// `min(cap, base * 2^attempt)`, without overflowing.
+fn backoff(base: Duration, cap: Duration, attempt: u32) -> Duration {
+    2u32.checked_pow(attempt)
+        .and_then(|factor| base.checked_mul(factor))
+        .map_or(cap, |delay| delay.min(cap))
+}
+
+/// `delay` times a uniform value in `[0, 1)`.
+fn scaled<R: Rng>(delay: Duration, rng: &mut R) -> Duration {
+    delay.mul_f64(f64::closed_open01(rng))
+}
+
+/// A delay uniformly in `[0, min(cap, base * 2^attempt))`, for the
+/// `attempt`th retry (from 0), e.g. `full_jitter(Duration::from_millis(
+/// 100), Duration::from_secs(10), attempt, rng)`.
+///
+/// This spreads retries out the most, for the least total work, at the
+/// cost of some very short delays.
+pub fn full_jitter<R: Rng>(base: Duration, cap: Duration, attempt: u32, rng: &mut R) -> Duration {
+    scaled(backoff(base, cap, attempt), rng)
+}
+
+/// A delay uniformly in `[d/2, d)`, for `d = min(cap, base *
+/// 2^attempt)` and the `attempt`th retry (from 0).
+///
+/// This always waits at least half the backoff, but spreads retries
+/// out less than `full_jitter`.
+pub fn equal_jitter<R: Rng>(base: Duration, cap: Duration, attempt: u32, rng: &mut R) -> Duration {
+    let half = backoff(base, cap, attempt) / 2;
+    half + scaled(half, rng)
+}
+
+/// Decorrelated jitter: each delay is uniformly between `base` and
+/// three times the last (starting from `base`), capped at `cap`, e.g.
+/// `Decorrelated::new(Duration::from_millis(100),
+/// Duration::from_secs(10))`, with `next` for each retry.
+///
+/// The delays grow by 2x on average, like exponential backoff, but
+/// depend on the last delay rather than the attempt number, so need
+/// this to remember it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decorrelated {
+    base: Duration,
+    cap: Duration,
+    last: Duration,
+}
+
+impl Decorrelated {
+    /// # Panics
+    ///
+    /// If `base` is more than `cap`.
+    pub fn new(base: Duration, cap: Duration) -> Decorrelated {
+        assert!(base <= cap, "base delay must be at most the cap");
+        Decorrelated { base, cap, last: base }
+    }
+
+    /// The delay before the next retry.
+    pub fn next<R: Rng>(&mut self, rng: &mut R) -> Duration {
+        let high = self.last.saturating_mul(3).min(self.cap);
+        self.last = self.base + scaled(high - self.base, rng);
+        self.last
+    }
+
+    /// Start again from `base`, e.g. after a success.
+    pub fn reset(&mut self) {
+        self.last = self.base;
+    }
+}
@@ -0,0 +1,108 @@
+//! JSON documents, as `serde_json::Value` trees, e.g. for fuzzing an
+//! API with bodies of arbitrary shape.
+
+use rand::Rng;
+use serde_json::{Map, Number, Value};
+use std::ops::Range;
+use stream::{FloatStreamBounded, IntegerStreamBounded, IntegerStreamCompact, Rand, RandStream};
+use text::{Text, TextStream};
+
+/// Documents with arrays and objects nested at most `depth` deep
+/// (so `0` for just a scalar), each with a number of elements or keys
+/// in `len`, at most `size` values in all, and keys and strings from
+/// `strings`, e.g. `Value::rand(Json { depth: 4, len: 0..8, size:
+/// 1000, strings: Text(Charset::english(), 0..20) })`.
+///
+/// Once `size` values have been generated, the containers still
+/// being filled are cut short, so a document is never larger than
+/// that, whatever `depth` and `len` allow. An object can have fewer
+/// keys than drawn, when two are the same.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Json {
+    pub depth: usize,
+    pub len: Range<usize>,
+    pub size: usize,
+    pub strings: Text,
+}
+
+/// The streams for each part of a document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonStream {
+    depth: usize,
+    size: usize,
+    len: IntegerStreamBounded<u64>,
+    strings: TextStream,
+    /// Null, bool, integer, float, string, array and object, in that
+    /// order, with the scalars first so `0..5` excludes containers.
+    kind: IntegerStreamCompact<u32>,
+    scalar: IntegerStreamCompact<u32>,
+    float: FloatStreamBounded<f64>,
+}
+
+impl Rand<Json> for Value {
+    type Stream = JsonStream;
+    /// # Panics
+    ///
+    /// If `size` is zero, or the range of lengths or string lengths
+    /// is empty.
+    fn rand(dist: Json) -> JsonStream {
+        assert!(dist.size > 0, "size must be positive");
+        assert!(dist.len.start < dist.len.end, "range of lengths must be non-empty");
+        JsonStream {
+            depth: dist.depth,
+            size: dist.size,
+            len: u64::rand(dist.len.start as u64..dist.len.end as u64),
+            strings: String::rand(dist.strings),
+            kind: u32::rand(0..7),
+            scalar: u32::rand(0..5),
+            float: f64::rand(-1e9..1e9),
+        }
+    }
+}
+
+impl JsonStream {
+    /// A value with containers nested at most `depth` deep, taking
+    /// itself and its contents out of `budget`, which must be
+    /// positive.
+    fn value<R: Rng>(&self, depth: usize, budget: &mut usize, rng: &mut R) -> Value {
+        *budget -= 1;
+        let kind = if depth == 0 || *budget == 0 {
+            self.scalar.next(rng)
+        } else {
+            self.kind.next(rng)
+        };
+        match kind {
+            0 => Value::Null,
+            1 => Value::Bool(rng.next_u32() & 1 == 1),
+            2 => Value::Number(Number::from(rng.next_u64() as i64)),
+            3 => Value::Number(Number::from_f64(self.float.next(rng)).unwrap()),
+            4 => Value::String(self.strings.next(rng)),
+            5 => {
+                let len = self.len.next(rng) as usize;
+                let mut array = Vec::with_capacity(len.min(*budget));
+                while array.len() < len && *budget > 0 {
+                    array.push(self.value(depth - 1, budget, rng));
+                }
+                Value::Array(array)
+            }
+            _ => {
+                let len = self.len.next(rng);
+                let mut object = Map::new();
+                for _ in 0..len {
+                    if *budget == 0 {
+                        break;
+                    }
+                    let key = self.strings.next(rng);
+                    object.insert(key, self.value(depth - 1, budget, rng));
+                }
+                Value::Object(object)
+            }
+        }
+    }
+}
+
+impl RandStream<Value> for JsonStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> Value {
+        self.value(self.depth, &mut self.size.clone(), rng)
+    }
+}
@@ -1,22 +1,27 @@
-#![feature(test, core)]
-#![allow(non_snake_case)]
-
 /*!
 
 ## TODO: update text for the stream type.
 
 # Updated benchmarks
 
+Copied from a run of `cargo bench` (see `benches/compare.rs`), which
+writes the table to `target/criterion/comparison.md`: rerun it and
+paste the rows in after changing the sampling code. Times are the mean ns
+per iteration, with the fastest design(s) in bold. The rows below
+use `u32`; `cargo bench` also covers `u8`, `u64`, `i64`, `f32` and
+`f64` with narrow and wide ranges (e.g. `range_gen__wide_u8`), and
+the XorShift, PCG, ChaCha and OS generators (e.g. `iter__chacha`).
+
 | bench| assoc | stream | typeparam | original |
 |---|---|---|---|---|
-| `gen_` | 708 | 706 | **688** | 717 |
-| `iter` | **262** | **265** | 274 | 322 |
-| `iter__noiterbb` | **258** | **255** | 270 | 263 |
-| `range_gen` | 1292 | **1247** | **1244** |  |
-| `range_gen__bb` | 3960 | 3937 | **3847** |  |
-| `range_iter` | 765 | **724** | 1018 |  |
-| `range_iter__bb` | 779 | **665** | 1088 |  |
-| `range_iter__noiterbb` | **384** | 394 | 405 |  |
+| `gen_` | 390 | **350** | 367 | **356** |
+| `iter` | 151 | **126** | 130 | 136 |
+| `iter__noiterbb` | 148 | 120 | 129 | **105** |
+| `range_gen` | 2243 | 1365 | **1046** |  |
+| `range_gen__bb` | 2256 | 1486 | **1352** |  |
+| `range_iter` | 338 | 359 | **305** |  |
+| `range_iter__bb` | 333 | 332 | **292** |  |
+| `range_iter__noiterbb` | **300** | 379 | 384 |  |
 
 # Old text
 
@@ -33,7 +38,7 @@ everything).
 (of either `assoc` or `typeparam`, in the method form they
 would use in a final API, which this crate doesn't implement)
 
-```rust
+```rust,ignore
 // thread-local
 let x: u32 = rand::random(..);
 
@@ -47,6 +52,10 @@ let type_hint = rng.gen::<i64, _>(..);
 
 ## Benchmarks
 
+These are historical: they're from the nightly `#[bench]` harness that
+`benches/compare.rs` replaced, before the designs shared their
+sampling code, so they're only comparable with each other.
+
 ```text
 test gen_assoc                      ... bench:       676 ns/iter (+/- 29)
 test gen_original                   ... bench:       684 ns/iter (+/- 14)
@@ -93,56 +102,231 @@ in `typeparam` out of the loop.
 
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Without `std`, `std::` paths refer to `core`, and the `alloc`
+// modules import `Vec` and `String` from `alloc` themselves.
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use]
+extern crate alloc;
 
-extern crate test;
 extern crate rand;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "num")]
+extern crate num_bigint;
+#[cfg(feature = "num")]
+extern crate num_complex;
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+extern crate getrandom;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "simd")]
+extern crate wide;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
 
 pub mod stream;
 pub mod assoc;
 pub mod typeparam;
 
-pub trait Into<Target> {
-    fn into(self) -> Target;
+pub mod color;
+#[cfg(feature = "chrono")]
+pub mod dates;
+#[cfg(feature = "alloc")]
+pub mod dice;
+#[cfg(feature = "std")]
+pub mod distributions;
+pub mod entropy;
+pub mod fuzz;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod graph;
+#[cfg(feature = "ids")]
+pub mod ids;
+#[cfg(feature = "alloc")]
+pub mod interleave;
+pub mod jitter;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod markov;
+#[cfg(feature = "std")]
+pub mod mcmc;
+#[cfg(feature = "noise")]
+pub mod noise;
+#[cfg(feature = "num")]
+pub mod num;
+#[cfg(feature = "std")]
+pub mod pool;
+pub mod prelude;
+#[cfg(feature = "std")]
+pub mod process;
+#[cfg(feature = "alloc")]
+pub mod quasi;
+#[cfg(feature = "std")]
+pub mod rotation;
+#[cfg(feature = "std")]
+pub mod sampler;
+#[cfg(feature = "alloc")]
+pub mod schema;
+pub mod secure;
+pub mod seq;
+pub mod shrink;
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "std")]
+pub mod sparse;
+#[cfg(feature = "alloc")]
+pub mod text;
+#[cfg(feature = "std")]
+pub mod thread;
+#[cfg(feature = "std")]
+pub mod timeseries;
+#[cfg(feature = "alloc")]
+pub mod variant;
+#[cfg(feature = "alloc")]
+pub mod weighted;
+
+pub mod pcg;
+pub mod splitmix;
+#[cfg(feature = "std")]
+pub mod testing;
+
+mod float;
+mod uniform;
+
+/// Conversion into the constraint used by `assoc::gen` and
+/// `assoc::gen_iter`. This is implemented for everything that is
+/// `std::convert::Into` the constraint, so the constraint types have
+/// ordinary `From` impls (e.g. `IntegerConstraint::from(4..321)`), and
+/// a `From` impl for a type of one's own makes it usable with `gen`
+/// too.
+pub trait IntoConstraint<Target> {
+    fn into_constraint(self) -> Target;
 }
 
-
-mod original {
-    #[cfg(test)]
-    use test::{Bencher, black_box};
-    #[cfg(test)]
-    use rand::{self, Rng};
-
-    #[bench]
-    fn iter(b: &mut Bencher) {
-        let rng: rand::XorShiftRng = rand::random();
-
-        b.iter(|| {
-            for x in black_box(rng.clone().gen_iter::<u32>().take(100)) {
-                black_box(x);
-            }
-        })
+impl<T: std::convert::Into<Target>, Target> IntoConstraint<Target> for T {
+    fn into_constraint(self) -> Target {
+        self.into()
     }
+}
 
-    #[bench]
-    fn iter__noiterbb(b: &mut Bencher) {
-        let rng: rand::XorShiftRng = rand::random();
-
-        b.iter(|| {
-            for x in (rng.clone().gen_iter::<u32>().take(100)) {
-                black_box(x);
-            }
-        })
+// Constraints selecting which ends of the unit interval `f32`s and
+// `f64`s can take. `..` gives `[0, 1)`, and `start..end` gives
+// `[start, end)`: `start` can appear, `end` never does.
+
+/// Floats in the open interval `(0, 1)`, e.g. for taking logarithms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Open01;
+
+/// Floats in the closed interval `[0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Closed01;
+
+/// Floats in the half-open interval `(0, 1]`, i.e. never `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpenClosed01;
+
+/// Floats in `[0, 1)` like `..`, but with every representable value
+/// possible, each weighted by the width of the interval of reals that
+/// round down to it. `..` only gives multiples of `2^-24` (`f32`) or
+/// `2^-53` (`f64`), which loses precision near zero; this costs an
+/// extra draw from the generator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HighPrecision01;
+
+// Streams and constraints are plain data, or keep their state behind a
+// `Mutex` or atomics, so one can be built once (e.g. in a `static`
+// `OnceLock`) and shared between threads. This stops compiling if one
+// of them loses `Send` or `Sync`, e.g. by gaining a `Cell`.
+const _: () = {
+    const fn shareable<T: Send + Sync>() {}
+
+    shareable::<stream::IntegerStreamBounded<u8>>();
+    shareable::<stream::IntegerStreamBounded<u32>>();
+    shareable::<stream::IntegerStreamBounded<u64>>();
+    shareable::<stream::IntegerStreamBounded<i64>>();
+    shareable::<stream::IntegerStreamCompact<u32>>();
+    shareable::<stream::IntegerStreamFull<u64>>();
+    shareable::<stream::IntegerStreamInclusive<u32>>();
+    shareable::<stream::FloatStreamBounded<f64>>();
+    shareable::<stream::FloatStreamClosed<f64>>();
+    shareable::<stream::FloatStreamFull<f32>>();
+    shareable::<stream::ConstRange<0, 1>>();
+    shareable::<stream::SteppedStream<u32>>();
+    shareable::<stream::BernoulliStream>();
+    shareable::<stream::RatioStream>();
+    shareable::<stream::FallibleStream<stream::IntegerStreamBounded<u32>, std::ops::RangeFull>>();
+    #[cfg(feature = "std")]
+    shareable::<stream::DecimalStream>();
+    shareable::<stream::Antithetic<stream::FloatStreamFull<f64>>>();
+    shareable::<stream::Wrapped<stream::IntegerStreamBounded<u32>>>();
+    shareable::<assoc::IntegerConstraint<u32>>();
+    shareable::<assoc::FloatConstraint<f64>>();
+    shareable::<(Open01, Closed01, OpenClosed01, HighPrecision01)>();
+
+    #[cfg(feature = "alloc")]
+    {
+        shareable::<dice::Dice>();
+        shareable::<quasi::Halton>();
+        shareable::<quasi::Sobol>();
+        shareable::<schema::Schema>();
+        shareable::<text::Charset>();
+        shareable::<text::FileNameStream>();
+        shareable::<text::Imitation>();
+        shareable::<text::SequenceStream>();
+        shareable::<text::TextStream>();
+        shareable::<variant::VariantMask<u8>>();
+        shareable::<weighted::AliasTable>();
+        shareable::<weighted::WeightedTree<u8>>();
     }
-
-    #[bench]
-    fn gen_(b: &mut Bencher) {
-        let mut rng: rand::XorShiftRng = rand::random();
-
-        b.iter(|| {
-            for _ in 4..321 {
-                black_box(rng.gen::<u32>());
-            }
-        })
+    #[cfg(feature = "std")]
+    {
+        shareable::<distributions::CircularUniform>();
+        shareable::<distributions::CorrelatedNormals>();
+        shareable::<distributions::DirichletStream>();
+        shareable::<distributions::Distribution<fn(f64) -> f64>>();
+        shareable::<distributions::Empirical>();
+        shareable::<distributions::GaussianCopulaStream<distributions::Empirical, 3>>();
+        shareable::<distributions::GevStream>();
+        shareable::<distributions::Kde>();
+        shareable::<distributions::LogitNormal>();
+        shareable::<distributions::Normal>();
+        shareable::<distributions::OrderStatisticStream>();
+        shareable::<distributions::PiecewiseStream>();
+        shareable::<distributions::SkewNormalStream>();
+        shareable::<distributions::StableStream>();
+        shareable::<distributions::TruncatedNormalStream>();
+        shareable::<distributions::WrappedNormal>();
+        shareable::<markov::MarkovChain>();
+        shareable::<mcmc::MetropolisHastings<f64, fn(&f64) -> f64, stream::FloatStreamBounded<f64>>>();
+        shareable::<rotation::Angle>();
+        shareable::<rotation::UnitQuaternion>();
+        shareable::<text::RelativePathStream>();
+        shareable::<timeseries::TimeSeries<distributions::Normal>>();
     }
-
-}
+    #[cfg(feature = "ids")]
+    shareable::<ids::Ulid>();
+    #[cfg(feature = "json")]
+    shareable::<json::JsonStream>();
+    #[cfg(feature = "chrono")]
+    shareable::<dates::OffsetStream<chrono::DateTime<chrono::FixedOffset>>>();
+    #[cfg(feature = "num")]
+    {
+        shareable::<num::BigUintStream>();
+        shareable::<num::ComplexRectangle>();
+        shareable::<num::Disc>();
+        shareable::<num::StandardComplexNormal>();
+    }
+    #[cfg(feature = "simd")]
+    {
+        shareable::<simd::U32x8Full>();
+        shareable::<simd::U32x8Bounded>();
+        shareable::<simd::F32x8Full>();
+        shareable::<simd::F32x8Bounded>();
+    }
+};
@@ -0,0 +1,63 @@
+//! Markov chains over a finite set of states.
+
+use rand::Rng;
+use std::sync::Mutex;
+use stream::RandStream;
+use weighted::AliasTable;
+
+/// A Markov chain over the states `0..n`, stepping from state `i` to
+/// state `j` with probability `matrix[i][j]`.
+///
+/// Each row is compiled into an alias table up front, so each step
+/// takes constant time. Streams can't change as they're used, so the
+/// current state lives behind a `Mutex`: each `next` steps the chain,
+/// and returns the new state.
+#[derive(Debug)]
+pub struct MarkovChain {
+    rows: Vec<AliasTable>,
+    state: Mutex<usize>,
+}
+
+impl MarkovChain {
+    /// A chain with transition probabilities `matrix`, starting at
+    /// `initial`.
+    ///
+    /// # Panics
+    ///
+    /// If `matrix` isn't square, any probability is negative or isn't
+    /// finite, any row doesn't sum to one (within `1e-9`), or `initial`
+    /// isn't a state.
+    pub fn new<Row: AsRef<[f64]>>(matrix: &[Row], initial: usize) -> MarkovChain {
+        let n = matrix.len();
+        assert!(initial < n, "initial state {} out of range 0..{}", initial, n);
+        let rows = matrix.iter().enumerate().map(|(i, row)| {
+            let row = row.as_ref();
+            assert!(row.len() == n, "transition matrix must be square");
+            let total = row.iter().sum::<f64>();
+            assert!((total - 1.0).abs() <= 1e-9, "row {} sums to {}, not 1", i, total);
+            AliasTable::new(row)
+        }).collect();
+        MarkovChain {
+            rows,
+            state: Mutex::new(initial),
+        }
+    }
+
+    /// The number of states.
+    pub fn states(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The current state.
+    pub fn current(&self) -> usize {
+        *self.state.lock().unwrap()
+    }
+}
+
+impl RandStream<usize> for MarkovChain {
+    fn next<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut state = self.state.lock().unwrap();
+        *state = self.rows[*state].sample(rng);
+        *state
+    }
+}
@@ -0,0 +1,99 @@
+//! Markov chain Monte Carlo: sampling from distributions known only up
+//! to a constant factor, via a chain of states whose distribution
+//! converges to them.
+//!
+//! Streams can't change as they're used, so the chain's state lives
+//! behind a `Mutex`: each `next` steps the chain.
+
+use float::Float;
+use rand::Rng;
+use std::ops::Add;
+use std::sync::Mutex;
+use stream::RandStream;
+
+/// A random walk Metropolis-Hastings chain, sampling from the
+/// distribution with (unnormalized) log-density `log_density`.
+///
+/// Each step proposes the current state plus a step from `proposal`,
+/// and moves there with probability `min(1, p(proposed) /
+/// p(current))`, otherwise staying put. The steps must be symmetric
+/// (a step and its negation equally likely), since there's no
+/// correction for the proposal's density.
+///
+/// Successive values are correlated, and the first ones depend on the
+/// initial state, so it's usual to discard some at the start ("burn
+/// in"), and to tune the size of the steps so that a moderate
+/// fraction of proposals are accepted (`acceptance_rate`).
+#[derive(Debug)]
+pub struct MetropolisHastings<T, F, S> {
+    log_density: F,
+    proposal: S,
+    chain: Mutex<Chain<T>>,
+}
+
+#[derive(Debug)]
+struct Chain<T> {
+    state: T,
+    log_density: f64,
+    steps: u64,
+    accepted: u64,
+}
+
+impl<T, F: Fn(&T) -> f64, S> MetropolisHastings<T, F, S> {
+    /// A chain starting at `initial`.
+    ///
+    /// # Panics
+    ///
+    /// If the density of `initial` is zero (its log-density is
+    /// `-infinity`) or isn't finite, since the chain can't compare
+    /// states to it.
+    pub fn new(initial: T, log_density: F, proposal: S) -> MetropolisHastings<T, F, S> {
+        let initial_log_density = log_density(&initial);
+        assert!(initial_log_density.is_finite(), "initial state must have a finite log-density");
+        MetropolisHastings {
+            log_density,
+            proposal,
+            chain: Mutex::new(Chain {
+                state: initial,
+                log_density: initial_log_density,
+                steps: 0,
+                accepted: 0,
+            }),
+        }
+    }
+
+    /// The fraction of proposals accepted so far (zero before any).
+    pub fn acceptance_rate(&self) -> f64 {
+        let chain = self.chain.lock().unwrap();
+        if chain.steps == 0 { 0.0 } else { chain.accepted as f64 / chain.steps as f64 }
+    }
+}
+
+impl<T, F, S> MetropolisHastings<T, F, S> where T: Clone {
+    /// The current state.
+    pub fn current(&self) -> T {
+        self.chain.lock().unwrap().state.clone()
+    }
+}
+
+impl<T, F, S> RandStream<T> for MetropolisHastings<T, F, S>
+    where T: Clone + Add<Output = T>, F: Fn(&T) -> f64, S: RandStream<T>
+{
+    fn next<R: Rng>(&self, rng: &mut R) -> T {
+        let mut chain = self.chain.lock().unwrap();
+        let proposed = chain.state.clone() + self.proposal.next(rng);
+        let log_density = (self.log_density)(&proposed);
+
+        // Accept if `u < p(proposed) / p(current)`, with `u` in `(0,
+        // 1]` so that its logarithm is finite. A NaN log-density is
+        // never accepted.
+        let u = f64::open_closed01(rng);
+        chain.steps += 1;
+        if u.ln() < log_density - chain.log_density {
+            chain.state = proposed;
+            chain.log_density = log_density;
+            chain.accepted += 1;
+        }
+        chain.state.clone()
+    }
+}
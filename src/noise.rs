@@ -0,0 +1,180 @@
+//! Coherent noise for procedural generation: functions of a point
+//! that vary smoothly, but look random at scales above one unit.
+//!
+//! Each noise function is fixed by tables drawn once from a
+//! generator, so seeding the generator (e.g.
+//! `XorShiftRng::from_seed`) reproduces the same terrain, texture,
+//! etc. The tables repeat every 256 units along each axis.
+
+use float::Float;
+use rand::Rng;
+use seq::partial_shuffle;
+
+/// A uniformly random permutation of `0..256`, hashing lattice points
+/// to table indices.
+#[derive(Clone, Debug)]
+struct Permutation([u8; 256]);
+
+impl Permutation {
+    fn new<R: Rng>(rng: &mut R) -> Permutation {
+        let mut table = [0; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+        partial_shuffle(&mut table, 256, rng);
+        Permutation(table)
+    }
+
+    fn hash(&self, lattice: &[i64]) -> u8 {
+        lattice.iter().fold(0, |h, &c| self.0[(h as usize + (c & 255) as usize) & 255])
+    }
+}
+
+/// The `(i, f)` with `i` the integer part of `x` towards negative
+/// infinity, and `f = x - i` in `[0, 1)`.
+fn split(x: f64) -> (i64, f64) {
+    let i = x.floor();
+    (i as i64, x - i)
+}
+
+/// Perlin's quintic, taking `0` to `0` and `1` to `1` with zero first
+/// and second derivatives at both.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Value noise: a random value in `[-1, 1]` at each point of the
+/// integer lattice, smoothly interpolated between them.
+///
+/// This is cheap, but its features line up with the lattice axes; see
+/// `Perlin` for an alternative.
+#[derive(Clone, Debug)]
+pub struct ValueNoise {
+    permutation: Permutation,
+    values: [f64; 256],
+}
+
+impl ValueNoise {
+    /// Noise with tables drawn from `rng`.
+    pub fn new<R: Rng>(rng: &mut R) -> ValueNoise {
+        let permutation = Permutation::new(rng);
+        let mut values = [0.0; 256];
+        for value in values.iter_mut() {
+            *value = 2.0 * f64::closed01(rng) - 1.0;
+        }
+        ValueNoise { permutation, values }
+    }
+
+    fn at(&self, lattice: &[i64]) -> f64 {
+        self.values[self.permutation.hash(lattice) as usize]
+    }
+
+    /// The noise at `x`, in `[-1, 1]`.
+    pub fn noise1(&self, x: f64) -> f64 {
+        let (i, fx) = split(x);
+        lerp(fade(fx), self.at(&[i]), self.at(&[i + 1]))
+    }
+
+    /// The noise at `(x, y)`, in `[-1, 1]`.
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        let ((i, fx), (j, fy)) = (split(x), split(y));
+        let (u, v) = (fade(fx), fade(fy));
+        lerp(v,
+             lerp(u, self.at(&[i, j]), self.at(&[i + 1, j])),
+             lerp(u, self.at(&[i, j + 1]), self.at(&[i + 1, j + 1])))
+    }
+
+    /// The noise at `(x, y, z)`, in `[-1, 1]`.
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let ((i, fx), (j, fy), (k, fz)) = (split(x), split(y), split(z));
+        let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+        let layer = |k| {
+            lerp(v,
+                 lerp(u, self.at(&[i, j, k]), self.at(&[i + 1, j, k])),
+                 lerp(u, self.at(&[i, j + 1, k]), self.at(&[i + 1, j + 1, k])))
+        };
+        lerp(w, layer(k), layer(k + 1))
+    }
+}
+
+/// Perlin's gradient noise ("Improving Noise", 2002): a random
+/// gradient at each point of the integer lattice, so the noise is zero
+/// there, and smoothly interpolated between them.
+///
+/// The gradients are chosen from a fixed set (in 3D, the twelve
+/// midpoints of the edges of a cube), so the noise is roughly in `[-1,
+/// 1]`, but rarely near either end.
+#[derive(Clone, Debug)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    /// Noise with tables drawn from `rng`.
+    pub fn new<R: Rng>(rng: &mut R) -> Perlin {
+        Perlin { permutation: Permutation::new(rng) }
+    }
+
+    /// The dot product of the gradient at `lattice` with the offset
+    /// `d` from it.
+    fn grad(&self, lattice: &[i64], d: &[f64]) -> f64 {
+        let h = self.permutation.hash(lattice);
+        match *d {
+            // Slopes of either sign, with magnitudes `1/8, 2/8, ...,
+            // 1`.
+            [x] => x * f64::from((h & 7) + 1) / 8.0 * if h & 8 == 0 { 1.0 } else { -1.0 },
+            [x, y] => {
+                let (a, b) = if h & 4 == 0 { (x, y) } else { (y, x) };
+                let b = if h & 8 == 0 { b } else { 0.0 };
+                (if h & 1 == 0 { a } else { -a }) + (if h & 2 == 0 { b } else { -b })
+            }
+            [x, y, z] => {
+                // Perlin's choice of twelve gradients, padded to
+                // sixteen by repeating four.
+                let h = h & 15;
+                let a = if h < 8 { x } else { y };
+                let b = if h < 4 { y } else if h == 12 || h == 14 { x } else { z };
+                (if h & 1 == 0 { a } else { -a }) + (if h & 2 == 0 { b } else { -b })
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// The noise at `x`, in `[-1, 1]`.
+    pub fn noise1(&self, x: f64) -> f64 {
+        let (i, fx) = split(x);
+        // The interpolated slopes give at most `1/2`.
+        2.0 * lerp(fade(fx), self.grad(&[i], &[fx]), self.grad(&[i + 1], &[fx - 1.0]))
+    }
+
+    /// The noise at `(x, y)`.
+    pub fn noise2(&self, x: f64, y: f64) -> f64 {
+        let ((i, fx), (j, fy)) = (split(x), split(y));
+        let (u, v) = (fade(fx), fade(fy));
+        lerp(v,
+             lerp(u, self.grad(&[i, j], &[fx, fy]), self.grad(&[i + 1, j], &[fx - 1.0, fy])),
+             lerp(u,
+                  self.grad(&[i, j + 1], &[fx, fy - 1.0]),
+                  self.grad(&[i + 1, j + 1], &[fx - 1.0, fy - 1.0])))
+    }
+
+    /// The noise at `(x, y, z)`.
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let ((i, fx), (j, fy), (k, fz)) = (split(x), split(y), split(z));
+        let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+        let layer = |k, fz| {
+            lerp(v,
+                 lerp(u,
+                      self.grad(&[i, j, k], &[fx, fy, fz]),
+                      self.grad(&[i + 1, j, k], &[fx - 1.0, fy, fz])),
+                 lerp(u,
+                      self.grad(&[i, j + 1, k], &[fx, fy - 1.0, fz]),
+                      self.grad(&[i + 1, j + 1, k], &[fx - 1.0, fy - 1.0, fz])))
+        };
+        lerp(w, layer(k, fz), layer(k + 1, fz - 1.0))
+    }
+}
@@ -0,0 +1,162 @@
+//! Big integers and complex numbers, from the `num` crates.
+
+use float::Float;
+use num_bigint::BigUint;
+use num_complex::Complex;
+use rand::Rng;
+use rotation::Angle;
+use std::ops::Range;
+use stream::{FloatStreamBounded, Rand, RandStream};
+
+/// Values in `low..low + range`.
+///
+/// Each value is drawn 32 bits at a time, from the most significant,
+/// rejecting as soon as the part drawn so far exceeds that of `range`.
+/// With the top digit masked to the bit length of `range`, each
+/// attempt succeeds with probability over `1/2`, and most failures
+/// are spotted after the first digit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigUintStream {
+    low: BigUint,
+    /// The digits of `range`, least significant first.
+    range: Vec<u32>,
+    /// The bits of the top digit that `range` uses.
+    mask: u32,
+}
+
+impl Rand<Range<BigUint>> for BigUint {
+    type Stream = BigUintStream;
+    fn rand(dist: Range<BigUint>) -> BigUintStream {
+        assert!(dist.start < dist.end);
+        let range = (dist.end - &dist.start).to_u32_digits();
+        let top = range[range.len() - 1];
+        BigUintStream {
+            low: dist.start,
+            mask: u32::MAX >> top.leading_zeros(),
+            range,
+        }
+    }
+}
+
+impl RandStream<BigUint> for BigUintStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> BigUint {
+        let mut digits = vec![0; self.range.len()];
+        'attempt: loop {
+            // Whether the digits so far equal those of `range`, so the
+            // rest must not exceed it.
+            let mut tight = true;
+            for i in (0..digits.len()).rev() {
+                let mut digit = rng.next_u32();
+                if i == digits.len() - 1 {
+                    digit &= self.mask;
+                }
+                if tight {
+                    if digit > self.range[i] {
+                        continue 'attempt
+                    }
+                    tight = digit == self.range[i];
+                }
+                digits[i] = digit;
+            }
+            if !tight {
+                return &self.low + BigUint::new(digits)
+            }
+        }
+    }
+}
+
+/// Complex numbers uniformly distributed in a rectangle, with each
+/// part in the range of the corresponding parts of `start..end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComplexRectangle {
+    re: FloatStreamBounded<f64>,
+    im: FloatStreamBounded<f64>,
+}
+
+/// Complex numbers uniformly distributed in the open disc around
+/// `center` with radius `radius`, e.g. `Complex::rand(Disc { center:
+/// Complex::new(0.0, 0.0), radius: 1.0 })`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Disc {
+    pub center: Complex<f64>,
+    pub radius: f64,
+}
+
+/// Standard complex normal values: circularly symmetric, with mean
+/// zero and `E[|z|^2] = 1`, so the real and imaginary parts are
+/// independent normals with variance `1/2` each. This is the usual
+/// model of complex white noise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StandardComplexNormal;
+
+impl Rand<Range<Complex<f64>>> for Complex<f64> {
+    type Stream = ComplexRectangle;
+    fn rand(dist: Range<Complex<f64>>) -> ComplexRectangle {
+        ComplexRectangle {
+            re: f64::rand(dist.start.re..dist.end.re),
+            im: f64::rand(dist.start.im..dist.end.im),
+        }
+    }
+}
+impl Rand<Disc> for Complex<f64> {
+    type Stream = Disc;
+    fn rand(dist: Disc) -> Disc {
+        assert!(dist.radius > 0.0 && dist.radius.is_finite(), "radius must be positive and finite");
+        dist
+    }
+}
+impl Rand<StandardComplexNormal> for Complex<f64> {
+    type Stream = StandardComplexNormal;
+    fn rand(dist: StandardComplexNormal) -> StandardComplexNormal {
+        dist
+    }
+}
+
+impl RandStream<Complex<f64>> for ComplexRectangle {
+    fn next<R: Rng>(&self, rng: &mut R) -> Complex<f64> {
+        Complex::new(self.re.next(rng), self.im.next(rng))
+    }
+}
+impl RandStream<Complex<f64>> for Disc {
+    fn next<R: Rng>(&self, rng: &mut R) -> Complex<f64> {
+        // The area within radius `r` grows as `r^2`.
+        let r = self.radius * f64::closed_open01(rng).sqrt();
+        self.center + Complex::from_polar(r, Angle.next(rng))
+    }
+}
+impl RandStream<Complex<f64>> for StandardComplexNormal {
+    fn next<R: Rng>(&self, rng: &mut R) -> Complex<f64> {
+        // Box-Muller: `|z|^2` is exponential with mean 1, and the
+        // angle is uniform.
+        let r = (-f64::open_closed01(rng).ln()).sqrt();
+        Complex::from_polar(r, Angle.next(rng))
+    }
+}
+
+/// A random `n` by `n` unitary matrix, as its rows, uniform with
+/// respect to the Haar measure, e.g. for random quantum circuits.
+///
+/// This is `rotation::random_orthogonal` over the complex numbers: the
+/// rows of a matrix of `StandardComplexNormal` values, orthonormalized
+/// by Gram-Schmidt (twice), which fixes the phases of `R`'s diagonal
+/// as the Haar measure needs.
+pub fn random_unitary<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<Complex<f64>>> {
+    let mut rows: Vec<Vec<Complex<f64>>> = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut row = (0..n).map(|_| StandardComplexNormal.next(rng)).collect::<Vec<_>>();
+        for _ in 0..2 {
+            for q in &rows {
+                let dot = q.iter().zip(&row).map(|(a, b)| a.conj() * b).sum::<Complex<f64>>();
+                for (x, a) in row.iter_mut().zip(q) {
+                    *x -= dot * a;
+                }
+            }
+        }
+        let norm = row.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        for x in &mut row {
+            *x /= norm;
+        }
+        rows.push(row);
+    }
+    rows
+}
@@ -0,0 +1,80 @@
+//! The PCG family of generators (<http://www.pcg-random.org>), which
+//! `rand` doesn't provide.
+
+use rand::{Rand, Rng, SeedableRng};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+const MULTIPLIER: u64 = 6364136223846793005;
+
+/// A PCG-XSH-RR generator, with 64 bits of state and 32 bits of
+/// output; this is `pcg32` from the reference implementation.
+#[derive(Clone, Debug)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// Create a generator starting from `state`, using the sequence
+    /// selected by `stream` (only the low 63 bits are significant).
+    pub fn new(state: u64, stream: u64) -> Pcg32 {
+        let mut pcg = Pcg32 {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(state);
+        pcg.step();
+        pcg
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.increment);
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+}
+
+impl SeedableRng<[u64; 2]> for Pcg32 {
+    /// Reseed with `[state, stream]`, as for `new`.
+    fn reseed(&mut self, seed: [u64; 2]) {
+        *self = Pcg32::new(seed[0], seed[1]);
+    }
+
+    /// Create a generator from `[state, stream]`, as for `new`.
+    fn from_seed(seed: [u64; 2]) -> Pcg32 {
+        Pcg32::new(seed[0], seed[1])
+    }
+}
+
+impl Rand for Pcg32 {
+    fn rand<R: Rng>(rng: &mut R) -> Pcg32 {
+        Pcg32::new(rng.gen(), rng.gen())
+    }
+}
+
+/// Wipes the state, so it can't be recovered from memory; this also
+/// happens on drop.
+#[cfg(feature = "zeroize")]
+impl Zeroize for Pcg32 {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.increment.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Pcg32 {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -0,0 +1,108 @@
+//! A pool of random values shared between threads, for servers with
+//! many short-lived threads or tasks, where seeding a generator for
+//! each (as `thread::thread_rng` does) costs more than it's worth.
+
+use rand::Rng;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// The number of values generated at a time.
+const BLOCK: usize = 4096;
+/// The number of values claimed at a time by each handle.
+const CHUNK: usize = 32;
+
+/// A pool of values drawn in blocks from one generator, and handed
+/// out to handles (`Pool::rng`) in chunks.
+///
+/// Claiming a chunk takes a shared (read) lock and an atomic
+/// increment, so handles never wait for each other, except when the
+/// block runs out and one of them replaces it. Each value
+/// is handed out at most once, so handles never share values, but
+/// which handle gets which values depends on the order they claim
+/// them in.
+///
+/// The values are only as unpredictable as `source`, and are kept in
+/// memory until they're used, so only use this for secrets if that's
+/// acceptable.
+#[derive(Debug)]
+pub struct Pool<R> {
+    source: Mutex<R>,
+    block: RwLock<Arc<Block>>,
+}
+
+#[derive(Debug)]
+struct Block {
+    values: Box<[u64]>,
+    /// The start of the next chunk to claim, which may be past the end
+    /// once the block is used up.
+    next: AtomicUsize,
+}
+
+impl Block {
+    fn new<R: Rng>(source: &mut R) -> Block {
+        Block {
+            values: (0..BLOCK).map(|_| source.next_u64()).collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<R: Rng> Pool<R> {
+    /// A pool drawing from `source`.
+    pub fn new(mut source: R) -> Pool<R> {
+        let block = Block::new(&mut source);
+        Pool {
+            source: Mutex::new(source),
+            block: RwLock::new(Arc::new(block)),
+        }
+    }
+
+    /// A generator drawing its values from the pool. This is cheap, so
+    /// make one per thread or task as needed.
+    pub fn rng(&self) -> PoolRng<'_, R> {
+        let (block, start) = self.claim();
+        PoolRng { pool: self, block, position: start, end: start + CHUNK }
+    }
+
+    /// A block, and the start of a chunk of it that's been claimed.
+    fn claim(&self) -> (Arc<Block>, usize) {
+        loop {
+            let block = self.block.read().unwrap().clone();
+            let start = block.next.fetch_add(CHUNK, Ordering::Relaxed);
+            if start + CHUNK <= block.values.len() {
+                return (block, start)
+            }
+            let mut current = self.block.write().unwrap();
+            // Another thread may have replaced it already.
+            if Arc::ptr_eq(&current, &block) {
+                *current = Arc::new(Block::new(&mut *self.source.lock().unwrap()));
+            }
+        }
+    }
+}
+
+/// The generator returned by `Pool::rng`.
+#[derive(Debug)]
+pub struct PoolRng<'a, R: 'a> {
+    pool: &'a Pool<R>,
+    block: Arc<Block>,
+    position: usize,
+    end: usize,
+}
+
+impl<'a, R: Rng> Rng for PoolRng<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.position == self.end {
+            let (block, start) = self.pool.claim();
+            self.block = block;
+            self.position = start;
+            self.end = start + CHUNK;
+        }
+        self.position += 1;
+        self.block.values[self.position - 1]
+    }
+}
@@ -0,0 +1,23 @@
+//! The commonly used items, for glob importing:
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate rand_sketch;
+//! use rand::{SeedableRng, XorShiftRng};
+//! use rand_sketch::prelude::*;
+//!
+//! # fn main() {
+//! let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+//! let x: u32 = rng.random(4..321);
+//! let y: f64 = gen(&mut rng, Open01);
+//! # assert!(4 <= x && x < 321 && 0.0 < y && y < 1.0);
+//! # }
+//! ```
+//!
+//! This uses the `stream` design.
+
+pub use rand::Rng;
+pub use stream::{gen, gen_iter, Bernoulli, Fallible, Rand, RandStream, Ratio, RngExt};
+#[cfg(feature = "std")]
+pub use stream::Decimal;
+pub use {Closed01, HighPrecision01, Open01, OpenClosed01};
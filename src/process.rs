@@ -0,0 +1,91 @@
+//! Point processes: random times at which events happen, as for the
+//! arrivals in a queueing simulation.
+
+use float::Float;
+use rand::Rng;
+
+/// The times of events in a Poisson process with `rate` events per
+/// unit time, starting from time zero.
+///
+/// The gaps between events are independent and exponentially
+/// distributed, with mean `1 / rate`. The times strictly increase:
+/// a gap too small to change the time (which can only happen once
+/// the time is very large) is rounded up to the next `f64`.
+///
+/// # Panics
+///
+/// If `rate` isn't positive and finite.
+pub fn poisson<R: Rng>(rate: f64, rng: R) -> Poisson<R> {
+    assert!(rate > 0.0 && rate.is_finite(), "rate must be positive and finite");
+    Poisson {
+        rate,
+        time: 0.0,
+        rng,
+    }
+}
+
+/// The iterator returned by `poisson`.
+#[derive(Clone, Debug)]
+pub struct Poisson<R> {
+    rate: f64,
+    time: f64,
+    rng: R,
+}
+
+impl<R: Rng> Iterator for Poisson<R> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // Invert the exponential CDF with a uniform value in `(0, 1)`,
+        // so the gap is positive and finite.
+        let gap = -f64::open01(&mut self.rng).ln() / self.rate;
+        self.time = (self.time + gap).max(self.time.next_up());
+        Some(self.time)
+    }
+}
+
+/// The times of events in a Poisson process whose rate varies over
+/// time, with `rate(t)` events per unit time at time `t`, starting
+/// from time zero.
+///
+/// This thins a process at the constant rate `max_rate`, keeping each
+/// of its events at time `t` with probability `rate(t) / max_rate`, so
+/// the closer `max_rate` is to the actual maximum, the fewer draws are
+/// wasted. If `rate` stays at zero, the iterator never returns.
+///
+/// # Panics
+///
+/// If `max_rate` isn't positive and finite, or (when iterating) if
+/// `rate(t)` is negative or exceeds `max_rate` for some `t`.
+pub fn non_homogeneous_poisson<F, R>(rate: F, max_rate: f64, rng: R) -> NonHomogeneousPoisson<F, R>
+    where F: FnMut(f64) -> f64, R: Rng
+{
+    NonHomogeneousPoisson {
+        rate,
+        events: poisson(max_rate, rng),
+    }
+}
+
+/// The iterator returned by `non_homogeneous_poisson`.
+#[derive(Clone, Debug)]
+pub struct NonHomogeneousPoisson<F, R> {
+    rate: F,
+    events: Poisson<R>,
+}
+
+impl<F: FnMut(f64) -> f64, R: Rng> Iterator for NonHomogeneousPoisson<F, R> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let max_rate = self.events.rate;
+        loop {
+            let time = self.events.next()?;
+            let rate = (self.rate)(time);
+            assert!((0.0..=max_rate).contains(&rate),
+                    "rate {} at time {} not in [0, max_rate]", rate, time);
+            if f64::closed_open01(&mut self.events.rng) * max_rate < rate {
+                return Some(time)
+            }
+        }
+    }
+}
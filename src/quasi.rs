@@ -0,0 +1,238 @@
+//! Low-discrepancy ("quasi-random") sequences, which cover the unit
+//! hypercube more evenly than independent uniform values, for
+//! quasi-Monte Carlo integration.
+//!
+//! These are deterministic, so their streams ignore the generator
+//! passed to `next`. Each is a `RandStream<f64>` giving the
+//! coordinates of successive points in order, so something drawing
+//! `dims` uniform values at a time (e.g. `gen_iter` in chunks) gets
+//! exactly the points.
+//!
+//! There are also randomized designs that fix the number of points up
+//! front, and spread them over strata: `stratified` and
+//! `latin_hypercube`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use float::Float;
+use rand::Rng;
+use seq::partial_shuffle;
+use std::sync::atomic::{AtomicU64, Ordering};
+use stream::RandStream;
+
+/// A uniformly random value in stratum `i` of `n`, i.e. `[i / n, (i +
+/// 1) / n)`.
+fn in_stratum<R: Rng>(i: usize, n: usize, rng: &mut R) -> f64 {
+    let x = (i as f64 + f64::closed_open01(rng)) / n as f64;
+    // The sum can round up to the next stratum.
+    let end = (i + 1) as f64 / n as f64;
+    if x < end { x } else { f64::from_bits(end.to_bits() - 1) }
+}
+
+/// `n` values in `[0, 1)`, one uniformly random value in each of the
+/// strata `[i / n, (i + 1) / n)`, in order.
+///
+/// This has the same mean as `n` independent uniform values, but
+/// smaller variance for any estimate that varies smoothly with them.
+pub fn stratified<R: Rng>(n: usize, rng: &mut R) -> Vec<f64> {
+    (0..n).map(|i| in_stratum(i, n, rng)).collect()
+}
+
+/// `n` points in `[0, 1)^dims` forming a Latin hypercube: projected
+/// onto any one dimension, they're `stratified`, with one point in
+/// each stratum, but the strata are paired up between dimensions
+/// uniformly at random.
+pub fn latin_hypercube<R: Rng>(dims: usize, n: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    let mut points = vec![Vec::with_capacity(dims); n];
+    let mut strata = (0..n).collect::<Vec<_>>();
+    for _ in 0..dims {
+        partial_shuffle(&mut strata, n, rng);
+        for (point, &i) in points.iter_mut().zip(&strata) {
+            point.push(in_stratum(i, n, rng));
+        }
+    }
+    points
+}
+
+/// The Halton sequence: coordinate `d` of point `i` is the radical
+/// inverse of `i` in the `d`th prime base, i.e. its digits reflected
+/// about the point.
+///
+/// This supports any number of dimensions, but the coordinates for
+/// large primes are strongly correlated for the first points, so it's
+/// best in a handful of dimensions.
+#[derive(Debug)]
+pub struct Halton {
+    bases: Vec<u64>,
+    next: AtomicU64,
+}
+
+impl Halton {
+    /// The `dims`-dimensional Halton sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero.
+    pub fn new(dims: usize) -> Halton {
+        assert!(dims > 0, "need at least one dimension");
+        let mut bases = Vec::with_capacity(dims);
+        let mut candidate = 2;
+        while bases.len() < dims {
+            if bases.iter().all(|&p| candidate % p != 0) {
+                bases.push(candidate);
+            }
+            candidate += 1;
+        }
+        Halton {
+            bases,
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// The number of dimensions.
+    pub fn dims(&self) -> usize {
+        self.bases.len()
+    }
+
+    /// Coordinate `dim` of point `index`, in `[0, 1)`.
+    ///
+    /// # Panics
+    ///
+    /// If `dim >= self.dims()`.
+    pub fn coordinate(&self, index: u64, dim: usize) -> f64 {
+        // Reflect the digits as an integer, and divide once at the
+        // end to round only once.
+        let base = self.bases[dim] as u128;
+        let (mut i, mut reflected, mut scale) = (index as u128, 0, 1);
+        while i > 0 {
+            reflected = reflected * base + i % base;
+            scale *= base;
+            i /= base;
+        }
+        reflected as f64 / scale as f64
+    }
+
+    /// Point `index`.
+    pub fn point(&self, index: u64) -> Vec<f64> {
+        (0..self.dims()).map(|dim| self.coordinate(index, dim)).collect()
+    }
+}
+
+impl RandStream<f64> for Halton {
+    fn next<R: Rng>(&self, _rng: &mut R) -> f64 {
+        let n = self.next.fetch_add(1, Ordering::Relaxed);
+        let dims = self.dims() as u64;
+        self.coordinate(n / dims, (n % dims) as usize)
+    }
+}
+
+/// The degree `s`, coefficients `a` (of `x^(s-1)` down to `x`, as
+/// bits) and initial direction numbers `m` of the primitive polynomial
+/// for each dimension after the first, from Joe and Kuo's
+/// `new-joe-kuo-6.21201`.
+const SOBOL_PARAMETERS: &[(u32, u32, &[u32])] = &[
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+];
+
+/// The Sobol sequence, with Joe and Kuo's direction numbers, in Gray
+/// code order (as usual), starting from the origin.
+///
+/// This supports up to 16 dimensions and `2^32` points.
+#[derive(Debug)]
+pub struct Sobol {
+    /// `directions[d][k]` is the `k`th direction number of dimension
+    /// `d`, as a 32-bit binary fraction.
+    directions: Vec<[u32; 32]>,
+    next: AtomicU64,
+}
+
+impl Sobol {
+    /// The largest number of dimensions supported.
+    pub const MAX_DIMS: usize = 16;
+
+    /// The `dims`-dimensional Sobol sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `dims` is zero or more than `MAX_DIMS`.
+    pub fn new(dims: usize) -> Sobol {
+        assert!(dims > 0, "need at least one dimension");
+        assert!(dims <= Sobol::MAX_DIMS, "at most {} dimensions are supported", Sobol::MAX_DIMS);
+
+        let mut directions = vec![[0; 32]; dims];
+        for (k, v) in directions[0].iter_mut().enumerate() {
+            *v = 1 << (31 - k);
+        }
+        for (v, &(s, a, m)) in directions[1..].iter_mut().zip(SOBOL_PARAMETERS) {
+            let s = s as usize;
+            for k in 0..s {
+                v[k] = m[k] << (31 - k);
+            }
+            for k in s..32 {
+                v[k] = v[k - s] ^ (v[k - s] >> s);
+                for j in 1..s {
+                    if (a >> (s - 1 - j)) & 1 == 1 {
+                        v[k] ^= v[k - j];
+                    }
+                }
+            }
+        }
+        Sobol {
+            directions,
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// The number of dimensions.
+    pub fn dims(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Coordinate `dim` of point `index`, in `[0, 1)`.
+    ///
+    /// # Panics
+    ///
+    /// If `dim >= self.dims()` or `index >= 2^32`.
+    pub fn coordinate(&self, index: u64, dim: usize) -> f64 {
+        assert!(index >> 32 == 0, "at most 2^32 points are supported");
+        let v = &self.directions[dim];
+        let mut gray = index ^ (index >> 1);
+        let mut x = 0;
+        let mut k = 0;
+        while gray != 0 {
+            if gray & 1 == 1 {
+                x ^= v[k];
+            }
+            gray >>= 1;
+            k += 1;
+        }
+        x as f64 / (1u64 << 32) as f64
+    }
+
+    /// Point `index`.
+    pub fn point(&self, index: u64) -> Vec<f64> {
+        (0..self.dims()).map(|dim| self.coordinate(index, dim)).collect()
+    }
+}
+
+impl RandStream<f64> for Sobol {
+    fn next<R: Rng>(&self, _rng: &mut R) -> f64 {
+        let n = self.next.fetch_add(1, Ordering::Relaxed);
+        let dims = self.dims() as u64;
+        self.coordinate(n / dims, (n % dims) as usize)
+    }
+}
@@ -0,0 +1,97 @@
+//! Uniformly random rotations, and orthogonal matrices.
+//!
+//! Drawing Euler angles uniformly does *not* give uniformly random
+//! rotations in 3D: it bunches them up near the poles of the first
+//! axis. These are uniform with respect to the Haar measure, i.e.
+//! composing one with any fixed rotation leaves its distribution
+//! unchanged.
+
+use distributions::Normal;
+use float::Float;
+use rand::Rng;
+use std::f64::consts::TAU;
+use stream::{Rand, RandStream};
+
+/// Angles in radians in `[0, 2π)`, i.e. uniformly random rotations
+/// in 2D, e.g. `f64::rand(Angle)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Angle;
+
+/// Unit quaternions `[w, x, y, z]` uniformly distributed on the
+/// 3-sphere, i.e. uniformly random rotations in 3D, e.g. `<[f64;
+/// 4]>::rand(UnitQuaternion)`.
+///
+/// `q` and `-q` are the same rotation, and both are equally likely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitQuaternion;
+
+// These need no precomputation, so are their own streams.
+impl Rand<Angle> for f64 {
+    type Stream = Angle;
+    fn rand(dist: Angle) -> Angle {
+        dist
+    }
+}
+impl Rand<UnitQuaternion> for [f64; 4] {
+    type Stream = UnitQuaternion;
+    fn rand(dist: UnitQuaternion) -> UnitQuaternion {
+        dist
+    }
+}
+
+impl RandStream<f64> for Angle {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        // Rounding can't reach `TAU`: the largest product is below it
+        // by more than half an ulp.
+        f64::closed_open01(rng) * TAU
+    }
+}
+
+impl RandStream<[f64; 4]> for UnitQuaternion {
+    fn next<R: Rng>(&self, rng: &mut R) -> [f64; 4] {
+        // Shoemake's method ("Uniform random rotations", Graphics Gems
+        // III): the squared norms of the pairs `(w, x)` and `(y, z)`
+        // are `1 - u` and `u` for uniform `u`, and each pair's angle
+        // is uniform and independent.
+        let u = f64::closed_open01(rng);
+        let (a, b) = ((1.0 - u).sqrt(), u.sqrt());
+        let (sin1, cos1) = Angle.next(rng).sin_cos();
+        let (sin2, cos2) = Angle.next(rng).sin_cos();
+        [a * cos1, a * sin1, b * cos2, b * sin2]
+    }
+}
+
+const STANDARD_NORMAL: Normal = Normal { mean: 0.0, sd: 1.0 };
+
+/// A random `n` by `n` orthogonal matrix, as its rows, uniform with
+/// respect to the Haar measure on all of them (rotations and
+/// reflections, each half the time), e.g. to test numerical linear
+/// algebra with well-conditioned matrices, or to rotate
+/// `n`-dimensional data.
+///
+/// This is the `Q` of the QR decomposition of a matrix of independent
+/// standard normals, with the signs fixed so the diagonal of `R` is
+/// positive (as Mezzadri, 2007, explains, a QR routine's arbitrary
+/// signs would skew the distribution). Gram-Schmidt orthogonalization
+/// of the rows gives exactly that; each is orthogonalized twice, so
+/// rounding leaves them orthogonal to within a few ulps.
+pub fn random_orthogonal<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<f64>> {
+    let mut rows: Vec<Vec<f64>> = Vec::with_capacity(n);
+    for _ in 0..n {
+        let mut row = (0..n).map(|_| STANDARD_NORMAL.next(rng)).collect::<Vec<_>>();
+        for _ in 0..2 {
+            for q in &rows {
+                let dot = q.iter().zip(&row).map(|(a, b)| a * b).sum::<f64>();
+                for (x, a) in row.iter_mut().zip(q) {
+                    *x -= dot * a;
+                }
+            }
+        }
+        let norm = row.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for x in &mut row {
+            *x /= norm;
+        }
+        rows.push(row);
+    }
+    rows
+}
@@ -0,0 +1,115 @@
+//! Deciding which events to keep, e.g. which requests to trace, at a
+//! fixed probability or a target rate.
+
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// `p` as a threshold for a `u64` draw, as `BernoulliStream` has it:
+/// `u64::MAX` means always.
+fn threshold(p: f64) -> u64 {
+    if p >= 1.0 { u64::MAX } else { (p * 18446744073709551616.0) as u64 }
+}
+
+/// Keeps each event independently with some probability: either a
+/// fixed one, or one adjusted each window of time to keep about a
+/// target number of events per second, however many there are.
+///
+/// Deciding is a relaxed atomic load, one `u64` draw and a comparison
+/// against a precomputed threshold (plus, for a target rate, counting
+/// the event and checking the time), so it's cheap enough to do for
+/// every event, and the sampler can be shared between threads.
+#[derive(Debug)]
+pub struct Sampler {
+    threshold: AtomicU64,
+    target: Option<Target>,
+}
+
+/// The target rate, and the events seen in the current window.
+#[derive(Debug)]
+struct Target {
+    per_second: f64,
+    window: Duration,
+    /// When the sampler was created, which times are measured from.
+    origin: Instant,
+    /// When the current window ends, in nanoseconds after `origin`.
+    end: AtomicU64,
+    seen: AtomicU64,
+}
+
+impl Sampler {
+    /// Keeps each event with probability `p`, e.g. `Sampler::fixed(
+    /// 0.01)` for one in a hundred.
+    ///
+    /// # Panics
+    ///
+    /// If `p` isn't in `[0, 1]`.
+    pub fn fixed(p: f64) -> Sampler {
+        assert!((0.0..=1.0).contains(&p), "probability must be in [0, 1]");
+        Sampler { threshold: AtomicU64::new(threshold(p)), target: None }
+    }
+
+    /// Keeps about `per_second` events per second, e.g.
+    /// `Sampler::target_rate(100.0, Duration::from_secs(1))`.
+    ///
+    /// Everything is kept for the first `window`. After that, at the
+    /// end of each window, the probability for the next is set to keep
+    /// `per_second` of the events per second seen in the last (or all
+    /// of them, if there were fewer). A shorter window follows changes
+    /// in traffic faster, with a noisier estimate of it.
+    ///
+    /// # Panics
+    ///
+    /// If `per_second` isn't positive and finite, or `window` is zero.
+    pub fn target_rate(per_second: f64, window: Duration) -> Sampler {
+        assert!(per_second > 0.0 && per_second.is_finite(), "rate must be positive and finite");
+        assert!(window > Duration::ZERO, "window must be non-zero");
+        Sampler {
+            threshold: AtomicU64::new(u64::MAX),
+            target: Some(Target {
+                per_second,
+                window,
+                origin: Instant::now(),
+                end: AtomicU64::new(window.as_nanos() as u64),
+                seen: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The probability each event is currently kept with, e.g. to
+    /// weight each kept event as `1 / p` events when counting.
+    pub fn probability(&self) -> f64 {
+        match self.threshold.load(Ordering::Relaxed) {
+            u64::MAX => 1.0,
+            t => t as f64 / 18446744073709551616.0,
+        }
+    }
+
+    /// Whether to keep an event happening now.
+    pub fn should_sample<R: Rng>(&self, rng: &mut R) -> bool {
+        self.should_sample_at(Instant::now(), rng)
+    }
+
+    /// Whether to keep an event happening at `now`, e.g. with a time
+    /// already taken for the event, or a simulated one.
+    pub fn should_sample_at<R: Rng>(&self, now: Instant, rng: &mut R) -> bool {
+        if let Some(ref target) = self.target {
+            target.seen.fetch_add(1, Ordering::Relaxed);
+            let time = now.saturating_duration_since(target.origin).as_nanos() as u64;
+            let end = target.end.load(Ordering::Relaxed);
+            // One thread wins the race to start the next window, and
+            // sets its probability.
+            if time >= end && target.end.compare_exchange(end, time + target.window.as_nanos() as u64,
+                                                          Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+                let seen = target.seen.swap(0, Ordering::Relaxed);
+                let start = end - target.window.as_nanos() as u64;
+                let elapsed = (time - start) as f64 / 1e9;
+                let p = target.per_second * elapsed / seen as f64;
+                self.threshold.store(threshold(p), Ordering::Relaxed);
+            }
+        }
+        let threshold = self.threshold.load(Ordering::Relaxed);
+        let v = rng.next_u64();
+        threshold == u64::MAX || v < threshold
+    }
+}
@@ -0,0 +1,163 @@
+//! Rows of tabular data, from a list of columns each with a type, a
+//! constraint and a chance of being null, e.g. to fill a database
+//! table or feed an ETL pipeline in tests.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use rand::Rng;
+use std::fmt;
+use stream::{Bernoulli, BernoulliStream, GenIter, Rand, RandStream};
+
+/// A single cell of a row.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+}
+
+macro_rules! value_from {
+    ($variant: ident, $target: ty: $($t: ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(x: $t) -> Value {
+                    Value::$variant(<$target>::from(x))
+                }
+            }
+        )*
+    };
+}
+value_from!(Bool, bool: bool);
+value_from!(Int, i64: i8, i16, i32, i64);
+value_from!(UInt, u64: u8, u16, u32, u64);
+value_from!(Float, f64: f32, f64);
+value_from!(Text, String: char, String);
+
+/// A column's stream, with its value type erased.
+type ValueStream = Box<dyn Fn(&mut dyn Rng) -> Value + Send + Sync>;
+
+/// A named column, with values of one type from one constraint, e.g.
+/// `Column::new::<u32, _>("age", 18..90)`.
+///
+/// The constraint's stream is built (and so checked) when the column
+/// is, and kept behind a trait object, so columns of different types
+/// can go in one `Schema`.
+pub struct Column {
+    name: String,
+    stream: ValueStream,
+    /// `None` for never null.
+    nulls: Option<BernoulliStream>,
+    null_probability: f64,
+}
+
+impl Column {
+    /// A column named `name` of `T`s from `dist`, never null.
+    pub fn new<T, Dist>(name: &str, dist: Dist) -> Column
+        where T: Rand<Dist> + Into<Value>, T::Stream: Send + Sync + 'static
+    {
+        let stream = T::rand(dist);
+        Column {
+            name: String::from(name),
+            stream: Box::new(move |mut rng| stream.next(&mut rng).into()),
+            nulls: None,
+            null_probability: 0.0,
+        }
+    }
+
+    /// A column named `name` of `T`s from `dist`, except each is
+    /// `Value::Null` with probability `null_probability`.
+    ///
+    /// # Panics
+    ///
+    /// If `null_probability` isn't in `[0, 1]`.
+    pub fn nullable<T, Dist>(name: &str, dist: Dist, null_probability: f64) -> Column
+        where T: Rand<Dist> + Into<Value>, T::Stream: Send + Sync + 'static
+    {
+        Column {
+            nulls: Some(bool::rand(Bernoulli(null_probability))),
+            null_probability,
+            ..Column::new::<T, Dist>(name, dist)
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn null_probability(&self) -> f64 {
+        self.null_probability
+    }
+}
+
+impl fmt::Debug for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Column")
+            .field("name", &self.name)
+            .field("null_probability", &self.null_probability)
+            .finish()
+    }
+}
+
+impl RandStream<Value> for Column {
+    fn next<R: Rng>(&self, rng: &mut R) -> Value {
+        // The null check comes first, so a null cell doesn't draw a
+        // value too.
+        match self.nulls {
+            Some(ref nulls) if nulls.next(rng) => Value::Null,
+            _ => (self.stream)(rng),
+        }
+    }
+}
+
+/// The columns of a table: rows are `Vec<Value>`s, one cell per
+/// column in order, e.g. `Schema::new(columns).rows(rng).take(1000)`.
+///
+/// Each column's stream is built with it, so the schema is its own
+/// stream.
+#[derive(Debug)]
+pub struct Schema {
+    columns: Vec<Column>,
+}
+
+impl Schema {
+    /// # Panics
+    ///
+    /// If two columns have the same name.
+    pub fn new(columns: Vec<Column>) -> Schema {
+        for (i, column) in columns.iter().enumerate() {
+            assert!(columns[..i].iter().all(|c| c.name != column.name),
+                    "duplicate column name {:?}", column.name);
+        }
+        Schema { columns }
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// The position of the column named `name` in each row, if any.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    /// An endless iterator of rows.
+    pub fn rows<R: Rng>(self, rng: R) -> GenIter<Vec<Value>, Schema, R> {
+        ::stream::gen_iter(rng, self)
+    }
+}
+
+impl Rand<Schema> for Vec<Value> {
+    type Stream = Schema;
+    fn rand(dist: Schema) -> Schema {
+        dist
+    }
+}
+
+impl RandStream<Vec<Value>> for Schema {
+    fn next<R: Rng>(&self, rng: &mut R) -> Vec<Value> {
+        self.columns.iter().map(|c| c.next(rng)).collect()
+    }
+}
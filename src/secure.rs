@@ -0,0 +1,149 @@
+//! Sampling for cryptographic protocols, where how long an operation
+//! takes mustn't reveal anything about the secret values it produces.
+//!
+//! The other integer ranges in this crate reject some draws, so how
+//! many draws they make (and so how long they take) depends on the
+//! value returned. The functions here always make the same draws and
+//! operations, whatever the value, at the cost of a tiny bias.
+//!
+//! They also only accept a `CryptoRng`, since a fast generator (such
+//! as `XorShiftRng` or `Pcg32`) can be predicted from a little of its
+//! output, which would give the secrets away.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use rand::OsRng;
+use rand::{ChaChaRng, Rng};
+use std::ops::Range;
+#[cfg(feature = "zeroize")]
+use std::mem::{self, ManuallyDrop};
+#[cfg(feature = "zeroize")]
+use std::ptr;
+#[cfg(feature = "zeroize")]
+use std::sync::atomic::{self, Ordering};
+
+/// A generator that's cryptographically secure: its output can't be
+/// predicted, even knowing earlier output. This is a promise made by
+/// the implementation, which can't be checked.
+///
+/// This is implemented for `rand`'s `OsRng` and `ChaChaRng`, but not
+/// its fast generators, nor this crate's `Pcg32`, so passing one of
+/// those to a function here doesn't compile:
+///
+/// ```compile_fail
+/// extern crate rand;
+/// extern crate rand_sketch;
+///
+/// use rand::{SeedableRng, XorShiftRng};
+///
+/// fn main() {
+///     let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+///     rand_sketch::secure::token(16, &mut rng);
+/// }
+/// ```
+pub trait CryptoRng: Rng {}
+
+#[cfg(feature = "std")]
+impl CryptoRng for OsRng {}
+impl CryptoRng for ChaChaRng {}
+impl<R: CryptoRng + ?Sized> CryptoRng for &mut R {}
+
+/// `bytes` random bytes, as a lowercase hexadecimal string (so `2 *
+/// bytes` characters long), e.g. for a session token or password
+/// reset link.
+#[cfg(feature = "alloc")]
+pub fn token<R: CryptoRng>(bytes: usize, rng: &mut R) -> String {
+    let mut buffer = vec![0; bytes];
+    rng.fill_bytes(&mut buffer);
+    buffer.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A random `N`-byte key, e.g. `key::<32, _>(rng)` for a 256-bit
+/// symmetric key.
+pub fn key<const N: usize, R: CryptoRng>(rng: &mut R) -> [u8; N] {
+    let mut key = [0; N];
+    rng.fill_bytes(&mut key);
+    key
+}
+
+/// A value in `range`, taking the same time whatever the value.
+///
+/// This draws 128 bits `v`, and returns `start + floor(v * width /
+/// 2^128)` (with `width = end - start`), computed with fixed-width
+/// multiplies and no branches on the value. With no rejection, each
+/// value's probability is off from `1 / width` by a factor of at most
+/// `1 ± width / 2^128`, i.e. at most `2^-64`, which is far below
+/// anything observable.
+///
+/// Only the secret value is protected: the bounds may affect the
+/// timing.
+///
+/// # Panics
+///
+/// If `range` is empty.
+pub fn uniform_ct<R: CryptoRng>(range: Range<u64>, rng: &mut R) -> u64 {
+    assert!(range.start < range.end, "range must be non-empty");
+    let width = u128::from(range.end - range.start);
+    let (high, low) = (u128::from(rng.next_u64()), u128::from(rng.next_u64()));
+    // The top 64 bits of the 192-bit `(high * 2^64 + low) * width`.
+    let carry = (low * width) >> 64;
+    range.start + ((high * width + carry) >> 64) as u64
+}
+
+/// A generator whose memory is overwritten with zeros when it's
+/// dropped, so its state (e.g. the key and buffered output of a
+/// `ChaChaRng`) can't be recovered from memory afterwards.
+///
+/// This wipes the generator itself, not anything it owns on the heap,
+/// nor copies made by moving it before it's wrapped: create it inside
+/// the wrapper, e.g. `ZeroizingRng::new(ChaChaRng::from_seed(key))`.
+#[cfg(feature = "zeroize")]
+#[derive(Debug)]
+pub struct ZeroizingRng<R> {
+    rng: ManuallyDrop<R>,
+}
+
+#[cfg(feature = "zeroize")]
+impl<R: Rng> ZeroizingRng<R> {
+    /// Wrap `rng`.
+    pub fn new(rng: R) -> ZeroizingRng<R> {
+        ZeroizingRng { rng: ManuallyDrop::new(rng) }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<R: Rng> Rng for ZeroizingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<R: CryptoRng> CryptoRng for ZeroizingRng<R> {}
+
+#[cfg(feature = "zeroize")]
+impl<R> Drop for ZeroizingRng<R> {
+    fn drop(&mut self) {
+        let bytes = &mut *self.rng as *mut R as *mut u8;
+        // SAFETY: the generator is dropped exactly once, here, and
+        // then its memory (which this still owns) is only written,
+        // through raw pointers, never read. The writes are volatile,
+        // and fenced, so they aren't optimized away as dead stores.
+        unsafe {
+            ManuallyDrop::drop(&mut self.rng);
+            for i in 0..mem::size_of::<R>() {
+                ptr::write_volatile(bytes.add(i), 0);
+            }
+        }
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,223 @@
+//! Sampling distinct indices, i.e. without replacement.
+
+use super::{gen_index, partial_shuffle};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Range;
+use std::slice;
+
+/// Distinct indices, stored as `u32`s where they fit, since that
+/// halves the memory (and time spent on it) on 64-bit platforms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexVec {
+    U32(Vec<u32>),
+    USize(Vec<usize>),
+}
+
+impl IndexVec {
+    /// The number of indices.
+    pub fn len(&self) -> usize {
+        match *self {
+            IndexVec::U32(ref v) => v.len(),
+            IndexVec::USize(ref v) => v.len(),
+        }
+    }
+
+    /// Whether there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `i`th index.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= self.len()`.
+    pub fn index(&self, i: usize) -> usize {
+        match *self {
+            IndexVec::U32(ref v) => v[i] as usize,
+            IndexVec::USize(ref v) => v[i],
+        }
+    }
+
+    /// The indices, in order.
+    pub fn iter(&self) -> IndexVecIter<'_> {
+        match *self {
+            IndexVec::U32(ref v) => IndexVecIter::U32(v.iter()),
+            IndexVec::USize(ref v) => IndexVecIter::USize(v.iter()),
+        }
+    }
+
+    /// The indices as `usize`s.
+    pub fn into_vec(self) -> Vec<usize> {
+        match self {
+            IndexVec::U32(v) => v.into_iter().map(|i| i as usize).collect(),
+            IndexVec::USize(v) => v,
+        }
+    }
+}
+
+/// The iterator returned by `IndexVec::iter`.
+#[derive(Clone, Debug)]
+pub enum IndexVecIter<'a> {
+    U32(slice::Iter<'a, u32>),
+    USize(slice::Iter<'a, usize>),
+}
+
+impl<'a> Iterator for IndexVecIter<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        match *self {
+            IndexVecIter::U32(ref mut it) => it.next().map(|&i| i as usize),
+            IndexVecIter::USize(ref mut it) => it.next().cloned(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            IndexVecIter::U32(ref it) => it.size_hint(),
+            IndexVecIter::USize(ref it) => it.size_hint(),
+        }
+    }
+}
+
+/// `k` distinct indices from `0..n`, each selection of `k` (and each
+/// order of them) equally likely.
+///
+/// This picks whichever algorithm suits `n` and `k`:
+///
+/// - for small `k`, Floyd's algorithm, which takes `O(k^2)` time but
+///   no extra memory,
+/// - when `k` is a large fraction of `n`, a partial shuffle of
+///   `0..n`, which takes `O(n)` time and memory,
+/// - otherwise, drawing indices and rejecting repeats, which takes
+///   `O(k)` expected time and memory.
+///
+/// # Panics
+///
+/// If `k > n`.
+pub fn sample<R: Rng>(n: usize, k: usize, rng: &mut R) -> IndexVec {
+    assert!(k <= n, "cannot sample {} indices from {}", k, n);
+    if n <= u32::MAX as usize {
+        IndexVec::U32(sample_as(n, k, rng))
+    } else {
+        IndexVec::USize(sample_as(n, k, rng))
+    }
+}
+
+/// The integer types indices are stored as.
+trait Index: Copy + Eq + Hash {
+    fn from_usize(i: usize) -> Self;
+}
+impl Index for u32 {
+    fn from_usize(i: usize) -> u32 {
+        i as u32
+    }
+}
+impl Index for usize {
+    fn from_usize(i: usize) -> usize {
+        i
+    }
+}
+
+fn sample_as<I: Index, R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<I> {
+    // Floyd's algorithm is quadratic, but with a tiny constant.
+    if k <= 16 {
+        floyd(n, k, rng)
+    } else if n <= 4 * k {
+        let mut indices = (0..n).map(I::from_usize).collect::<Vec<_>>();
+        partial_shuffle(&mut indices, k, rng);
+        indices.truncate(k);
+        indices
+    } else {
+        rejection(n, k, rng)
+    }
+}
+
+/// Floyd's algorithm: step `j` picks from `0..=j`, taking `j` itself if
+/// the pick was already taken. Inserting `j` just before the repeated
+/// pick, rather than at the end, makes the order uniform too.
+fn floyd<I: Index, R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<I> {
+    let mut indices = Vec::with_capacity(k);
+    for j in n - k..n {
+        let t = I::from_usize(gen_index(j + 1, rng));
+        match indices.iter().position(|&i| i == t) {
+            Some(pos) => indices.insert(pos, I::from_usize(j)),
+            None => indices.push(t),
+        }
+    }
+    indices
+}
+
+fn rejection<I: Index, R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<I> {
+    let mut seen = HashSet::with_capacity(k);
+    let mut indices = Vec::with_capacity(k);
+    while indices.len() < k {
+        let i = I::from_usize(gen_index(n, rng));
+        if seen.insert(i) {
+            indices.push(i);
+        }
+    }
+    indices
+}
+
+/// The values of `range` in a uniformly random order, each once, e.g.
+/// `unique_in_range(0..1 << 40, rng).take(1000)` for distinct IDs, or
+/// `unique_in_range(0..shards, rng)` for a test shard ordering.
+///
+/// This is a lazy Fisher-Yates shuffle: step `i` swaps a random
+/// position at or after `i` into position `i`, and yields it. The
+/// positions that have been swapped are kept in a map, and all the
+/// others hold their own value, so there's no array of the whole range:
+/// taking `k` values takes `O(k)` time and memory, however large the
+/// range.
+pub fn unique_in_range<R: Rng>(range: Range<usize>, rng: R) -> UniqueInRange<R> {
+    UniqueInRange {
+        start: range.start,
+        len: range.end.saturating_sub(range.start),
+        next: 0,
+        swapped: HashMap::new(),
+        rng,
+    }
+}
+
+/// The iterator returned by `unique_in_range`.
+#[derive(Clone, Debug)]
+pub struct UniqueInRange<R> {
+    start: usize,
+    len: usize,
+    /// The next position of the shuffle.
+    next: usize,
+    /// The values at positions from `next` on that aren't their own.
+    swapped: HashMap<usize, usize>,
+    rng: R,
+}
+
+impl<R: Rng> Iterator for UniqueInRange<R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.next == self.len {
+            return None
+        }
+        let i = self.next;
+        let j = i + gen_index(self.len - i, &mut self.rng);
+        let value = self.swapped.remove(&j).unwrap_or(j);
+        if j != i {
+            // Position `i` is done with, so its value moves to `j`.
+            let displaced = self.swapped.remove(&i).unwrap_or(i);
+            self.swapped.insert(j, displaced);
+        }
+        self.next += 1;
+        Some(self.start + value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<R: Rng> ExactSizeIterator for UniqueInRange<R> {}
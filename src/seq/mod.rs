@@ -0,0 +1,350 @@
+//! Random selections from slices and iterators.
+
+#[cfg(feature = "std")]
+use float::Float;
+use rand::Rng;
+use uniform::Uniform;
+
+#[cfg(feature = "std")]
+pub mod index;
+
+/// A uniformly random index in `0..n`, for `n > 0`, using 32-bit
+/// sampling where possible since it's faster.
+pub(crate) fn gen_index<R: Rng>(n: usize, rng: &mut R) -> usize {
+    if n <= u32::MAX as usize {
+        u32::sample_lazy(n as u32, rng) as usize
+    } else {
+        u64::sample_lazy(n as u64, rng) as usize
+    }
+}
+
+/// `k` distinct elements of `slice` (or all of them, if there are
+/// fewer), each selection of them (and each order) equally likely.
+///
+/// See `index::sample`, which picks their indices.
+#[cfg(feature = "std")]
+pub fn choose_multiple<'a, T, R: Rng>(slice: &'a [T], k: usize, rng: &mut R) -> Vec<&'a T> {
+    let k = k.min(slice.len());
+    index::sample(slice.len(), k, rng).iter().map(|i| &slice[i]).collect()
+}
+
+/// Shuffle `slice` just enough that its first `k` elements (or all of
+/// them, if there are fewer) are a uniformly random selection of `k`
+/// of its elements, in a uniformly random order, and return them
+/// along with the rest (in an unspecified order).
+///
+/// This is the first `k` steps of a Fisher-Yates shuffle, so takes
+/// time proportional to `k` rather than to the length.
+pub fn partial_shuffle<'a, T, R: Rng>(slice: &'a mut [T], k: usize, rng: &mut R)
+                                      -> (&'a mut [T], &'a mut [T])
+{
+    let len = slice.len();
+    let k = k.min(len);
+    for i in 0..k {
+        slice.swap(i, i + gen_index(len - i, rng));
+    }
+    slice.split_at_mut(k)
+}
+
+/// A uniformly random permutation of `0..n`.
+#[cfg(feature = "std")]
+fn permutation<R: Rng>(n: usize, rng: &mut R) -> Vec<usize> {
+    let mut values = (0..n).collect::<Vec<_>>();
+    partial_shuffle(&mut values, n, rng);
+    values
+}
+
+/// An `n` by `n` Latin square, of the symbols `0..n`: each occurs once
+/// in each row and once in each column, e.g. to assign `n` treatments
+/// to plots balanced across two blocking factors.
+///
+/// This is the cyclic square `(i + j) % n` with its rows, columns and
+/// symbols each shuffled, so each row, column and symbol is equally
+/// likely to be anywhere. It only gives the squares that can be made
+/// that way, which (above `n = 3`) isn't all of them, nor each equally
+/// often: that needs a Markov chain over the squares, such as
+/// Jacobson and Matthews's.
+#[cfg(feature = "std")]
+pub fn random_latin_square<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<usize>> {
+    let rows = permutation(n, rng);
+    let columns = permutation(n, rng);
+    let symbols = permutation(n, rng);
+    rows.iter().map(|&i| {
+        columns.iter().map(|&j| symbols[(i + j) % n]).collect()
+    }).collect()
+}
+
+/// A uniformly random `n` by `n` permutation matrix: a single `1` in
+/// each row and each column, and `0`s elsewhere.
+#[cfg(feature = "std")]
+pub fn random_permutation_matrix<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<u8>> {
+    permutation(n, rng).into_iter().map(|j| {
+        let mut row = vec![0; n];
+        row[j] = 1;
+        row
+    }).collect()
+}
+
+/// A uniformly random composition of `n` into `k` positive parts:
+/// `k` values of at least one, summing to `n`, in order, e.g. to split
+/// a workload of `n` items into `k` non-empty batches.
+///
+/// This is stars and bars: the parts are the gaps between `k - 1`
+/// distinct cut points among the `n - 1` places between the items,
+/// chosen with `index::sample` and sorted.
+///
+/// # Panics
+///
+/// If `k` is zero (unless `n` is too) or more than `n`.
+#[cfg(feature = "std")]
+pub fn random_composition<R: Rng>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    if n == 0 && k == 0 {
+        return Vec::new()
+    }
+    assert!(0 < k && k <= n, "need between 1 and n parts");
+    let mut cuts = index::sample(n - 1, k - 1, rng).into_vec();
+    cuts.sort_unstable();
+    let mut last = 0;
+    cuts.into_iter().map(|c| c + 1).chain(Some(n)).map(|cut| {
+        let part = cut - last;
+        last = cut;
+        part
+    }).collect()
+}
+
+/// A uniformly random partition of the set `0..n` into non-empty
+/// blocks, each listed in increasing order, and the blocks in order of
+/// their smallest elements.
+///
+/// This is Stam's method: draw a number of urns `m` with probability
+/// `m^n / (e B_n m!)` (where `B_n` is the `n`th Bell number, the
+/// number of partitions), throw each element into a uniformly random
+/// urn, and take the non-empty urns as the blocks. The probabilities
+/// are computed as logarithms and normalized, so `B_n` isn't needed,
+/// and the sum stops once further terms are negligible.
+#[cfg(feature = "std")]
+pub fn random_set_partition<R: Rng>(n: usize, rng: &mut R) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return Vec::new()
+    }
+    // `ln(m^n / m!)`, for `m = 1, 2, ...`, which rises to a peak below
+    // `n` and then falls faster than geometrically.
+    let mut log_weights = Vec::new();
+    let (mut ln_factorial, mut peak) = (0.0, f64::NEG_INFINITY);
+    for m in 1.. {
+        let m = m as f64;
+        ln_factorial += m.ln();
+        let log_weight = n as f64 * m.ln() - ln_factorial;
+        peak = peak.max(log_weight);
+        log_weights.push(log_weight);
+        if m > n as f64 && log_weight < peak - 50.0 {
+            break
+        }
+    }
+    let weights = log_weights.iter().map(|w| (w - peak).exp()).collect::<Vec<_>>();
+    let mut u = f64::closed_open01(rng) * weights.iter().sum::<f64>();
+    let mut urns = weights.len();
+    for (i, &weight) in weights.iter().enumerate() {
+        if u < weight {
+            urns = i + 1;
+            break
+        }
+        u -= weight;
+    }
+
+    // Number the blocks in order of their first element.
+    let mut block_of_urn = vec![None; urns];
+    let mut blocks = Vec::<Vec<usize>>::new();
+    for element in 0..n {
+        let urn = gen_index(urns, rng);
+        let block = *block_of_urn[urn].get_or_insert_with(|| {
+            blocks.push(Vec::new());
+            blocks.len() - 1
+        });
+        blocks[block].push(element);
+    }
+    blocks
+}
+
+/// The elements of `slice` that survive being kept independently with
+/// probability `p` each, in order.
+///
+/// See `bernoulli_iter`, which this collects.
+///
+/// # Panics
+///
+/// If `p` isn't in `[0, 1]`.
+#[cfg(feature = "std")]
+pub fn sample_bernoulli<'a, T, R: Rng>(slice: &'a [T], p: f64, rng: &mut R) -> Vec<&'a T> {
+    bernoulli_iter(slice.iter(), p, rng).collect()
+}
+
+/// The elements of `iter` that survive being kept independently with
+/// probability `p` each, in order.
+///
+/// Rather than drawing once per element, this draws the number of
+/// elements skipped before the next one kept (which is geometrically
+/// distributed), and skips them with `Iterator::nth`. That's one draw
+/// per element kept, and, for iterators with a fast `nth` (such as
+/// those over slices), time proportional to the number kept rather
+/// than the length.
+///
+/// # Panics
+///
+/// If `p` isn't in `[0, 1]`.
+#[cfg(feature = "std")]
+pub fn bernoulli_iter<I: Iterator, R: Rng>(iter: I, p: f64, rng: R) -> BernoulliIter<I, R> {
+    assert!((0.0..=1.0).contains(&p), "probability must be in [0, 1]");
+    BernoulliIter {
+        iter,
+        ln_q: (-p).ln_1p(),
+        rng,
+    }
+}
+
+/// The iterator returned by `bernoulli_iter`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct BernoulliIter<I, R> {
+    iter: I,
+    /// `ln(1 - p)`, which is `-0.0` when nothing is kept, and
+    /// `-infinity` when everything is.
+    ln_q: f64,
+    rng: R,
+}
+
+#[cfg(feature = "std")]
+impl<I: Iterator, R: Rng> Iterator for BernoulliIter<I, R> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.ln_q == 0.0 {
+            return None
+        }
+        // `P(skip >= k) = (1 - p)^k`, by inverting the CDF with a
+        // uniform value in `(0, 1]` (so the logarithm is finite). A
+        // skip too large for `usize` saturates, which exhausts the
+        // iterator.
+        let u = f64::open_closed01(&mut self.rng);
+        let skip = (u.ln() / self.ln_q).floor();
+        self.iter.nth(skip as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.ln_q == 0.0 {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+/// `k` distinct crossover points for genomes of `len` genes, in
+/// increasing order, each selection equally likely: the positions in
+/// `1..len` where a child switches from one parent's genes to the
+/// other's, e.g. `crossover_points(len, 2, rng)` for two-point
+/// crossover.
+///
+/// # Panics
+///
+/// If there are fewer than `k` places to cut, i.e. `k >= len` (unless
+/// `k` is zero).
+#[cfg(feature = "std")]
+pub fn crossover_points<R: Rng>(len: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    if k == 0 {
+        return Vec::new()
+    }
+    assert!(k < len, "cannot choose {} crossover points in {} genes", k, len);
+    let mut points = index::sample(len - 1, k, rng).into_vec();
+    points.sort_unstable();
+    points.into_iter().map(|p| p + 1).collect()
+}
+
+/// A mask of `len` bits, each set independently with probability
+/// `rate`, packed into `u64`s from the least significant bit, e.g. to
+/// flip bits of a genome with `genome[i] ^= mask[i]`.
+///
+/// The set bits are found with `bernoulli_iter`, which skips the unset
+/// ones, so at the low rates mutation uses this takes time
+/// proportional to the number set (besides zeroing the mask).
+///
+/// # Panics
+///
+/// If `rate` isn't in `[0, 1]`.
+#[cfg(feature = "std")]
+pub fn mutation_mask<R: Rng>(len: usize, rate: f64, rng: &mut R) -> Vec<u64> {
+    let mut mask = vec![0; len.div_ceil(64)];
+    for bit in bernoulli_iter(0..len, rate, rng) {
+        mask[bit / 64] |= 1 << (bit % 64);
+    }
+    mask
+}
+
+/// An endless sequence of the elements of `items`, each once per
+/// epoch of `items.len()` elements, in a new uniformly random order
+/// each epoch, e.g. for minibatches that see every example each epoch,
+/// or a loot table that can't go long without any one drop. (For an
+/// empty `items`, the sequence is empty.)
+///
+/// Each element is one step of a Fisher-Yates shuffle of `items`, so
+/// an epoch takes time proportional to its length, with nothing done
+/// up front or between epochs.
+#[cfg(feature = "std")]
+pub fn shuffle_bag<T: Clone, R: Rng>(items: Vec<T>, rng: R) -> ShuffleBag<T, R> {
+    ShuffleBag { items, next: 0, epoch: 0, rng }
+}
+
+/// The iterator returned by `shuffle_bag`.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct ShuffleBag<T, R> {
+    /// Shuffled before `next`, and still to be drawn from after.
+    items: Vec<T>,
+    next: usize,
+    epoch: u64,
+    rng: R,
+}
+
+#[cfg(feature = "std")]
+impl<T, R> ShuffleBag<T, R> {
+    /// The number of epochs finished, i.e. the number of times every
+    /// element has been yielded.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// The number of elements left in the current epoch.
+    pub fn remaining(&self) -> usize {
+        self.items.len() - self.next
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone, R: Rng> Iterator for ShuffleBag<T, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let len = self.items.len();
+        if len == 0 {
+            return None
+        }
+        let i = self.next;
+        self.items.swap(i, i + gen_index(len - i, &mut self.rng));
+        self.next += 1;
+        if self.next == len {
+            // The shuffle of the next epoch starts from this one's
+            // order, which makes no difference to its distribution.
+            self.next = 0;
+            self.epoch += 1;
+        }
+        Some(self.items[i].clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.items.is_empty() {
+            (0, Some(0))
+        } else {
+            (usize::MAX, None)
+        }
+    }
+}
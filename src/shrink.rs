@@ -0,0 +1,311 @@
+//! Shrinking: simpler values than one a constraint produced, so a
+//! property test that fails on a generated value can be cut down to a
+//! minimal failing case, e.g. `shrink::minimize(&(4..321), x, |&x|
+//! fails(x))`.
+//!
+//! This is separate from generating, and optional: a constraint
+//! needn't implement `Shrink`, and nothing here draws randomness.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo, RangeToInclusive};
+#[cfg(feature = "alloc")]
+use text::{Sequence, Text};
+
+/// Constraints that can suggest simpler values than one of theirs.
+pub trait Shrink<T> {
+    type Shrinker: Iterator<Item = T>;
+    /// Values that satisfy the constraint and are simpler than
+    /// `value` (which should satisfy it too), simplest first, and
+    /// none equal to `value`.
+    fn shrink(&self, value: &T) -> Self::Shrinker;
+}
+
+/// The simplest value `constraint` shrinks `value` to while `fails`
+/// still holds, found greedily: take the first candidate that fails,
+/// shrink that, and stop when no candidate fails.
+pub fn minimize<T, C, F>(constraint: &C, mut value: T, mut fails: F) -> T
+    where C: Shrink<T>, F: FnMut(&T) -> bool
+{
+    'outer: loop {
+        for candidate in constraint.shrink(&value) {
+            if fails(&candidate) {
+                value = candidate;
+                continue 'outer;
+            }
+        }
+        return value;
+    }
+}
+
+/// Integers between a target and a value: the target, then halving
+/// the distance back towards the value, e.g. `4, 52, 76, 88, 94, 97,
+/// 99` from 100 towards 4.
+///
+/// The target is the start of the range, or 0 for ranges without a
+/// start that include it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntegerShrinker<T> {
+    value: T,
+    /// How far the next candidate is from `value`.
+    distance: u64,
+    /// Whether the target is below `value`.
+    down: bool,
+}
+
+macro_rules! integer_shrink_impls {
+    ($($t: ident),*) => {$(
+        impl IntegerShrinker<$t> {
+            fn new(value: $t, target: $t) -> IntegerShrinker<$t> {
+                let difference = value as i128 - target as i128;
+                IntegerShrinker {
+                    value,
+                    distance: difference.unsigned_abs() as u64,
+                    down: difference > 0,
+                }
+            }
+        }
+
+        impl Iterator for IntegerShrinker<$t> {
+            type Item = $t;
+
+            fn next(&mut self) -> Option<$t> {
+                if self.distance == 0 {
+                    return None;
+                }
+                let distance = self.distance as i128;
+                let candidate = if self.down { self.value as i128 - distance } else { self.value as i128 + distance };
+                self.distance /= 2;
+                Some(candidate as $t)
+            }
+        }
+    )*}
+}
+integer_shrink_impls!(u8, u32, u64, i64);
+
+macro_rules! unsigned_shrink_impls {
+    ($($t: ident),*) => {$(
+        impl Shrink<$t> for Range<$t> {
+            type Shrinker = IntegerShrinker<$t>;
+            fn shrink(&self, value: &$t) -> IntegerShrinker<$t> {
+                IntegerShrinker::<$t>::new(*value, self.start)
+            }
+        }
+        impl Shrink<$t> for RangeFrom<$t> {
+            type Shrinker = IntegerShrinker<$t>;
+            fn shrink(&self, value: &$t) -> IntegerShrinker<$t> {
+                IntegerShrinker::<$t>::new(*value, self.start)
+            }
+        }
+        impl Shrink<$t> for RangeTo<$t> {
+            type Shrinker = IntegerShrinker<$t>;
+            fn shrink(&self, value: &$t) -> IntegerShrinker<$t> {
+                IntegerShrinker::<$t>::new(*value, 0)
+            }
+        }
+        impl Shrink<$t> for RangeToInclusive<$t> {
+            type Shrinker = IntegerShrinker<$t>;
+            fn shrink(&self, value: &$t) -> IntegerShrinker<$t> {
+                IntegerShrinker::<$t>::new(*value, 0)
+            }
+        }
+        impl Shrink<$t> for RangeFull {
+            type Shrinker = IntegerShrinker<$t>;
+            fn shrink(&self, value: &$t) -> IntegerShrinker<$t> {
+                IntegerShrinker::<$t>::new(*value, 0)
+            }
+        }
+    )*}
+}
+unsigned_shrink_impls!(u8, u32, u64);
+
+impl Shrink<i64> for Range<i64> {
+    type Shrinker = IntegerShrinker<i64>;
+    fn shrink(&self, value: &i64) -> IntegerShrinker<i64> {
+        IntegerShrinker::<i64>::new(*value, self.start)
+    }
+}
+impl Shrink<i64> for RangeFrom<i64> {
+    type Shrinker = IntegerShrinker<i64>;
+    fn shrink(&self, value: &i64) -> IntegerShrinker<i64> {
+        IntegerShrinker::<i64>::new(*value, self.start)
+    }
+}
+impl Shrink<i64> for RangeTo<i64> {
+    type Shrinker = IntegerShrinker<i64>;
+    fn shrink(&self, value: &i64) -> IntegerShrinker<i64> {
+        IntegerShrinker::<i64>::new(*value, 0.min(self.end - 1))
+    }
+}
+impl Shrink<i64> for RangeToInclusive<i64> {
+    type Shrinker = IntegerShrinker<i64>;
+    fn shrink(&self, value: &i64) -> IntegerShrinker<i64> {
+        IntegerShrinker::<i64>::new(*value, 0.min(self.end))
+    }
+}
+impl Shrink<i64> for RangeFull {
+    type Shrinker = IntegerShrinker<i64>;
+    fn shrink(&self, value: &i64) -> IntegerShrinker<i64> {
+        IntegerShrinker::<i64>::new(*value, 0)
+    }
+}
+
+/// Floats between a target and a value, like `IntegerShrinker`,
+/// until halving the distance no longer changes the value.
+///
+/// The target is the start of the range, which is 0 unless given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatShrinker<T> {
+    value: T,
+    /// How far the next candidate is from `value`.
+    gap: T,
+}
+
+macro_rules! float_shrink_impls {
+    ($($t: ident),*) => {$(
+        impl FloatShrinker<$t> {
+            fn new(value: $t, target: $t) -> FloatShrinker<$t> {
+                // Nothing is simpler than a NaN or infinity that
+                // stays in range, so those don't shrink.
+                let gap = value - target;
+                FloatShrinker { value, gap: if gap.is_finite() { gap } else { 0.0 } }
+            }
+        }
+
+        impl Iterator for FloatShrinker<$t> {
+            type Item = $t;
+
+            fn next(&mut self) -> Option<$t> {
+                // (Checking the gap too, since a NaN never equals itself.)
+                let candidate = self.value - self.gap;
+                if self.gap == 0.0 || candidate == self.value {
+                    return None;
+                }
+                self.gap /= 2.0;
+                Some(candidate)
+            }
+        }
+
+        impl Shrink<$t> for Range<$t> {
+            type Shrinker = FloatShrinker<$t>;
+            fn shrink(&self, value: &$t) -> FloatShrinker<$t> {
+                FloatShrinker::<$t>::new(*value, self.start)
+            }
+        }
+        // Like generating, these start at zero.
+        impl Shrink<$t> for RangeTo<$t> {
+            type Shrinker = FloatShrinker<$t>;
+            fn shrink(&self, value: &$t) -> FloatShrinker<$t> {
+                FloatShrinker::<$t>::new(*value, 0.0)
+            }
+        }
+        impl Shrink<$t> for RangeToInclusive<$t> {
+            type Shrinker = FloatShrinker<$t>;
+            fn shrink(&self, value: &$t) -> FloatShrinker<$t> {
+                FloatShrinker::<$t>::new(*value, 0.0)
+            }
+        }
+        impl Shrink<$t> for RangeFull {
+            type Shrinker = FloatShrinker<$t>;
+            fn shrink(&self, value: &$t) -> FloatShrinker<$t> {
+                FloatShrinker::<$t>::new(*value, 0.0)
+            }
+        }
+    )*}
+}
+float_shrink_impls!(f32, f64);
+
+/// Sequences with fewer or simpler elements than a value: first with
+/// runs of elements removed (as long as the length stays at least a
+/// minimum), largest runs first, and then with each element in turn
+/// replaced by the simplest one.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elements<T> {
+    items: Vec<T>,
+    simplest: T,
+    /// The length of the runs being removed, or 0 once replacing.
+    run: usize,
+    /// Where the next run to remove or element to replace starts.
+    position: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone + PartialEq> Elements<T> {
+    fn new(items: Vec<T>, min_len: usize, simplest: T) -> Elements<T> {
+        Elements {
+            run: items.len().saturating_sub(min_len),
+            items,
+            simplest,
+            position: 0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone + PartialEq> Iterator for Elements<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        while self.run > 0 {
+            if self.position + self.run <= self.items.len() {
+                let mut candidate = self.items[..self.position].to_vec();
+                candidate.extend_from_slice(&self.items[self.position + self.run..]);
+                self.position += self.run;
+                return Some(candidate);
+            }
+            self.run /= 2;
+            self.position = 0;
+        }
+        while self.position < self.items.len() {
+            let i = self.position;
+            self.position += 1;
+            if self.items[i] != self.simplest {
+                let mut candidate = self.items.clone();
+                candidate[i] = self.simplest.clone();
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+/// The iterator returned by `Text`'s `shrink`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextShrinker {
+    chars: Elements<char>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for TextShrinker {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.chars.next().map(|chars| chars.into_iter().collect())
+    }
+}
+
+/// Shorter strings, down to the least length, and then with
+/// characters replaced by the charset's first.
+#[cfg(feature = "alloc")]
+impl Shrink<String> for Text {
+    type Shrinker = TextShrinker;
+    fn shrink(&self, value: &String) -> TextShrinker {
+        let Text(ref charset, ref len) = *self;
+        TextShrinker {
+            chars: Elements::new(value.chars().collect(), len.start, charset.chars()[0]),
+        }
+    }
+}
+
+/// Shorter sequences, down to the least length, and then with bases
+/// (or residues) replaced by the charset's first.
+#[cfg(feature = "alloc")]
+impl Shrink<Vec<u8>> for Sequence {
+    type Shrinker = Elements<u8>;
+    fn shrink(&self, value: &Vec<u8>) -> Elements<u8> {
+        let Sequence(ref charset, ref len) = *self;
+        Elements::new(value.clone(), len.start, charset.chars()[0] as u8)
+    }
+}
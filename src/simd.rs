@@ -0,0 +1,162 @@
+//! Streams of eight values at a time, as `wide`'s portable SIMD
+//! vectors, for filling large buffers where drawing one value at a
+//! time is the bottleneck.
+//!
+//! These draw 32 bits per lane, so (unlike the scalar `u32` streams,
+//! which widen each draw to 64 bits) their values differ from the
+//! scalar streams for the same generator.
+
+use float::Float;
+use rand::Rng;
+use std::ops::{Range, RangeFull};
+use stream::{Rand, RandStream};
+use wide::{f32x8, i32x8, u32x8};
+
+/// Eight random `u32`s, from four 64-bit draws.
+fn draw<R: Rng>(rng: &mut R) -> u32x8 {
+    let mut lanes = [0; 8];
+    for pair in lanes.chunks_mut(2) {
+        let v = rng.next_u64();
+        pair[0] = v as u32;
+        pair[1] = (v >> 32) as u32;
+    }
+    u32x8::new(lanes)
+}
+
+/// Vectors of eight independent values, with a stream drawing them
+/// in bulk.
+pub trait Lanes: Copy {
+    type Scalar: Copy;
+    fn to_array(self) -> [Self::Scalar; 8];
+}
+impl Lanes for u32x8 {
+    type Scalar = u32;
+    fn to_array(self) -> [u32; 8] {
+        u32x8::to_array(self)
+    }
+}
+impl Lanes for f32x8 {
+    type Scalar = f32;
+    fn to_array(self) -> [f32; 8] {
+        f32x8::to_array(self)
+    }
+}
+
+/// Fill `dest` with values from `stream`, eight at a time, e.g.
+/// `fill_simd(&u32x8::rand(0..6), rng, &mut buffer)`.
+///
+/// If the length isn't a multiple of eight, the last vector is only
+/// partly used.
+pub fn fill_simd<V, S, R>(stream: &S, rng: &mut R, dest: &mut [V::Scalar])
+    where V: Lanes, S: RandStream<V>, R: Rng
+{
+    for chunk in dest.chunks_mut(8) {
+        let lanes = stream.next(rng).to_array();
+        chunk.copy_from_slice(&lanes[..chunk.len()]);
+    }
+}
+
+/// Every `u32` in each lane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U32x8Full;
+
+/// `low..low + range` in each lane, as the high half of a widening
+/// multiply of a 32-bit draw by `range`, rejecting (one lane at a
+/// time) when the low half is below `threshold`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U32x8Bounded {
+    low: u32,
+    range: u32,
+    threshold: u32,
+}
+
+impl Rand<RangeFull> for u32x8 {
+    type Stream = U32x8Full;
+    fn rand(_: RangeFull) -> U32x8Full {
+        U32x8Full
+    }
+}
+impl Rand<Range<u32>> for u32x8 {
+    type Stream = U32x8Bounded;
+    fn rand(dist: Range<u32>) -> U32x8Bounded {
+        assert!(dist.start < dist.end);
+        let range = dist.end - dist.start;
+        U32x8Bounded {
+            low: dist.start,
+            range,
+            threshold: range.wrapping_neg() % range,
+        }
+    }
+}
+
+impl RandStream<u32x8> for U32x8Full {
+    fn next<R: Rng>(&self, rng: &mut R) -> u32x8 {
+        draw(rng)
+    }
+}
+impl RandStream<u32x8> for U32x8Bounded {
+    fn next<R: Rng>(&self, rng: &mut R) -> u32x8 {
+        let range = u32x8::splat(self.range);
+        let v = draw(rng);
+        let mut high = v.mul_keep_high(range).to_array();
+        let low = (v * range).to_array();
+        // A lane is rejected with probability `threshold / 2^32`, so
+        // this is very rarely needed; redraw just those lanes.
+        for (h, &l) in high.iter_mut().zip(&low) {
+            let mut l = l;
+            while l < self.threshold {
+                let m = u64::from(rng.next_u32()) * u64::from(self.range);
+                *h = (m >> 32) as u32;
+                l = m as u32;
+            }
+        }
+        u32x8::splat(self.low) + u32x8::new(high)
+    }
+}
+
+/// `[0, 1)` in each lane, as a 24-bit draw divided by `2^24`, like
+/// `f32::rand(..)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct F32x8Full;
+
+/// `[start, end)` in each lane, as `(low + x * width) * scale` for `x`
+/// in `[0, 1)` (see `stream::FloatStreamBounded`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct F32x8Bounded {
+    low: f32,
+    width: f32,
+    scale: f32,
+}
+
+impl Rand<RangeFull> for f32x8 {
+    type Stream = F32x8Full;
+    fn rand(_: RangeFull) -> F32x8Full {
+        F32x8Full
+    }
+}
+impl Rand<Range<f32>> for f32x8 {
+    type Stream = F32x8Bounded;
+    fn rand(dist: Range<f32>) -> F32x8Bounded {
+        let (low, width, scale) = f32::half_open(dist.start, dist.end);
+        F32x8Bounded { low, width, scale }
+    }
+}
+
+impl RandStream<f32x8> for F32x8Full {
+    fn next<R: Rng>(&self, rng: &mut R) -> f32x8 {
+        // Below `2^24`, so exact as an `i32`, and as an `f32`.
+        let m: u32x8 = draw(rng) >> 8u32;
+        let m = m.to_array();
+        let m = i32x8::new([m[0] as i32, m[1] as i32, m[2] as i32, m[3] as i32,
+                            m[4] as i32, m[5] as i32, m[6] as i32, m[7] as i32]);
+        f32x8::from_i32x8(m) * f32x8::splat(1.0 / (1 << 24) as f32)
+    }
+}
+impl RandStream<f32x8> for F32x8Bounded {
+    fn next<R: Rng>(&self, rng: &mut R) -> f32x8 {
+        // Separate operations (not `mul_add`), rounding like the
+        // scalar stream, so the result stays below `end`.
+        let x = F32x8Full.next(rng);
+        (f32x8::splat(self.low) + x * f32x8::splat(self.width)) * f32x8::splat(self.scale)
+    }
+}
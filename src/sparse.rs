@@ -0,0 +1,99 @@
+//! Sparse random vectors and matrices, as their non-zero entries, e.g.
+//! for inputs to sparse solvers with millions of rows.
+//!
+//! Each index is present independently with probability `density`, but
+//! rather than flipping a coin for each, the gap to the next present
+//! index is drawn directly, from the geometric distribution. So
+//! generating takes time proportional to the number of entries, not
+//! the length.
+
+use float::Float;
+use rand::Rng;
+use stream::{Rand, RandStream};
+
+/// The entries of a sparse vector of length `len`, as `(index, value)`
+/// pairs in increasing order of index, with each index present with
+/// probability `density` and its value from `dist`, e.g.
+/// `sparse::<f64, _, _>(rng, 1_000_000, 0.001, -1.0..1.0)` for about
+/// a thousand entries.
+///
+/// # Panics
+///
+/// If `density` isn't in `[0, 1]`.
+pub fn sparse<Gen: Rand<Dist>, Dist, R: Rng>(rng: R, len: usize, density: f64, dist: Dist)
+    -> Sparse<Gen, Dist, R>
+{
+    assert!((0.0..=1.0).contains(&density), "density must be in [0, 1]");
+    Sparse {
+        values: Gen::rand(dist),
+        rng,
+        // With no entries, start at the end.
+        position: if density == 0.0 { len } else { 0 },
+        len,
+        ln_skip: (-density).ln_1p(),
+    }
+}
+
+/// The iterator returned by `sparse`.
+pub struct Sparse<Gen: Rand<Dist>, Dist, R: Rng> {
+    values: Gen::Stream,
+    rng: R,
+    /// The first index that could be next.
+    position: usize,
+    len: usize,
+    /// `ln(1 - density)`, the log of the probability of skipping an
+    /// index.
+    ln_skip: f64,
+}
+
+impl<Gen: Rand<Dist>, Dist, R: Rng> Sparse<Gen, Dist, R> {
+    /// The entries as a `rows` by `cols` matrix, `((row, col), value)`
+    /// in row-major order, for a vector of length `rows * cols`.
+    ///
+    /// # Panics
+    ///
+    /// If the vector's length isn't `rows * cols`.
+    pub fn matrix(self, rows: usize, cols: usize) -> SparseMatrix<Gen, Dist, R> {
+        assert!(rows.checked_mul(cols) == Some(self.len), "vector length must be rows * cols");
+        SparseMatrix { entries: self, cols }
+    }
+}
+
+impl<Gen: Rand<Dist>, Dist, R: Rng> Iterator for Sparse<Gen, Dist, R> {
+    type Item = (usize, Gen);
+
+    fn next(&mut self) -> Option<(usize, Gen)> {
+        if self.position >= self.len {
+            return None;
+        }
+        // `floor(ln u / ln(1 - density))` indices are skipped, which
+        // saturates for huge gaps; with a density of one, `ln_skip` is
+        // -∞, and there are none.
+        let skip = (f64::open_closed01(&mut self.rng).ln() / self.ln_skip) as usize;
+        match self.position.checked_add(skip) {
+            Some(index) if index < self.len => {
+                self.position = index + 1;
+                Some((index, self.values.next(&mut self.rng)))
+            }
+            _ => {
+                self.position = self.len;
+                None
+            }
+        }
+    }
+}
+
+/// The iterator returned by `Sparse::matrix`.
+pub struct SparseMatrix<Gen: Rand<Dist>, Dist, R: Rng> {
+    entries: Sparse<Gen, Dist, R>,
+    cols: usize,
+}
+
+impl<Gen: Rand<Dist>, Dist, R: Rng> Iterator for SparseMatrix<Gen, Dist, R> {
+    type Item = ((usize, usize), Gen);
+
+    fn next(&mut self) -> Option<((usize, usize), Gen)> {
+        let (index, value) = self.entries.next()?;
+        Some(((index / self.cols, index % self.cols), value))
+    }
+}
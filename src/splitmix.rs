@@ -0,0 +1,87 @@
+//! The SplitMix64 generator, for deriving many decorrelated seeds from
+//! one, e.g. for the hash functions of a Bloom filter.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+use rand::{Rand, Rng, SeedableRng};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+/// `k` seeds for hash functions, e.g. `hash_seeds(user_seed, 7)` for
+/// a Bloom filter with seven: the first `k` outputs of
+/// `SplitMix64::new(seed)`.
+///
+/// The same `seed` always gives the same seeds, so a filter can be
+/// rebuilt (or shared) from it, while the seeds themselves are
+/// thoroughly mixed, and distinct, even for seeds such as 0, 1, 2.
+#[cfg(feature = "alloc")]
+pub fn hash_seeds(seed: u64, k: usize) -> Vec<u64> {
+    let mut rng = SplitMix64::new(seed);
+    (0..k).map(|_| rng.next_u64()).collect()
+}
+
+/// Vigna's SplitMix64: a counter stepped by the golden ratio, with
+/// each value hashed by a variant of MurmurHash3's finalizer.
+///
+/// With 64 bits of state, this isn't for long simulations, but every
+/// seed (including 0) is good, consecutive outputs are never equal,
+/// and nearby seeds give unrelated outputs, which makes it the usual
+/// choice for turning one number into seeds for something else.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl SeedableRng<u64> for SplitMix64 {
+    fn reseed(&mut self, seed: u64) {
+        *self = SplitMix64::new(seed);
+    }
+
+    fn from_seed(seed: u64) -> SplitMix64 {
+        SplitMix64::new(seed)
+    }
+}
+
+impl Rand for SplitMix64 {
+    fn rand<R: Rng>(rng: &mut R) -> SplitMix64 {
+        SplitMix64::new(rng.gen())
+    }
+}
+
+/// Wipes the state, so it can't be recovered from memory; this also
+/// happens on drop.
+#[cfg(feature = "zeroize")]
+impl Zeroize for SplitMix64 {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SplitMix64 {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -1,14 +1,63 @@
+#[cfg(feature = "std")]
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::iter::StepBy;
 use std::marker;
-use std::ops::{Range, RangeFull};
+use std::mem::MaybeUninit;
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo, RangeToInclusive};
+#[cfg(feature = "std")]
+use std::ops::RangeInclusive;
+use {Closed01, HighPrecision01, Open01, OpenClosed01};
+use float::Float;
 use rand::Rng;
+use uniform::Uniform;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
-pub trait Rand<Distribution> {
+pub trait Rand<Distribution>: Sized {
     type Stream: RandStream<Self>;
     fn rand(dist: Distribution) -> Self::Stream;
 }
 
+/// Values drawn with the work for a constraint done up front.
+///
+/// The crate's streams are all `Send + Sync`, so one can be shared
+/// between threads, or built once and kept in a global.
 pub trait RandStream<T> {
     fn next<R: Rng>(&self, rng: &mut R) -> T;
+
+    /// An infinite sequence of values, borrowing both the stream and
+    /// the generator, so one stream can feed many short loops, e.g.
+    /// `for x in stream.sample_iter(&mut rng).take(10)`.
+    fn sample_iter<'a, R: Rng>(&'a self, rng: &'a mut R) -> SampleIter<'a, T, Self, R>
+        where Self: Sized
+    {
+        SampleIter {
+            stream: self,
+            rng,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+/// The iterator returned by `RandStream::sample_iter`.
+#[derive(Debug)]
+pub struct SampleIter<'a, T, S: 'a, R: 'a> {
+    stream: &'a S,
+    rng: &'a mut R,
+    _marker: marker::PhantomData<fn() -> T>,
+}
+impl<'a, T, S: RandStream<T>, R: Rng> Iterator for SampleIter<'a, T, S, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.stream.next(self.rng))
+    }
 }
 
 /// Create a single random value, mediated by `constraint`.
@@ -18,13 +67,63 @@ pub fn gen<Gen: Rand<Dist>, Dist, R: Rng>(rng: &mut R, dist: Dist)
     Gen::rand(dist).next(rng)
 }
 
+/// How many constraints `gen_memo` remembers per thread.
+#[cfg(feature = "std")]
+const MEMO_ENTRIES: usize = 8;
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// The streams for the constraints `gen_memo` has seen most
+    /// recently, most recent first, each as `(Dist, Stream)` tagged
+    /// with the `TypeId` of `(Gen, Dist)`.
+    static MEMO: RefCell<Vec<(TypeId, Box<dyn Any>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Create a single random value like `gen`, but reuse the stream from
+/// an earlier call on this thread with the same type and constraint,
+/// if it's among the last few used.
+///
+/// This trades the work of building a stream for a search of a small
+/// per-thread cache, which only pays off for constraints that are
+/// expensive to build: for a `u32` range, the `range_gen__memo`
+/// benchmark is several times slower than `range_gen`.
+#[cfg(feature = "std")]
+pub fn gen_memo<Gen, Dist, R: Rng>(rng: &mut R, dist: Dist) -> Gen
+    where Gen: Rand<Dist> + 'static, Dist: Clone + PartialEq + 'static, Gen::Stream: Clone + 'static
+{
+    let key = TypeId::of::<(Gen, Dist)>();
+    // The stream is cloned out so the cache isn't borrowed while it
+    // runs, in case it uses `gen_memo` too.
+    let stream = MEMO.with(|memo| {
+        let mut memo = memo.borrow_mut();
+        let found = memo.iter().position(|&(id, ref entry)| {
+            id == key && entry.downcast_ref::<(Dist, Gen::Stream)>().unwrap().0 == dist
+        });
+        match found {
+            Some(i) => {
+                let entry = memo.remove(i);
+                let stream = entry.1.downcast_ref::<(Dist, Gen::Stream)>().unwrap().1.clone();
+                memo.insert(0, entry);
+                stream
+            }
+            None => {
+                let stream = Gen::rand(dist.clone());
+                memo.truncate(MEMO_ENTRIES - 1);
+                memo.insert(0, (key, Box::new((dist, stream.clone()))));
+                stream
+            }
+        }
+    });
+    stream.next(rng)
+}
+
 /// Create an infinite sequence of random values, mediated by `constraint`.
 pub fn gen_iter<Gen: Rand<Dist>, Dist, R: Rng>(rng: R, dist: Dist)
     -> GenIter<Gen, Dist, R>
 {
     GenIter {
         stream: Gen::rand(dist),
-        rng: rng,
+        rng,
     }
 }
 pub struct GenIter<Gen: Rand<Dist>, Dist, R: Rng> {
@@ -39,70 +138,372 @@ impl<Gen: Rand<Dist>, Dist, R: Rng> Iterator for GenIter<Gen, Dist, R> {
     }
 }
 
+/// Create an infinite sequence of blocks of `K` random values, mediated
+/// by `constraint`, e.g. `gen_chunks::<f32, 8, _, _>(rng, -1.0..1.0)`
+/// for a consumer that processes eight lanes at a time.
+///
+/// The values are the same, in the same order, as those of
+/// `gen_iter`, just grouped, so this saves chunking a scalar iterator
+/// (and lets `K` be known to the optimizer). For `wide`'s vectors
+/// themselves, see the `simd` module.
+pub fn gen_chunks<Gen: Rand<Dist>, const K: usize, Dist, R: Rng>(rng: R, dist: Dist)
+    -> GenChunks<Gen, K, Dist, R>
+{
+    GenChunks {
+        stream: Gen::rand(dist),
+        rng,
+    }
+}
+pub struct GenChunks<Gen: Rand<Dist>, const K: usize, Dist, R: Rng> {
+    stream: Gen::Stream,
+    rng: R,
+}
+impl<Gen: Rand<Dist>, const K: usize, Dist, R: Rng> Iterator for GenChunks<Gen, K, Dist, R> {
+    type Item = [Gen; K];
+
+    fn next(&mut self) -> Option<[Gen; K]> {
+        Some(std::array::from_fn(|_| self.stream.next(&mut self.rng)))
+    }
+}
+
+/// Fill `dest` with values from `stream`, returning it as initialized,
+/// e.g. for a large buffer from `Vec::spare_capacity_mut` that isn't
+/// worth zeroing first.
+///
+/// If `stream` panics, the values written so far are leaked.
+pub fn fill_uninit<'a, T, S: RandStream<T>, R: Rng>(stream: &S, rng: &mut R,
+                                                     dest: &'a mut [MaybeUninit<T>])
+                                                     -> &'a mut [T]
+{
+    for slot in dest.iter_mut() {
+        slot.write(stream.next(rng));
+    }
+    // SAFETY: every element was just written, and `MaybeUninit<T>`
+    // has the same layout as `T`.
+    unsafe { &mut *(dest as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+/// Append `n` values from `stream` to `dest`, e.g. to refill a buffer
+/// that's reused between batches.
+///
+/// This reserves room for all `n` values once, and then writes them
+/// with `fill_uninit`, so unlike `gen_iter(..).take(n).collect()`, it
+/// doesn't grow the vector as it goes (`GenIter` is endless, so
+/// `take(n)` can't promise `collect` `n` values up front), nor check
+/// the capacity for each value. It's fastest compared to that when
+/// `dest` already has the capacity (so nothing is allocated at all),
+/// and for cheap streams (such as integer ranges) whose per-value cost
+/// is comparable to that bookkeeping; for expensive streams, the two
+/// are about the same.
+///
+/// If `stream` panics, the values written so far are leaked, and
+/// `dest` keeps its old length.
+#[cfg(feature = "alloc")]
+pub fn sample_n_into<T, S: RandStream<T>, R: Rng>(stream: &S, rng: &mut R, dest: &mut Vec<T>, n: usize) {
+    dest.reserve(n);
+    let len = dest.len();
+    fill_uninit(stream, rng, &mut dest.spare_capacity_mut()[..n]);
+    // SAFETY: the `n` values after the old length were just written.
+    unsafe { dest.set_len(len + n) };
+}
+
+/// The method forms of `gen` and `gen_iter`, for every `Rng`. These
+/// aren't called `gen` and `gen_iter`, so as not to clash with the
+/// methods of `Rng` itself.
+pub trait RngExt: Rng {
+    /// Create a single random value, e.g. `rng.random::<u32, _>(4..321)`.
+    fn random<Gen: Rand<Dist>, Dist>(&mut self, dist: Dist) -> Gen
+        where Self: Sized
+    {
+        gen(self, dist)
+    }
+
+    /// Create an infinite sequence of random values, borrowing the
+    /// generator.
+    fn random_iter<Gen: Rand<Dist>, Dist>(&mut self, dist: Dist) -> GenIter<Gen, Dist, &mut Self>
+        where Self: Sized
+    {
+        gen_iter(self, dist)
+    }
+
+    /// `true` with probability `p`; see `Bernoulli`.
+    fn gen_bool(&mut self, p: f64) -> bool
+        where Self: Sized
+    {
+        gen(self, Bernoulli(p))
+    }
+
+    /// `true` with probability exactly `num / den`; see `Ratio`.
+    fn gen_ratio(&mut self, num: u32, den: u32) -> bool
+        where Self: Sized
+    {
+        gen(self, Ratio(num, den))
+    }
+}
+impl<R: Rng + ?Sized> RngExt for R {}
+
+/// Values in `low..low + range` (wrapping), with the rejection
+/// threshold for `range` precomputed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct IntegerStreamBounded<T> {
     low: T,
     range: T,
-    accept_zone: T,
+    threshold: T,
 }
+/// Values in `low..low + range` (wrapping), like `IntegerStreamBounded`
+/// but a third smaller, for the types whose draws are widened (`u8` and
+/// `u32`). The threshold only matters for draws that land in a zone
+/// smaller than `range`, out of `2^32` (`u8`) or `2^64` (`u32`), so
+/// it's computed on the rare occasions that happens, rather than
+/// stored.
+///
+/// That saves the division when creating the stream (`range_gen` in
+/// `benches/compare.rs` is about 35-40% faster), but costs an extra
+/// comparison per value: `range_iter__worst_u8` and
+/// `range_iter__worst_u32` are about 15% slower, while `range_iter`
+/// and `range_iter__u8` are no slower.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntegerStreamCompact<T> {
+    low: T,
+    range: T,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct IntegerStreamFull<T> {
     _marker: marker::PhantomData<T>,
 }
+/// Values in an inclusive range, for `RangeFrom` and
+/// `RangeToInclusive`. This may be every value of the type, which the
+/// stream for `Range` can't represent, hence `bounded` is `None` in
+/// that case.
+pub struct IntegerStreamInclusive<T: Rand<Range<T>>> {
+    bounded: Option<<T as Rand<Range<T>>>::Stream>,
+}
 
-impl Rand<Range<u32>> for u32 {
-    type Stream = IntegerStreamBounded<u32>;
-    fn rand(dist: Range<u32>) -> IntegerStreamBounded<u32> {
-        assert!(dist.start < dist.end);
-        let range = dist.end - dist.start;
-        let max = !0;
-        let zone = max - (max % range);
-        IntegerStreamBounded {
-            low: dist.start,
-            range: range,
-            accept_zone: zone,
-        }
+// `derive` would only bound `T`, not the `Range` stream it contains.
+impl<T: Rand<Range<T>>> Clone for IntegerStreamInclusive<T>
+    where T::Stream: Clone
+{
+    fn clone(&self) -> Self {
+        IntegerStreamInclusive { bounded: self.bounded.clone() }
     }
 }
-impl Rand<RangeFull> for u32 {
-    type Stream = IntegerStreamFull<u32>;
-    fn rand(_dist: RangeFull) -> IntegerStreamFull<u32> {
-        IntegerStreamFull {
-            _marker: marker::PhantomData,
+impl<T: Rand<Range<T>>> Copy for IntegerStreamInclusive<T> where T::Stream: Copy {}
+impl<T: Rand<Range<T>>> fmt::Debug for IntegerStreamInclusive<T>
+    where T::Stream: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IntegerStreamInclusive")
+            .field("bounded", &self.bounded)
+            .finish()
+    }
+}
+impl<T: Rand<Range<T>>> PartialEq for IntegerStreamInclusive<T>
+    where T::Stream: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.bounded == other.bounded
+    }
+}
+impl<T: Rand<Range<T>>> Eq for IntegerStreamInclusive<T> where T::Stream: Eq {}
+
+impl<T: Rand<Range<T>>> RandStream<T> for IntegerStreamInclusive<T>
+    where IntegerStreamFull<T>: RandStream<T>
+{
+    fn next<R: Rng>(&self, rng: &mut R) -> T {
+        match self.bounded {
+            Some(ref bounded) => bounded.next(rng),
+            None => IntegerStreamFull { _marker: marker::PhantomData }.next(rng),
         }
     }
 }
 
-impl RandStream<u32> for IntegerStreamBounded<u32> {
-    fn next<R: Rng>(&self, rng: &mut R) -> u32 {
-        loop {
-            let v = rng.next_u32();
+/// Sample from `0..range` for a power of two `range`, which is when the
+/// threshold is zero: nothing is rejected, and the high half of the
+/// product with `range` is just the top bits of the draw, so this
+/// gives the same values as `Uniform::sample` without the multiply or
+/// the loop.
+#[inline]
+fn sample_pow2<R: Rng>(range: u64, rng: &mut R) -> u64 {
+    // Two shifts, since `range == 1` would need a shift by 64.
+    rng.next_u64() >> 1 >> range.leading_zeros()
+}
 
-            if v < self.accept_zone {
-                return self.low.wrapping_add((v % self.range))
+macro_rules! bounded_impls {
+    ($($t: ident),*) => {$(
+        impl IntegerStreamBounded<$t> {
+            fn new(low: $t, range: $t) -> IntegerStreamBounded<$t> {
+                IntegerStreamBounded {
+                    low,
+                    range,
+                    threshold: Uniform::threshold(range),
+                }
+            }
+
+            /// The smallest value.
+            pub fn low(&self) -> $t {
+                self.low
+            }
+
+            /// The number of possible values.
+            pub fn width(&self) -> $t {
+                self.range
             }
         }
-    }
+
+        impl RandStream<$t> for IntegerStreamBounded<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                if self.threshold == 0 {
+                    return self.low.wrapping_add(sample_pow2(self.range as u64, rng) as $t)
+                }
+                self.low.wrapping_add(Uniform::sample(self.range, self.threshold, rng))
+            }
+        }
+    )*}
 }
+bounded_impls!(u64);
 
-impl RandStream<u32> for IntegerStreamFull<u32> {
-    fn next<R: Rng>(&self, rng: &mut R) -> u32 {
-        rng.next_u32()
-    }
+macro_rules! compact_impls {
+    ($($t: ident),*) => {$(
+        impl IntegerStreamCompact<$t> {
+            fn new(low: $t, range: $t) -> IntegerStreamCompact<$t> {
+                IntegerStreamCompact { low, range }
+            }
+
+            /// The smallest value.
+            pub fn low(&self) -> $t {
+                self.low
+            }
+
+            /// The number of possible values.
+            pub fn width(&self) -> $t {
+                self.range
+            }
+        }
+
+        impl RandStream<$t> for IntegerStreamCompact<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                self.low.wrapping_add(Uniform::sample_lazy(self.range, rng))
+            }
+        }
+    )*}
 }
+compact_impls!(u8, u32);
+
+macro_rules! unsigned_impls {
+    ($($t: ident, $next: ident, $bounded: ident;)*) => {$(
+        impl Rand<Range<$t>> for $t {
+            type Stream = $bounded<$t>;
+            fn rand(dist: Range<$t>) -> $bounded<$t> {
+                assert!(dist.start < dist.end);
+                $bounded::<$t>::new(dist.start, dist.end - dist.start)
+            }
+        }
+        impl Rand<RangeFrom<$t>> for $t {
+            type Stream = IntegerStreamInclusive<$t>;
+            fn rand(dist: RangeFrom<$t>) -> IntegerStreamInclusive<$t> {
+                let bounded = ($t::MAX - dist.start).checked_add(1).map(|range| {
+                    $bounded::<$t>::new(dist.start, range)
+                });
+                IntegerStreamInclusive { bounded }
+            }
+        }
+        impl Rand<RangeTo<$t>> for $t {
+            type Stream = $bounded<$t>;
+            fn rand(dist: RangeTo<$t>) -> $bounded<$t> {
+                $t::rand(0..dist.end)
+            }
+        }
+        impl Rand<RangeToInclusive<$t>> for $t {
+            type Stream = IntegerStreamInclusive<$t>;
+            fn rand(dist: RangeToInclusive<$t>) -> IntegerStreamInclusive<$t> {
+                IntegerStreamInclusive {
+                    bounded: dist.end.checked_add(1).map(|end| $t::rand(0..end)),
+                }
+            }
+        }
+        impl Rand<RangeFull> for $t {
+            type Stream = IntegerStreamFull<$t>;
+            fn rand(_dist: RangeFull) -> IntegerStreamFull<$t> {
+                IntegerStreamFull {
+                    _marker: marker::PhantomData,
+                }
+            }
+        }
 
+        impl IntegerStreamInclusive<$t> {
+            /// The smallest value.
+            pub fn low(&self) -> $t {
+                self.bounded.as_ref().map_or(0, |b| b.low())
+            }
 
-use std::mem;
+            /// The number of possible values, or `None` if every value
+            /// of the type is possible (which is one too many to fit).
+            pub fn width(&self) -> Option<$t> {
+                self.bounded.as_ref().map(|b| b.width())
+            }
+        }
+
+        impl RandStream<$t> for IntegerStreamFull<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                rng.$next() as $t
+            }
+        }
+    )*}
+}
+unsigned_impls! {
+    u8, next_u32, IntegerStreamCompact;
+    u32, next_u32, IntegerStreamCompact;
+    u64, next_u64, IntegerStreamBounded;
+}
+
+impl IntegerStreamBounded<i64> {
+    fn new(low: i64, range: u64) -> IntegerStreamBounded<i64> {
+        IntegerStreamBounded {
+            low,
+            range: range as i64,
+            threshold: u64::threshold(range) as i64,
+        }
+    }
+
+    /// The smallest value.
+    pub fn low(&self) -> i64 {
+        self.low
+    }
+
+    /// The number of possible values.
+    pub fn width(&self) -> u64 {
+        self.range as u64
+    }
+}
 
 impl Rand<Range<i64>> for i64 {
     type Stream = IntegerStreamBounded<i64>;
     fn rand(dist: Range<i64>) -> IntegerStreamBounded<i64> {
         assert!(dist.start < dist.end);
-        let range = dist.end.wrapping_sub(dist.start);
-        let max = !0;
-        let zone = max - (max % range);
-        IntegerStreamBounded {
-            low: dist.start,
-            range: range,
-            accept_zone: unsafe {mem::transmute(zone)},
+        IntegerStreamBounded::<i64>::new(dist.start, dist.end.wrapping_sub(dist.start) as u64)
+    }
+}
+impl Rand<RangeFrom<i64>> for i64 {
+    type Stream = IntegerStreamInclusive<i64>;
+    fn rand(dist: RangeFrom<i64>) -> IntegerStreamInclusive<i64> {
+        let span = i64::MAX.wrapping_sub(dist.start) as u64;
+        let bounded = span.checked_add(1).map(|range| {
+            IntegerStreamBounded::<i64>::new(dist.start, range)
+        });
+        IntegerStreamInclusive { bounded }
+    }
+}
+impl Rand<RangeTo<i64>> for i64 {
+    type Stream = IntegerStreamBounded<i64>;
+    fn rand(dist: RangeTo<i64>) -> IntegerStreamBounded<i64> {
+        i64::rand(i64::MIN..dist.end)
+    }
+}
+impl Rand<RangeToInclusive<i64>> for i64 {
+    type Stream = IntegerStreamInclusive<i64>;
+    fn rand(dist: RangeToInclusive<i64>) -> IntegerStreamInclusive<i64> {
+        IntegerStreamInclusive {
+            bounded: dist.end.checked_add(1).map(|end| i64::rand(i64::MIN..end)),
         }
     }
 }
@@ -115,117 +516,646 @@ impl Rand<RangeFull> for i64 {
     }
 }
 
+impl IntegerStreamInclusive<i64> {
+    /// The smallest value.
+    pub fn low(&self) -> i64 {
+        self.bounded.as_ref().map_or(i64::MIN, |b| b.low())
+    }
+
+    /// The number of possible values, or `None` if every value of the
+    /// type is possible (which is one too many to fit).
+    pub fn width(&self) -> Option<u64> {
+        self.bounded.as_ref().map(|b| b.width())
+    }
+}
 
 impl RandStream<i64> for IntegerStreamBounded<i64> {
     fn next<R: Rng>(&self, rng: &mut R) -> i64 {
-        let zone: u64 = unsafe {mem::transmute(self.accept_zone)};
-        let range: u64 = unsafe {mem::transmute(self.range)};
-        loop {
-            let v = rng.next_u64();
-
-            if v < zone {
-                let value: i64 = unsafe {mem::transmute(v % range)};
-                return self.low.wrapping_add(value)
-            }
+        if self.threshold == 0 {
+            return self.low.wrapping_add(sample_pow2(self.range as u64, rng) as i64)
         }
+        let v = u64::sample(self.range as u64, self.threshold as u64, rng);
+        self.low.wrapping_add(v as i64)
     }
 }
 impl RandStream<i64> for IntegerStreamFull<i64> {
     fn next<R: Rng>(&self, rng: &mut R) -> i64 {
-        unsafe {mem::transmute(rng.next_u64())}
+        rng.next_u64() as i64
     }
 }
 
+/// Values in `[start, end)`, as `(low + x * width) * scale` (see
+/// `Float::half_open`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatStreamBounded<T> {
+    low: T,
+    width: T,
+    scale: T,
+}
+/// Values in `low..=low + width`, for `RangeToInclusive`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatStreamClosed<T> {
+    low: T,
+    width: T,
+}
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatStreamFull<T> {
+    _marker: marker::PhantomData<T>,
+}
+
+macro_rules! float_impls {
+    ($($t: ident),*) => {$(
+        impl Rand<Range<$t>> for $t {
+            type Stream = FloatStreamBounded<$t>;
+            fn rand(dist: Range<$t>) -> FloatStreamBounded<$t> {
+                let (low, width, scale) = <$t>::half_open(dist.start, dist.end);
+                FloatStreamBounded { low, width, scale }
+            }
+        }
+        // Like the `RangeFull` impl, these start at zero, so `..end`
+        // is `0.0..end`.
+        impl Rand<RangeTo<$t>> for $t {
+            type Stream = FloatStreamBounded<$t>;
+            fn rand(dist: RangeTo<$t>) -> FloatStreamBounded<$t> {
+                $t::rand(0.0..dist.end)
+            }
+        }
+        impl Rand<RangeToInclusive<$t>> for $t {
+            type Stream = FloatStreamClosed<$t>;
+            fn rand(dist: RangeToInclusive<$t>) -> FloatStreamClosed<$t> {
+                FloatStreamClosed {
+                    low: 0.0,
+                    width: <$t>::closed_width(dist.end),
+                }
+            }
+        }
+        impl Rand<RangeFull> for $t {
+            type Stream = FloatStreamFull<$t>;
+            fn rand(_dist: RangeFull) -> FloatStreamFull<$t> {
+                FloatStreamFull {
+                    _marker: marker::PhantomData,
+                }
+            }
+        }
+        // The interval constraints need no precomputation, so they
+        // are their own streams.
+        impl Rand<Open01> for $t {
+            type Stream = Open01;
+            fn rand(dist: Open01) -> Open01 {
+                dist
+            }
+        }
+        impl Rand<Closed01> for $t {
+            type Stream = Closed01;
+            fn rand(dist: Closed01) -> Closed01 {
+                dist
+            }
+        }
+        impl Rand<OpenClosed01> for $t {
+            type Stream = OpenClosed01;
+            fn rand(dist: OpenClosed01) -> OpenClosed01 {
+                dist
+            }
+        }
+        impl Rand<HighPrecision01> for $t {
+            type Stream = HighPrecision01;
+            fn rand(dist: HighPrecision01) -> HighPrecision01 {
+                dist
+            }
+        }
+
+        impl FloatStreamBounded<$t> {
+            /// The start of the range, which can appear.
+            pub fn low(&self) -> $t {
+                self.low * self.scale
+            }
 
-#[cfg(test)]
-use test::{Bencher, black_box};
-#[cfg(test)]
-use rand;
+            /// The width of the range. This can be slightly less than
+            /// `end - start` (to keep values below `end`), and is
+            /// infinite if that overflows.
+            pub fn width(&self) -> $t {
+                self.width * self.scale
+            }
+        }
+        impl FloatStreamClosed<$t> {
+            /// The start of the range, which can appear.
+            pub fn low(&self) -> $t {
+                self.low
+            }
 
-#[bench]
-fn iter(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
+            /// The width of the range: `low + width` can appear too.
+            pub fn width(&self) -> $t {
+                self.width
+            }
+        }
 
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), ..).take(100)) {
-            black_box(x);
+        impl RandStream<$t> for FloatStreamBounded<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                (self.low + <$t>::closed_open01(rng) * self.width) * self.scale
+            }
+        }
+        impl RandStream<$t> for FloatStreamClosed<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                self.low + <$t>::closed01(rng) * self.width
+            }
+        }
+        impl RandStream<$t> for FloatStreamFull<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                <$t>::closed_open01(rng)
+            }
         }
-    })
+        impl RandStream<$t> for Open01 {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                <$t>::open01(rng)
+            }
+        }
+        impl RandStream<$t> for Closed01 {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                <$t>::closed01(rng)
+            }
+        }
+        impl RandStream<$t> for OpenClosed01 {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                <$t>::open_closed01(rng)
+            }
+        }
+        impl RandStream<$t> for HighPrecision01 {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                <$t>::high_precision01(rng)
+            }
+        }
+    )*}
 }
+float_impls!(f32, f64);
+
+/// Values in `LOW..HIGH`, with the bounds fixed at compile time, e.g.
+/// `gen::<u32, _, _>(rng, ConstRange::<4, 321>)`.
+///
+/// The rejection threshold is a constant too, so this is its own
+/// (zero-sized) stream, with nothing to compute when it's created. It
+/// gives exactly the same values as `LOW..HIGH`.
+///
+/// Bounds that are empty or don't fit the type are a compile error
+/// where the stream is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstRange<const LOW: u64, const HIGH: u64>;
 
-#[bench]
-fn iter__noiterbb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
+macro_rules! const_range_impls {
+    ($($t: ident, $wide: ident;)*) => {$(
+        impl<const LOW: u64, const HIGH: u64> Rand<ConstRange<LOW, HIGH>> for $t {
+            type Stream = ConstRange<LOW, HIGH>;
+            fn rand(dist: ConstRange<LOW, HIGH>) -> ConstRange<LOW, HIGH> {
+                dist
+            }
+        }
 
-    b.iter(|| {
-        for x in (gen_iter::<u32, _, _>(rng.clone(), ..).take(100)) {
-            black_box(x);
+        impl<const LOW: u64, const HIGH: u64> RandStream<$t> for ConstRange<LOW, HIGH> {
+            #[inline]
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                // Evaluated at compile time, so each call only samples.
+                // The threshold is `Uniform::threshold`, as a constant.
+                let (range, threshold) = const {
+                    assert!(LOW < HIGH, "ConstRange must be non-empty");
+                    assert!(HIGH <= $t::MAX as u64, "ConstRange must fit the type");
+                    let range = (HIGH - LOW) as $t;
+                    (range, ((range as $wide).wrapping_neg() % range as $wide) as $t)
+                };
+                (LOW as $t).wrapping_add(Uniform::sample(range, threshold, rng))
+            }
         }
-    })
+    )*}
+}
+const_range_impls! {
+    u8, u32;
+    u32, u64;
+    u64, u64;
 }
 
-#[bench]
-fn range_iter__bb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
+/// Values in a stepped range, e.g. `gen(rng, (0..100).step_by(5))`
+/// for multiples of five below 100: a uniformly random index into the
+/// range, scaled by the step, so each value is equally likely even
+/// when the step doesn't divide the width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SteppedStream<T> {
+    start: T,
+    step: T,
+    index: IntegerStreamBounded<u64>,
+}
+
+macro_rules! stepped_impls {
+    ($($t: ident),*) => {$(
+        impl Rand<StepBy<Range<$t>>> for $t {
+            type Stream = SteppedStream<$t>;
+            fn rand(dist: StepBy<Range<$t>>) -> SteppedStream<$t> {
+                // Exact for every range of these types, on 64-bit
+                // targets.
+                let (len, upper) = dist.size_hint();
+                assert_eq!(Some(len), upper, "stepped range too long");
+                assert!(len > 0, "stepped range must be non-empty");
+                let mut values = dist;
+                let start = values.next().unwrap();
+                // Any step works for a single value.
+                let step = values.next().map_or(1, |second| second.wrapping_sub(start));
+                SteppedStream {
+                    start,
+                    step,
+                    index: u64::rand(0..len as u64),
+                }
+            }
+        }
 
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), black_box(4..321)).take(100)) {
-            black_box(x);
+        impl RandStream<$t> for SteppedStream<$t> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t {
+                // The offset is less than the width of the range, so
+                // wrapping arithmetic (modulo `2^bits`) gives it
+                // exactly, even where it overflows `$t`.
+                let offset = (self.index.next(rng) as $t).wrapping_mul(self.step);
+                self.start.wrapping_add(offset)
+            }
+        }
+    )*}
+}
+stepped_impls!(u8, u32, u64, i64);
+
+/// Types with a single value, which need no randomness: `T::rand(..)`
+/// gives that value without drawing from the generator, so (e.g.
+/// derived) code generating a struct needs no special case for its
+/// markers. `..` is its own stream.
+macro_rules! trivial_impls {
+    ($($t: ty, $value: expr, [$($param: tt)*];)*) => {$(
+        impl<$($param)*> Rand<RangeFull> for $t {
+            type Stream = RangeFull;
+            fn rand(dist: RangeFull) -> RangeFull {
+                dist
+            }
+        }
+        impl<$($param)*> RandStream<$t> for RangeFull {
+            fn next<R: Rng>(&self, _: &mut R) -> $t {
+                $value
+            }
+        }
+    )*}
+}
+trivial_impls! {
+    (), (), [];
+    marker::PhantomData<T>, marker::PhantomData, [T: ?Sized];
+    marker::PhantomPinned, marker::PhantomPinned, [];
+    [T; 0], [], [T];
+}
+
+/// The stream for a smart pointer or cell, wrapping the stream `S` for
+/// its contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wrapped<S>(S);
+
+/// Smart pointers and cells, with their contents generated with the
+/// same constraint, e.g. `Box::<u32>::rand(4..321)`.
+///
+/// These are for each constraint in this module, rather than any
+/// `Dist`, which would overlap with `VariantMask`'s impl for every
+/// `T`.
+macro_rules! wrapper_impls {
+    ($($t: ident),*) => {$(
+        wrapper_impls! {
+            @dists $t:
+            [X] Range<X>, [X] RangeFrom<X>, [X] RangeTo<X>, [X] RangeToInclusive<X>,
+            [] RangeFull, [X] StepBy<Range<X>>, [const LOW: u64, const HIGH: u64] ConstRange<LOW, HIGH>,
+            [] Open01, [] Closed01, [] OpenClosed01, [] HighPrecision01,
+            [] Bernoulli, [] Ratio, #[cfg(feature = "std")] [] Decimal
+        }
+    )*};
+    (@dists $t: ident: $($(#[$attr: meta])* [$($param: tt)*] $dist: ty),*) => {$(
+        $(#[$attr])*
+        impl<T: Rand<$dist>, $($param)*> Rand<$dist> for $t<T> {
+            type Stream = Wrapped<T::Stream>;
+            fn rand(dist: $dist) -> Wrapped<T::Stream> {
+                Wrapped(T::rand(dist))
+            }
+        }
+    )*
+        impl<T, S: RandStream<T>> RandStream<$t<T>> for Wrapped<S> {
+            fn next<R: Rng>(&self, rng: &mut R) -> $t<T> {
+                $t::new(self.0.next(rng))
+            }
         }
-    })
+    };
 }
+#[cfg(feature = "alloc")]
+wrapper_impls!(Box, Rc, Arc);
+wrapper_impls!(Cell, RefCell);
+
+/// `bool`s that are `true` with probability `p`, e.g.
+/// `bool::rand(Bernoulli(0.25))` or `rng.gen_bool(0.25)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bernoulli(pub f64);
 
-#[bench]
-fn range_iter(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
+/// `bool`s that are `true` with probability exactly `num / den`,
+/// e.g. `bool::rand(Ratio(2, 3))` or `rng.gen_ratio(2, 3)`, where
+/// `Bernoulli(2.0 / 3.0)` would be off by the rounding of `2.0 / 3.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ratio(pub u32, pub u32);
+
+/// `true` when a draw is below `threshold`, so with probability
+/// `threshold / 2^64`, except that `u64::MAX` means always (which no
+/// `p < 1` reaches, having at most 53 significant bits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BernoulliStream {
+    threshold: u64,
+}
+/// `true` when a draw from `0..den` is below `num`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RatioStream {
+    num: u32,
+    den: IntegerStreamCompact<u32>,
+}
 
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100)) {
-            black_box(x);
+impl Rand<Bernoulli> for bool {
+    type Stream = BernoulliStream;
+    fn rand(dist: Bernoulli) -> BernoulliStream {
+        let p = dist.0;
+        assert!((0.0..=1.0).contains(&p), "probability must be in [0, 1]");
+        // Scaling by a power of two is exact.
+        let threshold = if p == 1.0 { u64::MAX } else { (p * 18446744073709551616.0) as u64 };
+        BernoulliStream { threshold }
+    }
+}
+impl Rand<Ratio> for bool {
+    type Stream = RatioStream;
+    fn rand(dist: Ratio) -> RatioStream {
+        let Ratio(num, den) = dist;
+        assert!(num <= den && den > 0, "ratio must be in [0, 1]");
+        RatioStream {
+            num,
+            den: u32::rand(0..den),
         }
-    })
+    }
+}
+
+impl RandStream<bool> for BernoulliStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> bool {
+        // Draw even when the answer is known, so that the generator
+        // advances the same way for every `p`.
+        let v = rng.next_u64();
+        self.threshold == u64::MAX || v < self.threshold
+    }
+}
+impl RandStream<bool> for RatioStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> bool {
+        self.den.next(rng) < self.num
+    }
+}
+
+/// `Result`s that are `Err` with probability `p`, with the value in
+/// each arm generated with its own constraint, e.g.
+/// `Result::<u32, u8>::rand(Fallible(0.1, 4..321, ..))` for an
+/// operation that fails one time in ten.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fallible<OkDist, ErrDist>(pub f64, pub OkDist, pub ErrDist);
+
+/// Whether to fail, then a value from the matching stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FallibleStream<S, F> {
+    fails: BernoulliStream,
+    ok: S,
+    err: F,
 }
 
-#[bench]
-fn range_iter__noiterbb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
+impl<T: Rand<OkDist>, E: Rand<ErrDist>, OkDist, ErrDist> Rand<Fallible<OkDist, ErrDist>> for Result<T, E> {
+    type Stream = FallibleStream<T::Stream, E::Stream>;
+    fn rand(dist: Fallible<OkDist, ErrDist>) -> FallibleStream<T::Stream, E::Stream> {
+        let Fallible(p, ok, err) = dist;
+        FallibleStream {
+            fails: bool::rand(Bernoulli(p)),
+            ok: T::rand(ok),
+            err: E::rand(err),
+        }
+    }
+}
 
-    b.iter(|| {
-        for x in (gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100)) {
-            black_box(x);
+impl<T, E, S: RandStream<T>, F: RandStream<E>> RandStream<Result<T, E>> for FallibleStream<S, F> {
+    fn next<R: Rng>(&self, rng: &mut R) -> Result<T, E> {
+        if self.fails.next(rng) {
+            Err(self.err.next(rng))
+        } else {
+            Ok(self.ok.next(rng))
         }
-    })
+    }
 }
 
-#[bench]
-fn gen_(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
+/// Decimals with a fixed number of decimal places in an inclusive
+/// range, e.g. `f64::rand(Decimal(0.01..=99.99, 2))` for prices in
+/// cents. Each value is a uniformly random integer number of units of
+/// `10^-places`, divided by `10^places`, so every step is equally
+/// likely, where rounding a uniform float would favour some.
+///
+/// `i64::rand(Decimal(...))` gives the integer numbers of units
+/// instead (e.g. `1..=9999` cents), for exact arithmetic.
+///
+/// The endpoints are rounded to the nearest unit, which needs the
+/// `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decimal(pub RangeInclusive<f64>, pub u32);
 
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, ..));
+/// Integer numbers of units, scaled down by `scale` for floats.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecimalStream {
+    units: IntegerStreamBounded<i64>,
+    scale: f64,
+}
+
+#[cfg(feature = "std")]
+impl DecimalStream {
+    fn new(dist: Decimal) -> DecimalStream {
+        let Decimal(range, places) = dist;
+        // Every integer up to `2^53` in magnitude is an exact `f64`.
+        assert!(places <= 15, "at most 15 decimal places are supported");
+        let scale = 10f64.powi(places as i32);
+        let (start, end) = ((range.start() * scale).round(), (range.end() * scale).round());
+        let limit = 2f64.powi(53);
+        assert!(-limit <= start && end <= limit, "decimal range must be finite and fit in 53 bits");
+        assert!(start <= end, "decimal range must be non-empty");
+        DecimalStream {
+            units: i64::rand(start as i64..end as i64 + 1),
+            scale,
         }
-    })
+    }
 }
 
-#[bench]
-fn range_gen__bb(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
+#[cfg(feature = "std")]
+impl Rand<Decimal> for i64 {
+    type Stream = DecimalStream;
+    fn rand(dist: Decimal) -> DecimalStream {
+        DecimalStream::new(dist)
+    }
+}
+#[cfg(feature = "std")]
+impl Rand<Decimal> for f64 {
+    type Stream = DecimalStream;
+    fn rand(dist: Decimal) -> DecimalStream {
+        DecimalStream::new(dist)
+    }
+}
 
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, black_box(4..321)));
+#[cfg(feature = "std")]
+impl RandStream<i64> for DecimalStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> i64 {
+        self.units.next(rng)
+    }
+}
+#[cfg(feature = "std")]
+impl RandStream<f64> for DecimalStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> f64 {
+        // Dividing (rather than multiplying by `10^-places`, which
+        // isn't exact) gives the closest `f64` to the decimal.
+        self.units.next(rng) as f64 / self.scale
+    }
+}
+
+/// Pairs of values from `stream` that are antithetic: the second is
+/// generated from the complement of every bit that went into the
+/// first, so (for a stream that increases with the bits it draws, as
+/// all of this crate's do) one is large when the other is small. E.g.
+/// for `f64::rand(..)`, the pairs are `(u, 1 - 2^-53 - u)`.
+///
+/// Averaging a function over both values of each pair gives the same
+/// expectation as over independent values, but with lower variance
+/// when the function is monotonic, for Monte Carlo estimates.
+///
+/// The stream is used as is, so keeps any precomputation. If it draws
+/// more for the second value than the first (e.g. rejecting a value),
+/// the extra draws are fresh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Antithetic<S> {
+    stream: S,
+}
+
+impl<S> Antithetic<S> {
+    /// Antithetic pairs from `stream`.
+    pub fn new(stream: S) -> Antithetic<S> {
+        Antithetic { stream }
+    }
+}
+
+impl<T, S: RandStream<T>> RandStream<(T, T)> for Antithetic<S> {
+    fn next<R: Rng>(&self, rng: &mut R) -> (T, T) {
+        let mut recording = Recording {
+            rng,
+            draws: [0; RECORDED],
+            len: 0,
+        };
+        let first = self.stream.next(&mut recording);
+        let Recording { rng, draws, len } = recording;
+        let mut complement = Complement {
+            rng,
+            draws: &draws[..len],
+        };
+        (first, self.stream.next(&mut complement))
+    }
+}
+
+/// How many draws `Antithetic` remembers, which is more than any of
+/// this crate's streams need without rejecting.
+const RECORDED: usize = 4;
+
+/// Passes draws through from `rng`, remembering the first few.
+struct Recording<'a, R: 'a> {
+    rng: &'a mut R,
+    draws: [u64; RECORDED],
+    len: usize,
+}
+
+impl<'a, R: Rng> Recording<'a, R> {
+    fn record(&mut self, draw: u64) {
+        if self.len < RECORDED {
+            self.draws[self.len] = draw;
+            self.len += 1;
         }
-    })
+    }
 }
 
-#[bench]
-fn range_gen(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
+impl<'a, R: Rng> Rng for Recording<'a, R> {
+    fn next_u32(&mut self) -> u32 {
+        let draw = self.rng.next_u32();
+        self.record(draw as u64);
+        draw
+    }
 
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, (4..321)));
+    fn next_u64(&mut self) -> u64 {
+        let draw = self.rng.next_u64();
+        self.record(draw);
+        draw
+    }
+}
+
+/// Replays the complements of `draws`, then passes through to `rng`.
+struct Complement<'a, 'b, R: 'a> {
+    rng: &'a mut R,
+    draws: &'b [u64],
+}
+
+impl<'a, 'b, R: Rng> Rng for Complement<'a, 'b, R> {
+    fn next_u32(&mut self) -> u32 {
+        match self.draws.split_first() {
+            Some((&draw, rest)) => {
+                self.draws = rest;
+                !(draw as u32)
+            }
+            None => self.rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.draws.split_first() {
+            Some((&draw, rest)) => {
+                self.draws = rest;
+                !draw
+            }
+            None => self.rng.next_u64(),
         }
-    })
+    }
+}
+
+/// The storage for a `cached_stream!`, one per call site.
+#[cfg(feature = "std")]
+pub type StreamCell = ::std::sync::OnceLock<Box<dyn Any + Send + Sync>>;
+
+/// The stream for `T` and the constraint from `constraint`, which is
+/// created on the first call with `cell`, and reused after that. See
+/// `cached_stream!`, which provides the cell.
+///
+/// # Panics
+///
+/// If `cell` was first used for a different type of stream.
+#[cfg(feature = "std")]
+pub fn cached<T, Dist, F>(cell: &'static StreamCell, constraint: F) -> &'static T::Stream
+    where T: Rand<Dist>, T::Stream: Send + Sync + 'static, F: FnOnce() -> Dist
+{
+    cell.get_or_init(|| Box::new(T::rand(constraint())))
+        .downcast_ref()
+        .expect("cached_stream! used for different types at one call site")
+}
+
+/// The stream for `$t` with `$constraint`, created the first time this
+/// call site runs and shared by every later run (on any thread), e.g.
+/// `cached_stream!(u32, 0..52).next(&mut rng)` in a hot loop, which
+/// does the work of `u32::rand(0..52)` just once per process.
+///
+/// That only helps if the optimizer can't already hoist that work out
+/// of the loop: for a literal range in a simple loop it can, and the
+/// check that the stream exists makes this slower (see the
+/// `range_gen__cached` benchmark).
+///
+/// `$constraint` is only evaluated the first time, so it should be
+/// constant. Each use has its own cache, so (since statics aren't
+/// duplicated per instantiation) this panics if used in a generic
+/// function with different types.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! cached_stream {
+    ($t: ty, $constraint: expr) => {{
+        static CELL: $crate::stream::StreamCell = $crate::stream::StreamCell::new();
+        $crate::stream::cached::<$t, _, _>(&CELL, || $constraint)
+    }}
 }
@@ -0,0 +1,73 @@
+//! Rough estimates of entropy, from how often each value appears.
+
+use rand::Rng;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How unpredictable some values look, from their frequencies alone.
+///
+/// This only catches gross failures, such as a generator stuck on a
+/// few values or a stream that never produces half its range: it
+/// ignores order, so a counter looks perfect. Use `stats` or an
+/// external suite (see `emit_bytes`) for anything subtler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entropy {
+    /// The number of values.
+    pub values: u64,
+    /// The number of different values.
+    pub distinct: usize,
+    /// How many times the most common value appeared.
+    pub max_count: u64,
+    /// The Shannon entropy of the frequencies, in bits per value.
+    pub shannon: f64,
+    /// The min-entropy, `-log2(max_count / values)`, in bits per value:
+    /// the most conservative measure, which is what matters for
+    /// guessing.
+    pub min_entropy: f64,
+}
+
+impl Entropy {
+    /// Whether the most common value appears no more than would be
+    /// expected for values with `bits` bits of entropy each (e.g. `8`
+    /// for bytes), allowing five standard deviations of sampling noise.
+    ///
+    /// Failing is a strong sign of a broken generator; passing is only
+    /// weak evidence of a good one. This needs many more values than
+    /// `2^bits` to be meaningful.
+    pub fn is_plausible(&self, bits: f64) -> bool {
+        let expected = self.values as f64 / bits.exp2();
+        self.max_count as f64 <= expected + 5.0 * expected.sqrt() + 1.0
+    }
+}
+
+/// Estimate the entropy of `values` from their frequencies, e.g. of a
+/// stream with `entropy(gen_iter::<u8, _, _>(rng, ..).take(1 << 20))`.
+pub fn entropy<T: Hash + Eq, I: IntoIterator<Item = T>>(values: I) -> Entropy {
+    let mut counts = HashMap::new();
+    let mut total = 0;
+    for x in values {
+        *counts.entry(x).or_insert(0u64) += 1;
+        total += 1;
+    }
+    let max_count = counts.values().cloned().max().unwrap_or(0);
+    let n = total as f64;
+    let shannon = -counts.values().map(|&c| {
+        let p = c as f64 / n;
+        p * p.log2()
+    }).sum::<f64>();
+    Entropy {
+        values: total,
+        distinct: counts.len(),
+        max_count,
+        shannon: shannon.max(0.0),
+        min_entropy: if total == 0 { 0.0 } else { -(max_count as f64 / n).log2() },
+    }
+}
+
+/// Estimate the entropy of `n` bytes from `rng.fill_bytes`, which is
+/// plausible for a working generator at `8` bits.
+pub fn byte_entropy<R: Rng>(rng: &mut R, n: usize) -> Entropy {
+    let mut bytes = vec![0; n];
+    rng.fill_bytes(&mut bytes);
+    entropy(bytes)
+}
@@ -0,0 +1,31 @@
+//! Helpers for checking that random values look the way they should,
+//! for this crate's own tests and for users writing their own
+//! `Random`/`Rand` impls.
+
+use rand::Rng;
+use std::io::{self, Write};
+
+mod entropy;
+pub mod stats;
+mod summary;
+
+pub use self::entropy::{byte_entropy, entropy, Entropy};
+pub use self::summary::{Histogram, RunningStats};
+
+/// Write `n` bytes of output from `rng` to `writer`, raw, for external
+/// test suites such as dieharder, PractRand or TestU01, e.g. `randgen
+/// --bytes 1000000000 | RNG_test stdin`.
+///
+/// For a test suite that reads until it's done, pass `u64::MAX` and
+/// stop when writing fails because the reader has closed the pipe.
+pub fn emit_bytes<R: Rng, W: Write>(rng: &mut R, mut writer: W, n: u64) -> io::Result<()> {
+    let mut buffer = [0; 1 << 16];
+    let mut remaining = n;
+    while remaining > 0 {
+        let len = remaining.min(buffer.len() as u64) as usize;
+        rng.fill_bytes(&mut buffer[..len]);
+        writer.write_all(&buffer[..len])?;
+        remaining -= len as u64;
+    }
+    writer.flush()
+}
@@ -0,0 +1,197 @@
+//! Statistical goodness-of-fit tests.
+//!
+//! For example, checking that a stream over `4..321` is uniform:
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate rand_sketch;
+//! use rand::{SeedableRng, XorShiftRng};
+//! use rand_sketch::stream::{Rand, RandStream};
+//! use rand_sketch::testing::stats;
+//!
+//! # fn main() {
+//! let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+//! let stream = u32::rand(4..321);
+//! let result = stats::uniformity(317, 100_000, || stream.next(&mut rng) as usize - 4);
+//! assert!(result.passes(0.001), "{:?}", result);
+//! # }
+//! ```
+
+/// The outcome of a chi-squared goodness-of-fit test.
+#[derive(Clone, Copy, Debug)]
+pub struct ChiSquared {
+    /// The chi-squared statistic, `sum((observed - expected)^2 / expected)`.
+    pub statistic: f64,
+    /// The degrees of freedom, one less than the number of buckets.
+    pub degrees_of_freedom: usize,
+    /// The probability of a statistic at least this large if the
+    /// observations do follow the expected distribution.
+    pub p_value: f64,
+}
+
+impl ChiSquared {
+    /// Whether the observations are consistent with the expected
+    /// distribution at significance level `alpha`, i.e. `p_value >=
+    /// alpha`.
+    ///
+    /// Even a perfect generator fails with probability `alpha`, so
+    /// tests should use a fixed seed (or a small `alpha`).
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+/// Test `observed` counts against the `expected` counts for each
+/// bucket.
+///
+/// # Panics
+///
+/// If the lengths differ, there are fewer than two buckets, or any
+/// expected count isn't positive.
+pub fn chi_squared(observed: &[u64], expected: &[f64]) -> ChiSquared {
+    assert_eq!(observed.len(), expected.len());
+    assert!(observed.len() >= 2, "need at least two buckets");
+
+    let statistic = observed.iter().zip(expected).map(|(&o, &e)| {
+        assert!(e > 0.0, "expected counts must be positive");
+        let d = o as f64 - e;
+        d * d / e
+    }).sum();
+    let degrees_of_freedom = observed.len() - 1;
+
+    ChiSquared {
+        statistic,
+        degrees_of_freedom,
+        p_value: gamma_q(degrees_of_freedom as f64 / 2.0, statistic / 2.0),
+    }
+}
+
+/// Test whether `observed` counts are consistent with every bucket
+/// being equally likely.
+pub fn chi_squared_uniform(observed: &[u64]) -> ChiSquared {
+    let total = observed.iter().sum::<u64>() as f64;
+    let expected = vec![total / observed.len() as f64; observed.len()];
+    chi_squared(observed, &expected)
+}
+
+/// Draw `samples` values via `bucket`, which returns the index (less
+/// than `buckets`) of the bucket each value falls into, and test
+/// whether the buckets are equally likely.
+///
+/// # Panics
+///
+/// If `bucket` returns an index outside `0..buckets`.
+pub fn uniformity<F: FnMut() -> usize>(buckets: usize, samples: usize, mut bucket: F) -> ChiSquared {
+    let mut counts = vec![0; buckets];
+    for _ in 0..samples {
+        let i = bucket();
+        assert!(i < buckets, "bucket {} out of range 0..{}", i, buckets);
+        counts[i] += 1;
+    }
+    chi_squared_uniform(&counts)
+}
+
+const EPSILON: f64 = 1e-15;
+const MAX_ITERATIONS: usize = 10_000;
+
+/// The regularized upper incomplete gamma function `Q(a, x)`, which is
+/// the survival function of the chi-squared distribution with `2a`
+/// degrees of freedom at `2x`.
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+/// `P(a, x) = 1 - Q(a, x)` by its series, which converges quickly
+/// for `x < a + 1`.
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    for n in 1..MAX_ITERATIONS {
+        term *= x / (a + n as f64);
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// `Q(a, x)` by its continued fraction (with the modified Lentz
+/// method), which converges quickly for `x >= a + 1`.
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / tiny;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..MAX_ITERATIONS {
+        let i = i as f64;
+        let an = -i * (i - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < tiny { d = tiny }
+        c = b + an / c;
+        if c.abs() < tiny { c = tiny }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break
+        }
+    }
+    h * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+/// `ln(Γ(x))` for `x >= 0.5`, via the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    debug_assert!(x >= 0.5);
+
+    let x = x - 1.0;
+    let sum = COEFFICIENTS[1..].iter().enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    let t = x + G + 0.5;
+    0.5 * (2.0 * ::std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn critical_values() {
+        // 5% and 0.1% critical values from standard tables.
+        for &(df, x, p) in &[(1, 3.841, 0.05), (2, 5.991, 0.05), (10, 18.307, 0.05),
+                             (10, 29.588, 0.001), (100, 124.342, 0.05)] {
+            let q = gamma_q(df as f64 / 2.0, x / 2.0);
+            assert!(close(q, p), "df = {}, x = {}: {} != {}", df, x, q, p);
+        }
+    }
+
+    #[test]
+    fn uniform_counts() {
+        assert!(chi_squared_uniform(&[100, 100, 100, 100]).p_value > 0.999);
+        assert!(!chi_squared_uniform(&[150, 50, 100, 100]).passes(0.001));
+    }
+}
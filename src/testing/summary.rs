@@ -0,0 +1,208 @@
+//! Summaries of many values, for eyeballing a distribution.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+/// The count, mean, variance, minimum and maximum of the values seen so
+/// far, updated one value at a time (with Welford's method, which
+/// avoids the cancellation of summing squares), e.g.
+/// `gen_iter::<f64, _, _>(rng, ..).take(10_000).collect::<RunningStats>()`.
+///
+/// This takes anything convertible to `f64`; for 64-bit integers, map
+/// them with `as f64` first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    /// The sum of squared differences from the mean.
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl RunningStats {
+    pub fn new() -> RunningStats {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The mean, or NaN if there are no values.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 { f64::NAN } else { self.mean }
+    }
+
+    /// The sample variance (dividing by `count - 1`), or NaN if there
+    /// are fewer than two values.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 { f64::NAN } else { self.m2 / (self.count - 1) as f64 }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// The smallest value, or infinity if there are none.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The largest value, or negative infinity if there are none.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+}
+
+impl Default for RunningStats {
+    fn default() -> RunningStats {
+        RunningStats::new()
+    }
+}
+
+impl<T: Into<f64>> Extend<T> for RunningStats {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x.into());
+        }
+    }
+}
+
+impl<T: Into<f64>> FromIterator<T> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RunningStats {
+        let mut stats = RunningStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl fmt::Display for RunningStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "n = {}, mean = {}, sd = {}, min = {}, max = {}",
+               self.count, self.mean(), self.std_dev(), self.min, self.max)
+    }
+}
+
+/// Counts of values in equal-width bins covering `[low, high)`, along
+/// with those below and above it, which prints as a bar chart.
+///
+/// ```rust
+/// extern crate rand;
+/// extern crate rand_sketch;
+/// use rand::{SeedableRng, XorShiftRng};
+/// use rand_sketch::stream::gen_iter;
+/// use rand_sketch::testing::Histogram;
+///
+/// # fn main() {
+/// let rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+/// let mut histogram = Histogram::new(0.0, 1.0, 10);
+/// histogram.extend(gen_iter::<f64, _, _>(rng, ..).take(10_000));
+/// println!("{}", histogram);
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    low: f64,
+    high: f64,
+    counts: Vec<u64>,
+    below: u64,
+    above: u64,
+}
+
+impl Histogram {
+    /// # Panics
+    ///
+    /// If `low..high` isn't a finite, non-empty range, or `bins` is
+    /// zero.
+    pub fn new(low: f64, high: f64, bins: usize) -> Histogram {
+        assert!(low.is_finite() && high.is_finite() && low < high,
+                "histogram range must be finite and non-empty");
+        assert!(bins > 0, "histogram needs at least one bin");
+        Histogram {
+            low,
+            high,
+            counts: vec![0; bins],
+            below: 0,
+            above: 0,
+        }
+    }
+
+    /// Count `x`, in its bin or as below or above the range (NaN
+    /// counts as above).
+    pub fn push(&mut self, x: f64) {
+        if x < self.low {
+            self.below += 1;
+        } else if x < self.high {
+            let bin = ((x - self.low) / (self.high - self.low) * self.counts.len() as f64) as usize;
+            // Rounding can put values just below `high` one too far.
+            let last = self.counts.len() - 1;
+            self.counts[bin.min(last)] += 1;
+        } else {
+            self.above += 1;
+        }
+    }
+
+    /// The count in each bin, from `low` to `high`.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// The number of values below `low`.
+    pub fn below(&self) -> u64 {
+        self.below
+    }
+
+    /// The number of values at or above `high`.
+    pub fn above(&self) -> u64 {
+        self.above
+    }
+
+    /// The lower edge of bin `i`.
+    fn edge(&self, i: usize) -> f64 {
+        self.low + (self.high - self.low) * i as f64 / self.counts.len() as f64
+    }
+}
+
+impl<T: Into<f64>> Extend<T> for Histogram {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x.into());
+        }
+    }
+}
+
+/// A line per bin, with a bar scaled to the largest count.
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const WIDTH: u64 = 50;
+        let max = self.counts.iter().cloned().max().unwrap_or(0).max(1);
+        if self.below > 0 {
+            writeln!(f, "below {}: {}", self.low, self.below)?;
+        }
+        for (i, &count) in self.counts.iter().enumerate() {
+            let bar = (count * WIDTH).div_ceil(max) as usize;
+            writeln!(f, "[{:.4}, {:.4}) {:>8} {}",
+                     self.edge(i), self.edge(i + 1), count, "#".repeat(bar))?;
+        }
+        if self.above > 0 {
+            writeln!(f, "at or above {}: {}", self.high, self.above)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+//! Characters and strings, e.g. for search and indexing test
+//! fixtures that should look something like natural text, names and
+//! identifiers imitating a list of examples, or file names for a
+//! temporary directory tree.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+use rand::Rng;
+use seq::gen_index;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+use stream::{IntegerStreamBounded, Rand, RandStream};
+use weighted::AliasTable;
+
+/// The frequency of each letter in English text, per 10,000 letters,
+/// and of spaces, at about one in five and a half characters (for
+/// words of 4.5 letters on average).
+const ENGLISH: [(char, u32); 27] = [
+    ('a', 817), ('b', 149), ('c', 278), ('d', 425), ('e', 1270), ('f', 223),
+    ('g', 202), ('h', 609), ('i', 697), ('j', 15), ('k', 77), ('l', 403),
+    ('m', 241), ('n', 675), ('o', 751), ('p', 193), ('q', 10), ('r', 599),
+    ('s', 633), ('t', 906), ('u', 276), ('v', 98), ('w', 236), ('x', 15),
+    ('y', 197), ('z', 7), (' ', 2200),
+];
+
+/// The characters to choose from, uniformly or by weight, e.g.
+/// `char::rand(Charset::new("0123456789abcdef"))`.
+///
+/// The weights are compiled into an alias table when the set is
+/// built, so it's its own stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Charset {
+    chars: Vec<char>,
+    /// `None` for uniform.
+    weights: Option<AliasTable>,
+}
+
+impl Charset {
+    /// Each of the characters of `chars`, equally likely. Repeating a
+    /// character makes it proportionally more likely.
+    ///
+    /// # Panics
+    ///
+    /// If `chars` is empty.
+    pub fn new(chars: &str) -> Charset {
+        let chars = chars.chars().collect::<Vec<_>>();
+        assert!(!chars.is_empty(), "no characters allowed");
+        Charset { chars, weights: None }
+    }
+
+    /// Each of the characters of `weighted`, with probability
+    /// proportional to its weight, e.g. `Charset::weighted(&[('a',
+    /// 3), ('b', 1)])` for three times as many `a`s as `b`s.
+    ///
+    /// # Panics
+    ///
+    /// If `weighted` is empty, or the weights are all zero.
+    pub fn weighted(weighted: &[(char, u32)]) -> Charset {
+        assert!(!weighted.is_empty(), "no characters allowed");
+        let weights = weighted.iter().map(|&(_, w)| f64::from(w)).collect::<Vec<_>>();
+        Charset {
+            chars: weighted.iter().map(|&(c, _)| c).collect(),
+            weights: Some(AliasTable::new(&weights)),
+        }
+    }
+
+    /// Lowercase letters and spaces, with their frequencies in English
+    /// text: strings of these aren't words, but have the letter (and
+    /// roughly the word length) distribution of prose.
+    pub fn english() -> Charset {
+        Charset::weighted(&ENGLISH)
+    }
+
+    /// The nucleotides `ACGT`, equally likely.
+    pub fn dna() -> Charset {
+        Charset::new("ACGT")
+    }
+
+    /// The nucleotides `ACGT`, with `G` and `C` making up a fraction
+    /// `gc` of them on average (each half of that), and `A` and `T` the
+    /// rest, e.g. `Charset::dna_gc(0.41)` for the human genome's GC
+    /// content.
+    ///
+    /// # Panics
+    ///
+    /// If `gc` isn't in `[0, 1]`.
+    pub fn dna_gc(gc: f64) -> Charset {
+        assert!((0.0..=1.0).contains(&gc), "GC content must be in [0, 1]");
+        let (at, gc) = ((1.0 - gc) / 2.0, gc / 2.0);
+        Charset {
+            chars: vec!['A', 'C', 'G', 'T'],
+            weights: Some(AliasTable::new(&[at, gc, gc, at])),
+        }
+    }
+
+    /// The one-letter codes of the 20 standard amino acids,
+    /// `ACDEFGHIKLMNPQRSTVWY`, equally likely.
+    pub fn protein() -> Charset {
+        Charset::new("ACDEFGHIKLMNPQRSTVWY")
+    }
+
+    /// The characters that can be chosen, in order.
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+}
+
+impl Rand<Charset> for char {
+    type Stream = Charset;
+    fn rand(dist: Charset) -> Charset {
+        dist
+    }
+}
+
+impl RandStream<char> for Charset {
+    fn next<R: Rng>(&self, rng: &mut R) -> char {
+        let index = match self.weights {
+            Some(ref table) => table.sample(rng),
+            None => gen_index(self.chars.len(), rng),
+        };
+        self.chars[index]
+    }
+}
+
+/// Strings of characters from a `Charset`, with a number of
+/// characters uniformly in a range, e.g. `String::rand(Text(
+/// Charset::english(), 20..200))`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Text(pub Charset, pub Range<usize>);
+
+/// The characters, and the stream for the length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextStream {
+    charset: Charset,
+    len: IntegerStreamBounded<u64>,
+}
+
+impl Rand<Text> for String {
+    type Stream = TextStream;
+    /// # Panics
+    ///
+    /// If the range of lengths is empty.
+    fn rand(dist: Text) -> TextStream {
+        let Text(charset, len) = dist;
+        assert!(len.start < len.end, "range of lengths must be non-empty");
+        TextStream {
+            charset,
+            len: u64::rand(len.start as u64..len.end as u64),
+        }
+    }
+}
+
+impl RandStream<String> for TextStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> String {
+        let len = self.len.next(rng) as usize;
+        (0..len).map(|_| self.charset.next(rng)).collect()
+    }
+}
+
+/// Byte strings of ASCII characters from a `Charset`, with a number of
+/// characters uniformly in a range, e.g. `Vec::<u8>::rand(Sequence(
+/// Charset::dna_gc(0.6), 150..151))` for 150-base reads, or of length
+/// `k` for k-mers. These are `Text` as bytes, which is how
+/// bioinformatics libraries usually take sequences.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sequence(pub Charset, pub Range<usize>);
+
+/// The characters, and the stream for the length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequenceStream {
+    text: TextStream,
+}
+
+impl Rand<Sequence> for Vec<u8> {
+    type Stream = SequenceStream;
+    /// # Panics
+    ///
+    /// If the range of lengths is empty, or the charset has a
+    /// character that isn't ASCII.
+    fn rand(dist: Sequence) -> SequenceStream {
+        let Sequence(charset, len) = dist;
+        assert!(charset.chars.iter().all(char::is_ascii), "sequences must be ASCII");
+        SequenceStream { text: String::rand(Text(charset, len)) }
+    }
+}
+
+impl RandStream<Vec<u8>> for SequenceStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> Vec<u8> {
+        self.text.next(rng).into_bytes()
+    }
+}
+
+/// How many strings `Imitation` generates, looking for one of the
+/// right length, before giving up.
+const IMITATION_TRIES: u32 = 10_000;
+
+/// An order-`k` character Markov model of the words in a corpus: each
+/// character is chosen based on the `k` before it (or the start of the
+/// word), with the probabilities they had in the corpus, until the
+/// model chooses to end the word.
+///
+/// A low order gives more novel words, a high order more faithful
+/// ones; above the length of the longest word, it only repeats the
+/// corpus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextModel {
+    order: usize,
+    /// The characters following each context of `order` characters,
+    /// with `None` for the start of the word in a context, and for its
+    /// end in the successors.
+    successors: BTreeMap<Vec<Option<char>>, Successors>,
+}
+
+/// The characters that can follow a context, by how often they did.
+#[derive(Clone, Debug, PartialEq)]
+struct Successors {
+    chars: Vec<Option<char>>,
+    table: AliasTable,
+}
+
+impl TextModel {
+    /// The model of order `order` of the words of `corpus` (separated
+    /// by whitespace), e.g. a list of names.
+    ///
+    /// # Panics
+    ///
+    /// If `corpus` has no words.
+    pub fn train(corpus: &str, order: usize) -> TextModel {
+        let mut counts = BTreeMap::<Vec<Option<char>>, BTreeMap<Option<char>, u32>>::new();
+        for word in corpus.split_whitespace() {
+            let padded = vec![None; order].into_iter()
+                .chain(word.chars().map(Some))
+                .chain(Some(None))
+                .collect::<Vec<_>>();
+            for window in padded.windows(order + 1) {
+                let (context, next) = window.split_at(order);
+                *counts.entry(context.to_vec()).or_default().entry(next[0]).or_insert(0) += 1;
+            }
+        }
+        assert!(!counts.is_empty(), "corpus must have some words");
+        let successors = counts.into_iter().map(|(context, nexts)| {
+            let weights = nexts.values().map(|&n| f64::from(n)).collect::<Vec<_>>();
+            let table = AliasTable::new(&weights);
+            (context, Successors { chars: nexts.into_keys().collect(), table })
+        }).collect();
+        TextModel { order, successors }
+    }
+
+    /// The number of characters each is chosen based on.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// A word from the model, of any length.
+    fn word<R: Rng>(&self, rng: &mut R) -> String {
+        let mut context = vec![None; self.order];
+        let mut word = String::new();
+        loop {
+            // Each context is reached by a step the corpus made, so has
+            // successors.
+            let successors = &self.successors[&context];
+            match successors.chars[successors.table.sample(rng)] {
+                Some(c) => {
+                    word.push(c);
+                    if self.order > 0 {
+                        context.remove(0);
+                        context.push(Some(c));
+                    }
+                }
+                None => return word,
+            }
+        }
+    }
+}
+
+/// Words from a `TextModel`, with a number of characters in a range,
+/// e.g. `String::rand(Imitation(TextModel::train(names, 3), 4..10))`.
+///
+/// Words of other lengths are rejected, so the range should be one the
+/// corpus's words often fall in. The model needs no precomputation,
+/// so this is its own stream.
+///
+/// # Panics
+///
+/// When generating, if none of 10,000 words have a length in the
+/// range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Imitation(pub TextModel, pub Range<usize>);
+
+impl Rand<Imitation> for String {
+    type Stream = Imitation;
+    /// # Panics
+    ///
+    /// If the range of lengths is empty.
+    fn rand(dist: Imitation) -> Imitation {
+        assert!(dist.1.start < dist.1.end, "range of lengths must be non-empty");
+        dist
+    }
+}
+
+impl RandStream<String> for Imitation {
+    fn next<R: Rng>(&self, rng: &mut R) -> String {
+        for _ in 0..IMITATION_TRIES {
+            let word = self.0.word(rng);
+            if self.1.contains(&word.chars().count()) {
+                return word;
+            }
+        }
+        panic!("no word of {} to {} characters in {} tries", self.1.start, self.1.end - 1, IMITATION_TRIES)
+    }
+}
+
+/// The characters in `FileName`s: lowercase only, so no two names
+/// clash on a case-insensitive file system.
+const FILE_NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-_";
+/// The first character leaves out `-` and `_`, so names can't be
+/// mistaken for command-line options.
+const FILE_NAME_FIRST: usize = 36;
+/// Names that Windows reserves for devices.
+const RESERVED: [&str; 22] = [
+    "con", "prn", "aux", "nul",
+    "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9",
+    "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// File names with a number of characters in a range, e.g.
+/// `String::rand(FileName(1..16))`, that are valid, and mean the same
+/// thing, on every common file system and shell.
+///
+/// They're lowercase ASCII letters, digits, `-` and `_`, starting with
+/// a letter or digit, and never a name Windows reserves (such as
+/// `con` or `lpt1`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileName(pub Range<usize>);
+
+/// The stream for the length of the name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileNameStream {
+    len: IntegerStreamBounded<u64>,
+}
+
+impl Rand<FileName> for String {
+    type Stream = FileNameStream;
+    /// # Panics
+    ///
+    /// If the range of lengths is empty, or starts at zero.
+    fn rand(dist: FileName) -> FileNameStream {
+        let FileName(len) = dist;
+        assert!(len.start > 0, "file names must be non-empty");
+        assert!(len.start < len.end, "range of lengths must be non-empty");
+        FileNameStream {
+            len: u64::rand(len.start as u64..len.end as u64),
+        }
+    }
+}
+
+impl RandStream<String> for FileNameStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> String {
+        loop {
+            let len = self.len.next(rng) as usize;
+            let name = (0..len).map(|i| {
+                let choices = if i == 0 { FILE_NAME_FIRST } else { FILE_NAME_CHARS.len() };
+                FILE_NAME_CHARS[gen_index(choices, rng)] as char
+            }).collect::<String>();
+            if !RESERVED.contains(&&*name) {
+                return name;
+            }
+        }
+    }
+}
+
+/// Relative paths with a number of components in the first range,
+/// each a `FileName` with a number of characters in the second, e.g.
+/// `PathBuf::rand(RelativePath(1..4, 1..9))` for paths such as
+/// `q3/x_k`, to create a directory tree under a temporary directory.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelativePath(pub Range<usize>, pub Range<usize>);
+
+/// The streams for the depth, and for each component.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelativePathStream {
+    depth: IntegerStreamBounded<u64>,
+    component: FileNameStream,
+}
+
+#[cfg(feature = "std")]
+impl Rand<RelativePath> for PathBuf {
+    type Stream = RelativePathStream;
+    /// # Panics
+    ///
+    /// If either range is empty or starts at zero.
+    fn rand(dist: RelativePath) -> RelativePathStream {
+        let RelativePath(depth, len) = dist;
+        assert!(depth.start > 0, "paths must have a component");
+        assert!(depth.start < depth.end, "range of depths must be non-empty");
+        RelativePathStream {
+            depth: u64::rand(depth.start as u64..depth.end as u64),
+            component: String::rand(FileName(len)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl RandStream<PathBuf> for RelativePathStream {
+    fn next<R: Rng>(&self, rng: &mut R) -> PathBuf {
+        let depth = self.depth.next(rng);
+        (0..depth).map(|_| self.component.next(rng)).collect()
+    }
+}
@@ -0,0 +1,92 @@
+//! A cryptographically secure generator per thread, for when there's
+//! no generator to hand and no need to reproduce the values.
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+use entropy::WebCrypto;
+use entropy::FromEntropy;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+use rand::OsRng;
+use rand::{ChaChaRng, Rng};
+use secure::CryptoRng;
+use std::cell::RefCell;
+#[cfg(unix)]
+use std::process;
+use std::rc::Rc;
+
+thread_local! {
+    /// The generator, along with the ID of the process that seeded it.
+    static THREAD_RNG: RefCell<Option<(u32, Rc<RefCell<ChaChaRng>>)>> = const { RefCell::new(None) };
+}
+
+/// A handle to the current thread's generator: a `ChaChaRng` seeded
+/// from the operating system the first time it's needed (or, in a
+/// browser with the `wasm` feature, from `crypto.getRandomValues`).
+/// Without threads, as in a browser, this is just one generator.
+///
+/// After a `fork`, the child process inherits the parent's generator,
+/// and would repeat its values. To avoid that, this checks the process
+/// ID each time it's called, and reseeds if it's changed. Handles made
+/// before the fork keep using the old generator, so make a new one
+/// for each batch of values rather than keeping it around.
+///
+/// # Panics
+///
+/// If the operating system's (or browser's) generator fails.
+pub fn thread_rng() -> ThreadRng {
+    let pid = pid();
+    THREAD_RNG.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        match *slot {
+            Some((seeded_by, ref rng)) if seeded_by == pid => ThreadRng { rng: rng.clone() },
+            _ => {
+                let rng = Rc::new(RefCell::new(seed()));
+                *slot = Some((pid, rng.clone()));
+                ThreadRng { rng }
+            }
+        }
+    })
+}
+
+/// The process ID, or a constant where processes can't fork (and
+/// `process::id` may panic, e.g. on `wasm32-unknown-unknown`).
+#[cfg(unix)]
+fn pid() -> u32 {
+    process::id()
+}
+#[cfg(not(unix))]
+fn pid() -> u32 {
+    0
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+fn seed() -> ChaChaRng {
+    let mut os = OsRng::new().expect("could not open the operating system's generator");
+    ChaChaRng::from_entropy(&mut os).unwrap_or_else(|never| match never {})
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+fn seed() -> ChaChaRng {
+    ChaChaRng::from_entropy(&mut WebCrypto).expect("could not get values from crypto.getRandomValues")
+}
+
+/// The handle returned by `thread_rng`.
+#[derive(Clone, Debug)]
+pub struct ThreadRng {
+    rng: Rc<RefCell<ChaChaRng>>,
+}
+
+impl Rng for ThreadRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.borrow_mut().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.borrow_mut().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.borrow_mut().fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for ThreadRng {}
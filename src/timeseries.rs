@@ -0,0 +1,72 @@
+//! Synthetic time series, as a sum of a trend, seasonal cycles and
+//! noise, e.g. for metrics to test anomaly detectors on.
+
+use rand::Rng;
+use std::f64::consts::TAU;
+use stream::{Rand, RandStream};
+
+/// Series of `len` values, the `t`th (from 0) being `level + trend *
+/// t`, plus each season's sinusoid at `t`, plus a value from the
+/// stream `noise`, e.g.
+///
+/// ```rust,ignore
+/// Vec::<f64>::rand(TimeSeries {
+///     len: 24 * 7 * 4,
+///     level: 100.0,
+///     trend: 0.05,
+///     seasons: vec![Season { period: 24.0, amplitude: 20.0, phase: 0.0 },
+///                   Season { period: 24.0 * 7.0, amplitude: 10.0, phase: 0.25 }],
+///     noise: f64::rand(Normal { mean: 0.0, sd: 5.0 }),
+/// })
+/// ```
+///
+/// for four weeks of hourly values with daily and weekly cycles. Any
+/// stream will do for the noise, e.g. a `Stable` one for heavy tails;
+/// the trend and seasons are the same for each series.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeSeries<N> {
+    pub len: usize,
+    pub level: f64,
+    pub trend: f64,
+    pub seasons: Vec<Season>,
+    pub noise: N,
+}
+
+/// A cycle of `period` steps, `amplitude * sin(2π (t / period +
+/// phase))` at step `t`, with `phase` in turns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Season {
+    pub period: f64,
+    pub amplitude: f64,
+    pub phase: f64,
+}
+
+impl<N: RandStream<f64>> Rand<TimeSeries<N>> for Vec<f64> {
+    type Stream = TimeSeries<N>;
+    /// # Panics
+    ///
+    /// If `level`, `trend` or any season's amplitude or phase isn't
+    /// finite, or any season's period isn't positive and finite.
+    fn rand(dist: TimeSeries<N>) -> TimeSeries<N> {
+        assert!(dist.level.is_finite() && dist.trend.is_finite(), "level and trend must be finite");
+        for season in &dist.seasons {
+            assert!(season.period > 0.0 && season.period.is_finite(), "periods must be positive and finite");
+            assert!(season.amplitude.is_finite() && season.phase.is_finite(),
+                    "amplitudes and phases must be finite");
+        }
+        dist
+    }
+}
+
+// The series needs no precomputation, so is its own stream.
+impl<N: RandStream<f64>> RandStream<Vec<f64>> for TimeSeries<N> {
+    fn next<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        (0..self.len).map(|t| {
+            let t = t as f64;
+            let seasonal = self.seasons.iter()
+                .map(|s| s.amplitude * (TAU * (t / s.period + s.phase)).sin())
+                .sum::<f64>();
+            self.level + self.trend * t + seasonal + self.noise.next(rng)
+        }).collect()
+    }
+}
@@ -1,6 +1,16 @@
+use {Closed01, HighPrecision01, Open01, OpenClosed01};
+use float::Float;
 use rand::Rng;
+use uniform::Uniform;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 use std::marker;
-use std::ops::{RangeFull, Range, RangeFrom, RangeTo};
+use std::ops::{RangeFull, Range, RangeFrom, RangeTo, RangeToInclusive};
 
 /// Data types that can be created randomly, with `Constraint`
 /// restricting what values can be created.
@@ -16,8 +26,8 @@ pub fn gen<Rand: Random<Constraint>, Constraint, R: Rng>(rng: &mut R, constraint
 /// Create an infinite sequence of random values, mediated by `constraint`.
 pub fn gen_iter<Rand: Random<Constraint>, Constraint, R: Rng>(rng: R, constraint: Constraint) -> GenIter<Rand, Constraint, R> {
     GenIter {
-        constraint: constraint,
-        rng: rng,
+        constraint,
+        rng,
         _marker: marker::PhantomData,
     }
 }
@@ -37,48 +47,52 @@ impl<Constraint, Rand: Random<Constraint>, R: Rng> Iterator for GenIter<Rand, Co
 }
 
 
-impl Random<RangeFull> for u32 {
-    fn gen<R: Rng>(_: &RangeFull, rng: &mut R) -> u32 {
-        rng.gen()
-    }
-}
-impl Random<Range<u32>> for u32 {
-    fn gen<R: Rng>(range: &Range<u32>, rng: &mut R) -> u32 {
-        assert!(range.start < range.end);
-        let range_ = range.end - range.start;
-        let max = !0;
-        let zone = max - (max % range_);
-        loop {
-            let v = rng.gen();
-            if v < zone {
-                return range.start + (v % range_)
+macro_rules! unsigned_impls {
+    ($($t: ident),*) => {$(
+        impl Random<RangeFull> for $t {
+            fn gen<R: Rng>(_: &RangeFull, rng: &mut R) -> $t {
+                rng.gen()
+            }
+        }
+        impl Random<Range<$t>> for $t {
+            fn gen<R: Rng>(range: &Range<$t>, rng: &mut R) -> $t {
+                assert!(range.start < range.end);
+                let range_ = range.end - range.start;
+                let threshold = Uniform::threshold(range_);
+                range.start + <$t>::sample(range_, threshold, rng)
             }
         }
-    }
-}
 
-impl Random<RangeTo<u32>> for u32 {
-    fn gen<R: Rng>(range: &RangeTo<u32>, rng: &mut R) -> u32 {
-        Random::gen(&(0..range.end), rng)
-    }
-}
-impl Random<RangeFrom<u32>> for u32 {
-    #[allow(unsigned_negation)]
-    fn gen<R: Rng>(range: &RangeFrom<u32>, rng: &mut R) -> u32 {
-        if range.start == 0 {
-            return rng.gen()
+        impl Random<RangeTo<$t>> for $t {
+            fn gen<R: Rng>(range: &RangeTo<$t>, rng: &mut R) -> $t {
+                Random::gen(&(0..range.end), rng)
+            }
         }
-        let range_ = -range.start;
-        let max = !0;
-        let zone = max - (max % range_);
-        loop {
-            let v = rng.gen();
-            if v < zone {
-                return range.start + (v % range_)
+        impl Random<RangeToInclusive<$t>> for $t {
+            fn gen<R: Rng>(range: &RangeToInclusive<$t>, rng: &mut R) -> $t {
+                match range.end.checked_add(1) {
+                    None => rng.gen(),
+                    Some(end) => Random::gen(&(0..end), rng),
+                }
             }
         }
-    }
+        impl Random<RangeFrom<$t>> for $t {
+            fn gen<R: Rng>(range: &RangeFrom<$t>, rng: &mut R) -> $t {
+                // The number of values in `start..=MAX`, unless that's
+                // all of them.
+                match ($t::MAX - range.start).checked_add(1) {
+                    None => rng.gen(),
+                    Some(range_) => {
+                        let threshold = Uniform::threshold(range_);
+                        range.start + <$t>::sample(range_, threshold, rng)
+                    }
+                }
+            }
+        }
+    )*}
 }
+unsigned_impls!(u8, u32, u64);
+
 impl Random<RangeFull> for i64 {
     fn gen<R: Rng>(_: &RangeFull, rng: &mut R) -> i64 {
         rng.gen()
@@ -88,135 +102,117 @@ impl Random<Range<i64>> for i64 {
     fn gen<R: Rng>(range: &Range<i64>, rng: &mut R) -> i64 {
         assert!(range.start < range.end);
         let range_ = range.end.wrapping_sub(range.start) as u64;
-        let max = !0;
-        let zone = max - (max % range_);
-        loop {
-            let v: u64 = rng.gen();
-            if v < zone {
-                return range.start.wrapping_add((v % range_) as i64)
-            }
-        }
+        let threshold = Uniform::threshold(range_);
+        range.start.wrapping_add(u64::sample(range_, threshold, rng) as i64)
     }
 }
 
 impl Random<RangeFrom<i64>> for i64 {
     fn gen<R: Rng>(range: &RangeFrom<i64>, rng: &mut R) -> i64 {
-        if range.start == -::std::i64::MIN {
-            return rng.gen()
-        }
-        let range_ = -::std::i64::MIN.wrapping_add(range.start) as u64;
-        let max = !0;
-        let zone = max - (max % range_);
-        loop {
-            let v = rng.gen();
-            if v < zone {
-                return range.start.wrapping_add((v % range_) as i64)
+        match (i64::MAX.wrapping_sub(range.start) as u64).checked_add(1) {
+            None => rng.gen(),
+            Some(range_) => {
+                let threshold = Uniform::threshold(range_);
+                range.start.wrapping_add(u64::sample(range_, threshold, rng) as i64)
             }
         }
     }
 }
-
-impl Random<Range<f64>> for f64 {
-    fn gen<R: Rng>(range: &Range<f64>, rng: &mut R) -> f64 {
-        assert!(range.start < range.end);
-        range.start + rng.gen() * (range.end - range.start)
+impl Random<RangeTo<i64>> for i64 {
+    fn gen<R: Rng>(range: &RangeTo<i64>, rng: &mut R) -> i64 {
+        Random::gen(&(i64::MIN..range.end), rng)
     }
 }
-impl Random<RangeFull> for f64 {
-    fn gen<R: Rng>(_: &RangeFull, rng: &mut R) -> f64 {
-        rng.gen()
+impl Random<RangeToInclusive<i64>> for i64 {
+    fn gen<R: Rng>(range: &RangeToInclusive<i64>, rng: &mut R) -> i64 {
+        match range.end.checked_add(1) {
+            None => rng.gen(),
+            Some(end) => Random::gen(&(i64::MIN..end), rng),
+        }
     }
 }
 
-#[cfg(test)]
-use test::{Bencher, black_box};
-#[cfg(test)]
-use rand;
-
-#[bench]
-fn iter(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), ..).take(100)) {
-            black_box(x);
+macro_rules! float_impls {
+    ($($t: ident),*) => {$(
+        /// Values in `[start, end)`.
+        impl Random<Range<$t>> for $t {
+            fn gen<R: Rng>(range: &Range<$t>, rng: &mut R) -> $t {
+                let (low, width, scale) = <$t>::half_open(range.start, range.end);
+                (low + <$t>::closed_open01(rng) * width) * scale
+            }
         }
-    })
-}
-
-#[bench]
-fn iter__noiterbb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for x in (gen_iter::<u32, _, _>(rng.clone(), ..).take(100)) {
-            black_box(x);
+        // Like the `RangeFull` impl, these start at zero, so `..end`
+        // is `0.0..end`.
+        impl Random<RangeTo<$t>> for $t {
+            fn gen<R: Rng>(range: &RangeTo<$t>, rng: &mut R) -> $t {
+                Random::gen(&(0.0..range.end), rng)
+            }
         }
-    })
-}
-
-#[bench]
-fn range_iter__bb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), black_box(4..321)).take(100)) {
-            black_box(x);
+        impl Random<RangeToInclusive<$t>> for $t {
+            fn gen<R: Rng>(range: &RangeToInclusive<$t>, rng: &mut R) -> $t {
+                <$t>::closed01(rng) * <$t>::closed_width(range.end)
+            }
         }
-    })
-}
-
-#[bench]
-fn range_iter(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for x in black_box(gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100)) {
-            black_box(x);
+        /// Values in `[0, 1)`.
+        impl Random<RangeFull> for $t {
+            fn gen<R: Rng>(_: &RangeFull, rng: &mut R) -> $t {
+                <$t>::closed_open01(rng)
+            }
         }
-    })
-}
-
-#[bench]
-fn range_iter__noiterbb(b: &mut Bencher) {
-    let rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for x in (gen_iter::<u32, _, _>(rng.clone(), 4..321).take(100)) {
-            black_box(x);
+        impl Random<Open01> for $t {
+            fn gen<R: Rng>(_: &Open01, rng: &mut R) -> $t {
+                <$t>::open01(rng)
+            }
         }
-    })
-}
-
-#[bench]
-fn gen_(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, ..));
+        impl Random<Closed01> for $t {
+            fn gen<R: Rng>(_: &Closed01, rng: &mut R) -> $t {
+                <$t>::closed01(rng)
+            }
         }
-    })
-}
-
-#[bench]
-fn range_gen__bb(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, black_box(4..321)));
+        impl Random<OpenClosed01> for $t {
+            fn gen<R: Rng>(_: &OpenClosed01, rng: &mut R) -> $t {
+                <$t>::open_closed01(rng)
+            }
         }
-    })
-}
-
-#[bench]
-fn range_gen(b: &mut Bencher) {
-    let mut rng: rand::XorShiftRng = rand::random();
-
-    b.iter(|| {
-        for _ in 4..321 {
-            black_box(gen::<u32, _, _>(&mut rng, (4..321)));
+        impl Random<HighPrecision01> for $t {
+            fn gen<R: Rng>(_: &HighPrecision01, rng: &mut R) -> $t {
+                <$t>::high_precision01(rng)
+            }
+        }
+    )*}
+}
+float_impls!(f32, f64);
+
+/// Types with a single value, which need no randomness: `gen(rng, ..)`
+/// gives that value without drawing from `rng`, so (e.g. derived)
+/// code generating a struct needs no special case for its markers.
+macro_rules! trivial_impls {
+    ($($t: ty, $value: expr, [$($param: tt)*];)*) => {$(
+        impl<$($param)*> Random<RangeFull> for $t {
+            fn gen<R: Rng>(_: &RangeFull, _: &mut R) -> $t {
+                $value
+            }
+        }
+    )*}
+}
+trivial_impls! {
+    (), (), [];
+    marker::PhantomData<T>, marker::PhantomData, [T: ?Sized];
+    marker::PhantomPinned, marker::PhantomPinned, [];
+    [T; 0], [], [T];
+}
+
+/// Smart pointers and cells, with their contents generated with the
+/// same constraint, e.g. `gen::<Box<u32>, _, _>(rng, 4..321)`.
+macro_rules! wrapper_impls {
+    ($($t: ident),*) => {$(
+        impl<T: Random<Constraint>, Constraint> Random<Constraint> for $t<T> {
+            fn gen<R: Rng>(constraint: &Constraint, rng: &mut R) -> $t<T> {
+                $t::new(T::gen(constraint, rng))
+            }
         }
-    })
+    )*}
 }
+#[cfg(feature = "alloc")]
+wrapper_impls!(Box, Rc, Arc);
+wrapper_impls!(Cell, RefCell);
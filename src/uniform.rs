@@ -0,0 +1,73 @@
+//! Unbiased sampling of `0..range`, shared by the designs.
+//!
+//! The obvious approach draws a value of the same width as `range`
+//! and rejects those above the largest multiple of `range`, which
+//! rejects nearly half of all draws when `range` is just above a power
+//! of two. Instead, this draws a value `v` twice as wide as the type
+//! (for types up to 32 bits) and uses the high half of `v * range`,
+//! rejecting only if the low half is below `2^wide % range` (the
+//! `threshold`). That happens with probability below `range / 2^wide`,
+//! so almost never: e.g. at most `2^-32` for `u32`.
+//!
+//! For 64-bit types the draw isn't widened, so the worst case is the
+//! same as the obvious approach, but the multiply avoids a division
+//! per draw.
+
+use rand::Rng;
+
+pub trait Uniform: Copy {
+    /// The rejection threshold for sampling from `0..range`.
+    fn threshold(range: Self) -> Self;
+
+    /// Sample from `0..range`, where `threshold` is
+    /// `Uniform::threshold(range)`.
+    fn sample<R: Rng>(range: Self, threshold: Self, rng: &mut R) -> Self;
+
+    /// Sample from `0..range` like `sample`, computing the threshold
+    /// only if a draw might be rejected. That's rare for the widened
+    /// types, so this is barely slower than `sample`, but needs
+    /// nothing stored; for 64-bit types, it's a division per draw in
+    /// the worst case.
+    fn sample_lazy<R: Rng>(range: Self, rng: &mut R) -> Self;
+}
+
+macro_rules! uniform_impls {
+    ($($t: ident, $wide: ident, $product: ident, $next: ident;)*) => {$(
+        impl Uniform for $t {
+            #[inline]
+            fn threshold(range: $t) -> $t {
+                ((range as $wide).wrapping_neg() % range as $wide) as $t
+            }
+
+            #[inline]
+            fn sample<R: Rng>(range: $t, threshold: $t, rng: &mut R) -> $t {
+                loop {
+                    let m = rng.$next() as $product * range as $product;
+
+                    if m as $wide >= threshold as $wide {
+                        return (m >> $wide::BITS) as $t
+                    }
+                }
+            }
+
+            #[inline]
+            fn sample_lazy<R: Rng>(range: $t, rng: &mut R) -> $t {
+                loop {
+                    let m = rng.$next() as $product * range as $product;
+
+                    // `threshold < range`, so this usually avoids
+                    // computing it.
+                    if m as $wide >= range as $wide ||
+                        m as $wide >= <$t>::threshold(range) as $wide {
+                        return (m >> $wide::BITS) as $t
+                    }
+                }
+            }
+        }
+    )*}
+}
+uniform_impls! {
+    u8, u32, u64, next_u32;
+    u32, u64, u128, next_u64;
+    u64, u64, u128, next_u64;
+}
@@ -0,0 +1,89 @@
+//! Choosing among a runtime subset of an enum's variants (or any
+//! other values), e.g. to narrow what a test generates without
+//! defining a new type.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+use seq::gen_index;
+use stream::{Rand, RandStream};
+use weighted::AliasTable;
+
+/// One of a fixed list of values, uniformly or by weight, e.g.
+/// `Suit::rand(VariantMask::only([Suit::Hearts, Suit::Spades]))`.
+///
+/// The list is checked when the mask is built, so it's its own
+/// stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantMask<T> {
+    variants: Vec<T>,
+    /// `None` for uniform.
+    weights: Option<AliasTable>,
+}
+
+impl<T> VariantMask<T> {
+    /// Each of `variants`, equally likely. Repeating a variant makes
+    /// it proportionally more likely.
+    ///
+    /// # Panics
+    ///
+    /// If `variants` is empty.
+    pub fn only<I: IntoIterator<Item = T>>(variants: I) -> VariantMask<T> {
+        let variants = variants.into_iter().collect::<Vec<_>>();
+        assert!(!variants.is_empty(), "no variants allowed");
+        VariantMask { variants, weights: None }
+    }
+
+    /// Each of the variants of `weighted`, with probability
+    /// proportional to its weight.
+    ///
+    /// # Panics
+    ///
+    /// If `weighted` is empty, or the weights aren't finite and
+    /// non-negative with a positive sum.
+    pub fn weighted<I: IntoIterator<Item = (T, f64)>>(weighted: I) -> VariantMask<T> {
+        let (variants, weights): (Vec<_>, Vec<_>) = weighted.into_iter().unzip();
+        assert!(!variants.is_empty(), "no variants allowed");
+        VariantMask {
+            variants,
+            weights: Some(AliasTable::new(&weights)),
+        }
+    }
+
+    /// The variants `all[i]` for each bit `i` set in `mask`, equally
+    /// likely, e.g. `VariantMask::from_bits(&Suit::ALL, 0b0101)` for
+    /// the first and third.
+    ///
+    /// # Panics
+    ///
+    /// If no bit of `mask` below `all.len()` is set.
+    pub fn from_bits(all: &[T], mask: u64) -> VariantMask<T>
+        where T: Clone
+    {
+        VariantMask::only(all.iter().take(64).enumerate()
+                          .filter(|&(i, _)| mask >> i & 1 == 1)
+                          .map(|(_, v)| v.clone()))
+    }
+
+    /// The variants that can be chosen, in order.
+    pub fn variants(&self) -> &[T] {
+        &self.variants
+    }
+}
+
+impl<T: Clone> Rand<VariantMask<T>> for T {
+    type Stream = VariantMask<T>;
+    fn rand(dist: VariantMask<T>) -> VariantMask<T> {
+        dist
+    }
+}
+
+impl<T: Clone> RandStream<T> for VariantMask<T> {
+    fn next<R: Rng>(&self, rng: &mut R) -> T {
+        let index = match self.weights {
+            Some(ref table) => table.sample(rng),
+            None => gen_index(self.variants.len(), rng),
+        };
+        self.variants[index].clone()
+    }
+}
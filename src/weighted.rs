@@ -0,0 +1,275 @@
+//! Choosing items with probability proportional to their weights.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use float::Float;
+use rand::Rng;
+use seq::gen_index;
+
+/// Items with weights that can change, sampled with probability
+/// proportional to their weight.
+///
+/// The weights are kept in a Fenwick tree (of partial sums), so
+/// sampling, pushing, reweighting and removing all take `O(log n)`
+/// time, e.g. for simulations where the rates of events change each
+/// step. Items keep their index for as long as they're in the tree.
+///
+/// Repeatedly updating weights accumulates rounding error in the
+/// partial sums, which `rebuild` clears out.
+#[derive(Clone, Debug, Default)]
+pub struct WeightedTree<T> {
+    items: Vec<Option<T>>,
+    weights: Vec<f64>,
+    /// `tree[i - 1]` is the sum of the weights with (one-based)
+    /// indices in `(i - lowbit(i), i]`.
+    tree: Vec<f64>,
+}
+
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+fn check_weight(weight: f64) {
+    assert!(weight.is_finite() && weight >= 0.0, "weight must be finite and non-negative");
+}
+
+impl<T> WeightedTree<T> {
+    /// An empty tree.
+    pub fn new() -> WeightedTree<T> {
+        WeightedTree {
+            items: vec![],
+            weights: vec![],
+            tree: vec![],
+        }
+    }
+
+    /// The number of items, including removed ones (whose indices
+    /// aren't reused).
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no items have ever been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The item at `index`, unless it's been removed.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index).and_then(|item| item.as_ref())
+    }
+
+    /// The weight of the item at `index` (zero if it's been removed).
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn weight(&self, index: usize) -> f64 {
+        self.weights[index]
+    }
+
+    /// The sum of all the weights.
+    pub fn total_weight(&self) -> f64 {
+        self.prefix(self.tree.len())
+    }
+
+    /// Add `item` with `weight`, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// If `weight` is negative or isn't finite.
+    pub fn push(&mut self, item: T, weight: f64) -> usize {
+        check_weight(weight);
+        let index = self.items.len();
+        self.items.push(Some(item));
+        self.weights.push(weight);
+
+        self.push_node(weight);
+        index
+    }
+
+    /// Change the weight of the item at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds or has been removed, or `weight` is
+    /// negative or isn't finite.
+    pub fn set_weight(&mut self, index: usize, weight: f64) {
+        check_weight(weight);
+        assert!(self.get(index).is_some(), "no item at index {}", index);
+        self.update(index, weight);
+    }
+
+    /// Remove the item at `index`, returning it (or `None` if it was
+    /// already removed). Its index isn't reused.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let item = self.items[index].take();
+        if item.is_some() {
+            self.update(index, 0.0);
+        }
+        item
+    }
+
+    /// Recompute the partial sums from the weights, discarding the
+    /// rounding error accumulated by updates.
+    pub fn rebuild(&mut self) {
+        self.tree.clear();
+        for index in 0..self.weights.len() {
+            let weight = self.weights[index];
+            self.push_node(weight);
+        }
+    }
+
+    /// The index of an item chosen with probability proportional to
+    /// its weight, or `None` if every weight is zero.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let total = self.total_weight();
+        if total <= 0.0 {
+            return None
+        }
+        let mut remaining = f64::closed_open01(rng) * total;
+
+        // Descend the tree for the first index whose prefix sum
+        // exceeds `remaining`.
+        let n = self.tree.len();
+        let mut pos = 0;
+        let mut step = n.next_power_of_two();
+        while step > 0 {
+            if pos + step <= n && self.tree[pos + step - 1] <= remaining {
+                pos += step;
+                remaining -= self.tree[pos - 1];
+            }
+            step >>= 1;
+        }
+
+        // Rounding can land just past the end, or on an item with no
+        // weight (whose prefix sum equals the previous one): step back
+        // to the nearest item that can actually be chosen.
+        let mut index = pos.min(n - 1);
+        while self.weights[index] == 0.0 {
+            if index == 0 {
+                return self.weights.iter().position(|&w| w > 0.0)
+            }
+            index -= 1;
+        }
+        Some(index)
+    }
+
+    /// Add the node for the next weight, which covers it plus the
+    /// nodes below it.
+    fn push_node(&mut self, weight: f64) {
+        let i = self.tree.len() + 1;
+        let mut sum = weight;
+        let mut j = i - 1;
+        while j > i - lowbit(i) {
+            sum += self.tree[j - 1];
+            j -= lowbit(j);
+        }
+        self.tree.push(sum);
+    }
+
+    fn update(&mut self, index: usize, weight: f64) {
+        let delta = weight - self.weights[index];
+        self.weights[index] = weight;
+        let mut i = index + 1;
+        while i <= self.tree.len() {
+            self.tree[i - 1] += delta;
+            i += lowbit(i);
+        }
+    }
+
+    /// The sum of the first `i` weights.
+    fn prefix(&self, mut i: usize) -> f64 {
+        let mut sum = 0.0;
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i -= lowbit(i);
+        }
+        sum
+    }
+}
+
+impl<T> ::std::iter::FromIterator<(T, f64)> for WeightedTree<T> {
+    fn from_iter<I: IntoIterator<Item = (T, f64)>>(iter: I) -> WeightedTree<T> {
+        let mut tree = WeightedTree::new();
+        for (item, weight) in iter {
+            tree.push(item, weight);
+        }
+        tree
+    }
+}
+
+/// A fixed set of weights, sampled in constant time with Walker's
+/// alias method (as constructed by Vose).
+///
+/// Each of the `n` columns holds some of the probability of one index
+/// and the rest of that of an "alias", so sampling picks a column
+/// uniformly and then one of its two indices.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AliasTable {
+    /// The probability of keeping each column's own index rather than
+    /// its alias.
+    keep: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// A table choosing each index with probability proportional to
+    /// `weights[index]`.
+    ///
+    /// # Panics
+    ///
+    /// If any weight is negative or isn't finite, or they're all zero.
+    pub fn new(weights: &[f64]) -> AliasTable {
+        for &weight in weights {
+            check_weight(weight);
+        }
+        let total = weights.iter().sum::<f64>();
+        assert!(total > 0.0, "weights must not all be zero");
+
+        let n = weights.len();
+        let mut keep = weights.iter().map(|w| w * n as f64 / total).collect::<Vec<_>>();
+        let mut alias = (0..n).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..n).partition(|&i| keep[i] < 1.0);
+        // Fill each underfull column with the excess of an overfull
+        // one, which may then become underfull itself.
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            keep[l] -= 1.0 - keep[s];
+            if keep[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // What's left is full, up to rounding.
+        for i in small.into_iter().chain(large) {
+            keep[i] = 1.0;
+        }
+        AliasTable { keep, alias }
+    }
+
+    /// The number of indices.
+    pub fn len(&self) -> usize {
+        self.keep.len()
+    }
+
+    /// Whether there are no indices (which `new` doesn't allow).
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+    }
+
+    /// An index chosen with probability proportional to its weight.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> usize {
+        let column = gen_index(self.keep.len(), rng);
+        if f64::closed_open01(rng) < self.keep[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
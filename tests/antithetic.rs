@@ -0,0 +1,65 @@
+//! Antithetic pairs from streams.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{Antithetic, Rand, RandStream};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn floats_reflect() {
+    let mut rng = rng();
+    let pairs = Antithetic::new(f64::rand(..));
+    for _ in 0..1000 {
+        let (u, v) = pairs.next(&mut rng);
+        assert_eq!(u + v, 1.0 - f64::EPSILON / 2.0);
+    }
+    let pairs = Antithetic::new(f32::rand(2.0..4.0));
+    for _ in 0..1000 {
+        let (u, v) = pairs.next(&mut rng);
+        assert!((u + v - 6.0).abs() < 1e-5, "{} {}", u, v);
+    }
+}
+
+#[test]
+fn integers_reflect() {
+    // Apart from rounding near the boundaries between values, the pairs
+    // sum to the largest value.
+    let mut rng = rng();
+    let pairs = Antithetic::new(u32::rand(0..10));
+    let reflected = (0..10_000).filter(|_| {
+        let (a, b) = pairs.next(&mut rng);
+        assert!(a < 10 && b < 10);
+        a + b == 9
+    }).count();
+    assert!(reflected > 9_990, "{}", reflected);
+}
+
+#[test]
+fn reduces_variance() {
+    // Estimating the mean of `u^2` from pairs has lower variance than
+    // from independent values.
+    let mut rng = rng();
+    let uniform = f64::rand(..);
+    let pairs = Antithetic::new(uniform);
+    let (n, trials) = (100, 200);
+    let variance = |estimates: Vec<f64>| {
+        let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+        estimates.iter().map(|e| (e - mean) * (e - mean)).sum::<f64>() / estimates.len() as f64
+    };
+    let independent = (0..trials).map(|_| {
+        (0..n).map(|_| uniform.next(&mut rng).powi(2)).sum::<f64>() / n as f64
+    }).collect();
+    let antithetic = (0..trials).map(|_| {
+        (0..n / 2).map(|_| {
+            let (u, v) = pairs.next(&mut rng);
+            u * u + v * v
+        }).sum::<f64>() / n as f64
+    }).collect();
+    let (independent, antithetic) = (variance(independent), variance(antithetic));
+    assert!(antithetic < independent / 4.0, "{} vs {}", antithetic, independent);
+}
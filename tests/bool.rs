@@ -0,0 +1,68 @@
+//! The edge cases of `Bernoulli` and `Ratio`: certainties, the
+//! probabilities closest to them, and invalid probabilities.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{Rng, XorShiftRng};
+use rand_sketch::stream::{gen, Bernoulli, Ratio, RngExt};
+
+/// A generator that returns `bits` forever, for the smallest and
+/// largest draws. Only `Bernoulli` can use this: `Ratio` rejects some
+/// draws, and would reject a constant one forever.
+struct ConstRng(u64);
+
+impl Rng for ConstRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0 as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+}
+
+#[test]
+fn bernoulli_certainties() {
+    for &bits in &[0, !0] {
+        let mut rng = ConstRng(bits);
+        assert!(!rng.gen_bool(0.0) && rng.gen_bool(1.0));
+    }
+    // The smallest and largest probabilities short of certainty, with
+    // the draws that decide them.
+    assert!(ConstRng(0).gen_bool(2f64.powi(-64)));
+    assert!(!ConstRng(!0).gen_bool(1.0 - f64::EPSILON / 2.0));
+}
+
+#[test]
+fn ratio_certainties() {
+    let mut rng = XorShiftRng::new_unseeded();
+    for _ in 0..100 {
+        assert!(!rng.gen_ratio(0, 7) && rng.gen_ratio(7, 7));
+        assert!(!rng.gen_ratio(0, u32::MAX) && rng.gen_ratio(u32::MAX, u32::MAX));
+    }
+}
+
+#[test]
+#[should_panic(expected = "probability must be in [0, 1]")]
+fn bernoulli_above_one() {
+    gen::<bool, _, _>(&mut ConstRng(0), Bernoulli(1.5));
+}
+
+#[test]
+#[should_panic(expected = "probability must be in [0, 1]")]
+fn bernoulli_nan() {
+    gen::<bool, _, _>(&mut ConstRng(0), Bernoulli(f64::NAN));
+}
+
+#[test]
+#[should_panic(expected = "ratio must be in [0, 1]")]
+fn ratio_above_one() {
+    gen::<bool, _, _>(&mut XorShiftRng::new_unseeded(), Ratio(3, 2));
+}
+
+#[test]
+#[should_panic(expected = "ratio must be in [0, 1]")]
+fn ratio_zero_denominator() {
+    gen::<bool, _, _>(&mut XorShiftRng::new_unseeded(), Ratio(0, 0));
+}
@@ -0,0 +1,115 @@
+//! Integer constraints at the edges: single-value ranges, ranges
+//! touching the minimum or maximum of the type, `RangeFrom` and
+//! `RangeTo(Inclusive)` ending at extreme values, and the widest ranges
+//! each type allows.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use std::fmt::Debug;
+
+/// Draw values via `f`, checking that they all lie in `lo..=hi`, and
+/// that both ends appear when there are few enough possible values
+/// for that to be (all but) certain.
+fn check<T, F>(lo: T, hi: T, mut f: F)
+    where T: Copy + PartialOrd + Debug + Into<i128>, F: FnMut(&mut XorShiftRng) -> T
+{
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let (mut saw_lo, mut saw_hi) = (false, false);
+    for _ in 0..1000 {
+        let v = f(&mut rng);
+        assert!(lo <= v && v <= hi, "{:?} not in {:?}..={:?}", v, lo, hi);
+        saw_lo |= v == lo;
+        saw_hi |= v == hi;
+    }
+    if hi.into() - lo.into() < 8 {
+        assert!(saw_lo && saw_hi, "never saw both {:?} and {:?}", lo, hi);
+    }
+}
+
+/// For each `$name: $t, $constraint => $lo, $hi`, a test that the
+/// values from `$design::gen` with `$constraint` lie in `$lo..=$hi`
+/// (and hit both ends, for small ranges).
+macro_rules! boundaries {
+    ($design: ident { $($name: ident: $t: ident, $constraint: expr => $lo: expr, $hi: expr;)* }) => {
+        mod $design {
+            use super::check;
+            use rand::{SeedableRng, XorShiftRng};
+            use rand_sketch::$design::gen;
+
+            $(
+                #[test]
+                fn $name() {
+                    check::<$t, _>($lo, $hi, |rng| gen::<$t, _, _>(rng, $constraint));
+                }
+            )*
+
+            #[test]
+            #[should_panic]
+            fn empty_range() {
+                let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+                gen::<u32, _, _>(&mut rng, 5..5);
+            }
+        }
+    }
+}
+
+// The literals are suffixed where `assoc` needs them, since its
+// conversions into constraints are generic.
+macro_rules! all_boundaries {
+    ($($design: ident),*) => {$(
+        boundaries!($design {
+            u8_singleton: u8, 5..6 => 5, 5;
+            u8_bottom: u8, 0..3 => 0, 2;
+            u8_top: u8, 252..255 => 252, 254;
+            u8_widest: u8, 0..255 => 0, 254;
+            u8_from_max: u8, 255.. => 255, 255;
+            u8_from_near_max: u8, 252.. => 252, 255;
+            u8_from_zero: u8, 0.. => 0, 255;
+            u8_to_one: u8, ..1 => 0, 0;
+            u8_to_inclusive_zero: u8, ..=0 => 0, 0;
+            u8_to_inclusive_small: u8, ..=3 => 0, 3;
+            u8_to_inclusive_max: u8, ..=u8::MAX => 0, u8::MAX;
+            u32_singleton: u32, 5..6 => 5, 5;
+            u32_bottom: u32, 0..3 => 0, 2;
+            u32_top: u32, u32::MAX - 3..u32::MAX => u32::MAX - 3, u32::MAX - 1;
+            u32_widest: u32, 0..u32::MAX => 0, u32::MAX - 1;
+            u32_from_max: u32, u32::MAX.. => u32::MAX, u32::MAX;
+            u32_from_near_max: u32, u32::MAX - 3.. => u32::MAX - 3, u32::MAX;
+            u32_from_zero: u32, 0.. => 0, u32::MAX;
+            u32_to_one: u32, ..1 => 0, 0;
+            u32_to_inclusive_zero: u32, ..=0 => 0, 0;
+            u32_to_inclusive_small: u32, ..=3 => 0, 3;
+            u32_to_inclusive_max: u32, ..=u32::MAX => 0, u32::MAX;
+            u64_singleton: u64, 5u64..6 => 5, 5;
+            u64_bottom: u64, 0u64..3 => 0, 2;
+            u64_top: u64, u64::MAX - 3..u64::MAX => u64::MAX - 3, u64::MAX - 1;
+            u64_widest: u64, 0..u64::MAX => 0, u64::MAX - 1;
+            u64_from_max: u64, u64::MAX.. => u64::MAX, u64::MAX;
+            u64_from_near_max: u64, u64::MAX - 3.. => u64::MAX - 3, u64::MAX;
+            u64_from_zero: u64, 0u64.. => 0, u64::MAX;
+            u64_to_one: u64, ..1u64 => 0, 0;
+            u64_to_inclusive_zero: u64, ..=0u64 => 0, 0;
+            u64_to_inclusive_small: u64, ..=3u64 => 0, 3;
+            u64_to_inclusive_max: u64, ..=u64::MAX => 0, u64::MAX;
+            i64_singleton_min: i64, i64::MIN..i64::MIN + 1 => i64::MIN, i64::MIN;
+            i64_singleton_max: i64, i64::MAX - 1..i64::MAX => i64::MAX - 1, i64::MAX - 1;
+            i64_bottom: i64, i64::MIN..i64::MIN + 3 => i64::MIN, i64::MIN + 2;
+            i64_top: i64, i64::MAX - 3..i64::MAX => i64::MAX - 3, i64::MAX - 1;
+            i64_across_zero: i64, -2i64..2 => -2, 1;
+            i64_widest: i64, i64::MIN..i64::MAX => i64::MIN, i64::MAX - 1;
+            i64_from_min: i64, i64::MIN.. => i64::MIN, i64::MAX;
+            i64_from_max: i64, i64::MAX.. => i64::MAX, i64::MAX;
+            i64_from_near_max: i64, i64::MAX - 3.. => i64::MAX - 3, i64::MAX;
+            i64_from_zero: i64, 0i64.. => 0, i64::MAX;
+            i64_from_minus_one: i64, -1i64.. => -1, i64::MAX;
+            i64_to_min_plus_one: i64, ..i64::MIN + 1 => i64::MIN, i64::MIN;
+            i64_to_zero: i64, ..0i64 => i64::MIN, -1;
+            i64_to_inclusive_min: i64, ..=i64::MIN => i64::MIN, i64::MIN;
+            i64_to_inclusive_near_min: i64, ..=i64::MIN + 3 => i64::MIN, i64::MIN + 3;
+            i64_to_inclusive_max: i64, ..=i64::MAX => i64::MIN, i64::MAX;
+        });
+    )*}
+}
+all_boundaries!(assoc, typeparam, stream);
@@ -0,0 +1,60 @@
+//! Streams built once per call site.
+
+extern crate rand;
+#[macro_use]
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{Rand, RandStream};
+use std::ops::Range;
+use std::ptr;
+use std::thread;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn built_once() {
+    let mut built = 0;
+    let mut first = None;
+    for _ in 0..10 {
+        let stream = cached_stream!(u32, {
+            built += 1;
+            4..321
+        });
+        assert!(ptr::eq(stream, *first.get_or_insert(stream)));
+    }
+    assert_eq!(built, 1);
+}
+
+#[test]
+fn same_values() {
+    let (mut a, mut b) = (rng(), rng());
+    let stream = u32::rand(0..52);
+    for _ in 0..100 {
+        assert_eq!(cached_stream!(u32, 0..52).next(&mut a), stream.next(&mut b));
+    }
+}
+
+#[test]
+fn shared_between_threads() {
+    fn stream() -> &'static <f64 as Rand<Range<f64>>>::Stream {
+        cached_stream!(f64, -1.0..1.0)
+    }
+    let address = thread::spawn(|| stream() as *const _ as usize).join().unwrap();
+    assert_eq!(address, stream() as *const _ as usize);
+}
+
+fn generic<T>(range: Range<T>) -> &'static T::Stream
+    where T: Rand<Range<T>>, T::Stream: Send + Sync + 'static
+{
+    cached_stream!(T, range)
+}
+
+#[test]
+#[should_panic(expected = "cached_stream! used for different types at one call site")]
+fn generic_misuse() {
+    generic(0u32..10);
+    generic(0u64..10);
+}
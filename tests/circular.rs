@@ -0,0 +1,60 @@
+//! Angles wrapped into `[0, 2π)`.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{CircularUniform, WrappedNormal};
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::stats;
+use std::f64::consts::TAU;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// The direction and length of the mean of the unit vectors at
+/// `angles`.
+fn mean_resultant(angles: &[f64]) -> (f64, f64) {
+    let n = angles.len() as f64;
+    let (x, y) = angles.iter().fold((0.0, 0.0), |(x, y), a| (x + a.cos(), y + a.sin()));
+    ((y / n).atan2(x / n).rem_euclid(TAU), (x * x + y * y).sqrt() / n)
+}
+
+#[test]
+fn uniform_arc_across_zero() {
+    let (start, width) = (350f64.to_radians(), 20f64.to_radians());
+    let angles = gen_iter::<f64, _, _>(rng(), CircularUniform { start, width }).take(20_000).collect::<Vec<_>>();
+    assert!(angles.iter().all(|&a| (0.0..TAU).contains(&a)));
+    // Ten buckets of two degrees, starting at 350.
+    let result = stats::chi_squared_uniform(&angles.iter().fold([0; 10], |mut counts, &a| {
+        let offset = (a - start).rem_euclid(TAU);
+        assert!(offset < width, "{}", a.to_degrees());
+        counts[(offset / width * 10.0) as usize] += 1;
+        counts
+    }));
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn uniform_whole_circle() {
+    let angles = gen_iter::<f64, _, _>(rng(), CircularUniform { start: -1.0, width: TAU }).take(10_000).collect::<Vec<_>>();
+    assert!(angles.iter().all(|&a| (0.0..TAU).contains(&a)));
+    assert!(mean_resultant(&angles).1 < 0.03);
+}
+
+#[test]
+fn wrapped_normal() {
+    // The mean resultant length is `e^(-sd^2 / 2)`.
+    let angles = gen_iter::<f64, _, _>(rng(), WrappedNormal { mean: -0.5, sd: 1.2 }).take(100_000).collect::<Vec<_>>();
+    assert!(angles.iter().all(|&a| (0.0..TAU).contains(&a)));
+    let (direction, length) = mean_resultant(&angles);
+    assert!((direction - (TAU - 0.5)).abs() < 0.02, "{}", direction);
+    assert!((length - (-0.72f64).exp()).abs() < 0.01, "{}", length);
+}
+
+#[test]
+#[should_panic]
+fn uniform_too_wide() {
+    gen_iter::<f64, _, _>(rng(), CircularUniform { start: 0.0, width: 7.0 });
+}
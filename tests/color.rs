@@ -0,0 +1,52 @@
+//! Random colours.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::color;
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn hsl() {
+    assert_eq!(color::hsl(0.0, 1.0, 0.5), [255, 0, 0]);
+    assert_eq!(color::hsl(1.0 / 3.0, 1.0, 0.5), [0, 255, 0]);
+    assert_eq!(color::hsl(2.0 / 3.0, 1.0, 0.5), [0, 0, 255]);
+    assert_eq!(color::hsl(0.5, 1.0, 0.25), [0, 128, 128]);
+    assert_eq!(color::hsl(0.3, 0.0, 0.5), [128, 128, 128]);
+    assert_eq!(color::hsl(0.9, 0.7, 1.0), [255, 255, 255]);
+    assert_eq!(color::hsl(0.9, 0.7, 0.0), [0, 0, 0]);
+}
+
+#[test]
+fn rgb_uniform() {
+    let mut rng = rng();
+    for channel in 0..3 {
+        let result = stats::uniformity(256, 100, || color::rgb(&mut rng)[channel] as usize);
+        assert!(result.passes(0.001), "{:?}", result);
+    }
+}
+
+#[test]
+fn distinct() {
+    let colors = color::distinct(&mut rng()).take(12).collect::<Vec<_>>();
+    for (i, a) in colors.iter().enumerate() {
+        for b in &colors[..i] {
+            let distance = a.iter().zip(b).map(|(&x, &y)| (x as i32 - y as i32).pow(2)).sum::<i32>();
+            assert!(distance > 40 * 40, "{:?} and {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn distinct_start_varies() {
+    let mut rng = rng();
+    let mut firsts = (0..10).map(|_| color::distinct(&mut rng).next().unwrap()).collect::<Vec<_>>();
+    firsts.sort();
+    firsts.dedup();
+    assert!(firsts.len() > 5, "{:?}", firsts);
+}
@@ -0,0 +1,34 @@
+//! Ranges with bounds fixed at compile time.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen, ConstRange};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// Check that `ConstRange::<$low, $high>` gives the same values as
+/// `$low..$high` for `$t`.
+macro_rules! same_as_range {
+    ($($name: ident: $t: ident, $low: expr, $high: expr;)*) => {$(
+        #[test]
+        fn $name() {
+            let (mut a, mut b) = (rng(), rng());
+            for _ in 0..1000 {
+                let x = gen::<$t, _, _>(&mut a, ConstRange::<{ $low }, { $high }>);
+                assert_eq!(x, gen::<$t, _, _>(&mut b, $low..$high));
+            }
+        }
+    )*}
+}
+same_as_range! {
+    u8_small: u8, 4, 21;
+    u8_wide: u8, 0, 255;
+    u32_small: u32, 4, 321;
+    u32_wide: u32, 1, 0x8000_0001;
+    u64_small: u64, 4, 321;
+    u64_wide: u64, 1 << 63, u64::MAX;
+}
@@ -0,0 +1,95 @@
+//! The accessors and common traits of constraints and streams.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::XorShiftRng;
+use rand_sketch::assoc::{gen, gen_iter, FloatConstraint, IntegerConstraint};
+use rand_sketch::stream::Rand;
+
+#[test]
+fn assoc_integer() {
+    let c = IntegerConstraint::<u32>::from(4..321);
+    assert_eq!((c.low(), c.width()), (4, Some(317)));
+    assert_eq!(c, c.clone());
+    assert!(c != IntegerConstraint::from(4..322));
+    assert!(format!("{:?}", c).contains("317"));
+
+    let full = IntegerConstraint::<u32>::from(..);
+    assert_eq!(full.width(), None);
+    let from_zero = IntegerConstraint::<u32>::from(0..);
+    assert_eq!(full, from_zero);
+
+    assert_eq!(std::mem::size_of::<IntegerConstraint<u32>>(), 12);
+
+    let signed = IntegerConstraint::<u64>::from(-5i64..5);
+    assert_eq!((signed.low() as i64, signed.width()), (-5, Some(10)));
+}
+
+/// A constraint of one's own, via an ordinary `From` impl.
+struct Die;
+
+impl From<Die> for IntegerConstraint<u32> {
+    fn from(_die: Die) -> IntegerConstraint<u32> {
+        (1..7).into()
+    }
+}
+
+#[test]
+fn assoc_from() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let roll = gen::<u32, _, _>(&mut rng, Die);
+    assert!((1..=6).contains(&roll));
+
+    // A constraint is also a constraint for itself.
+    let c = IntegerConstraint::<u32>::from(1..7);
+    assert!(gen_iter::<u32, _, _>(rng, c).take(100).all(|roll| (1..=6).contains(&roll)));
+}
+
+#[test]
+fn assoc_float() {
+    let c = FloatConstraint::<f64>::from(-4.0..321.0);
+    assert_eq!((c.low(), c.width()), (-4.0, 325.0));
+    assert_eq!(c, c.clone());
+
+    let full = FloatConstraint::<f64>::from(..);
+    assert_eq!((full.low(), full.width()), (0.0, 1.0));
+    let widest = FloatConstraint::<f64>::from(-f64::MAX..f64::MAX);
+    assert_eq!(widest.low(), -f64::MAX);
+    assert!(widest.width().is_infinite());
+}
+
+#[test]
+fn stream_integer() {
+    let s = u8::rand(4..21);
+    assert_eq!((s.low(), s.width()), (4, 17));
+    assert_eq!(s, u8::rand(4..21));
+    assert!(s != u8::rand(4..22));
+
+    let s = i64::rand(-5..5);
+    assert_eq!((s.low(), s.width()), (-5, 10));
+
+    let from = u64::rand(10..);
+    assert_eq!((from.low(), from.width()), (10, Some(u64::MAX - 9)));
+    let all = i64::rand(i64::MIN..);
+    assert_eq!((all.low(), all.width()), (i64::MIN, None));
+
+    // Narrow types don't store the rejection threshold.
+    let from = u32::rand(7..);
+    assert_eq!((from.low(), from.width()), (7, Some(u32::MAX - 6)));
+    assert_eq!(from, u32::rand(7..));
+    assert!(format!("{:?}", from).contains("7"));
+    assert_eq!(std::mem::size_of_val(&s), 24);
+    assert_eq!(std::mem::size_of_val(&u32::rand(4..21)), 8);
+    assert_eq!(std::mem::size_of_val(&u8::rand(4..21)), 2);
+}
+
+#[test]
+fn stream_float() {
+    let s = f32::rand(-4.0..321.0);
+    assert_eq!((s.low(), s.width()), (-4.0, 325.0));
+    assert_eq!(s, s.clone());
+
+    let s = f64::rand(..=2.0);
+    assert_eq!((s.low(), s.width()), (0.0, 2.0));
+}
@@ -0,0 +1,112 @@
+//! Correlated values with given marginals, by a Gaussian copula.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{correlated_normals, Distribution, GaussianCopula};
+use rand_sketch::stream::{gen_iter, Rand};
+use rand_sketch::testing::{stats, RunningStats};
+use std::f64::consts::PI;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// The Pearson correlation of the pairs.
+fn correlation(pairs: &[(f64, f64)]) -> f64 {
+    let xs = pairs.iter().map(|p| p.0).collect::<RunningStats>();
+    let ys = pairs.iter().map(|p| p.1).collect::<RunningStats>();
+    let covariance = pairs.iter()
+        .map(|&(x, y)| (x - xs.mean()) * (y - ys.mean()))
+        .sum::<f64>() / pairs.len() as f64;
+    covariance / (xs.std_dev() * ys.std_dev())
+}
+
+#[test]
+fn normal_pairs() {
+    for &rho in &[-1.0, -0.3, 0.0, 0.8] {
+        let pairs = gen_iter::<(f64, f64), _, _>(rng(), correlated_normals(rho))
+            .take(50_000)
+            .collect::<Vec<_>>();
+        for stats in &[
+            pairs.iter().map(|p| p.0).collect::<RunningStats>(),
+            pairs.iter().map(|p| p.1).collect::<RunningStats>(),
+        ] {
+            assert!(stats.mean().abs() < 0.02, "{}: {}", rho, stats.mean());
+            assert!((stats.std_dev() - 1.0).abs() < 0.02, "{}: {}", rho, stats.std_dev());
+        }
+        let actual = correlation(&pairs);
+        assert!((actual - rho).abs() < 0.01, "{} vs. {}", actual, rho);
+    }
+}
+
+#[test]
+#[should_panic(expected = "in [-1, 1]")]
+fn correlation_out_of_range() {
+    correlated_normals(1.5);
+}
+
+#[test]
+fn uniform_marginals() {
+    let rho = 0.7;
+    let dist = GaussianCopula {
+        correlation: [[1.0, rho], [rho, 1.0]],
+        marginals: [f64::rand(0.0..1.0), f64::rand(0.0..1.0)],
+    };
+    let pairs = gen_iter::<[f64; 2], _, _>(rng(), dist)
+        .take(50_000)
+        .map(|[x, y]| (x, y))
+        .collect::<Vec<_>>();
+    for i in 0..2 {
+        let mut values = pairs.iter().map(|p| if i == 0 { p.0 } else { p.1 });
+        let result = stats::uniformity(20, 2500, || (values.next().unwrap() * 20.0) as usize);
+        assert!(result.passes(0.001), "{}: {:?}", i, result);
+    }
+    // With uniform marginals, the Pearson correlation is the Spearman
+    // (rank) correlation.
+    let expected = 6.0 / PI * (rho / 2.0).asin();
+    let actual = correlation(&pairs);
+    assert!((actual - expected).abs() < 0.01, "{} vs. {}", actual, expected);
+}
+
+#[test]
+fn exponential_marginals() {
+    let exponential = || Distribution::from_quantile(|u: f64| -(-u).ln_1p());
+    let dist = GaussianCopula {
+        correlation: [[1.0, 0.5, -0.5], [0.5, 1.0, 0.0], [-0.5, 0.0, 1.0]],
+        marginals: [exponential(), exponential(), exponential()],
+    };
+    let values = gen_iter::<[f64; 3], _, _>(rng(), dist)
+        .take(50_000)
+        .collect::<Vec<_>>();
+    for i in 0..3 {
+        let stats = values.iter().map(|v| v[i]).collect::<RunningStats>();
+        assert!(stats.min() >= 0.0);
+        assert!((stats.mean() - 1.0).abs() < 0.02, "{}: {}", i, stats.mean());
+        assert!((stats.std_dev() - 1.0).abs() < 0.03, "{}: {}", i, stats.std_dev());
+    }
+    let pairs = |i: usize, j: usize| values.iter().map(|v| (v[i], v[j])).collect::<Vec<_>>();
+    assert!(correlation(&pairs(0, 1)) > 0.3);
+    assert!(correlation(&pairs(0, 2)) < -0.3);
+    assert!(correlation(&pairs(1, 2)).abs() < 0.02);
+}
+
+#[test]
+#[should_panic(expected = "positive definite")]
+fn not_positive_definite() {
+    let rho = -0.9;
+    <[f64; 3]>::rand(GaussianCopula {
+        correlation: [[1.0, rho, rho], [rho, 1.0, rho], [rho, rho, 1.0]],
+        marginals: [f64::rand(0.0..1.0), f64::rand(0.0..1.0), f64::rand(0.0..1.0)],
+    });
+}
+
+#[test]
+#[should_panic(expected = "symmetric")]
+fn not_symmetric() {
+    <[f64; 2]>::rand(GaussianCopula {
+        correlation: [[1.0, 0.5], [0.2, 1.0]],
+        marginals: [f64::rand(0.0..1.0), f64::rand(0.0..1.0)],
+    });
+}
@@ -0,0 +1,66 @@
+//! Dates and times in ranges.
+#![cfg(feature = "chrono")]
+
+extern crate chrono;
+extern crate rand;
+extern crate rand_sketch;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen_iter, Rand, RandStream};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn dates_uniform() {
+    // 2024 is a leap year, so has 366 days, each equally likely.
+    let start = date(2024, 1, 1);
+    let stream = NaiveDate::rand(start..date(2025, 1, 1));
+    let mut rng = rng();
+    let result = stats::uniformity(366, 100, || (stream.next(&mut rng) - start).num_days() as usize);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn single_day() {
+    let day = date(1999, 12, 31);
+    assert!(gen_iter::<NaiveDate, _, _>(rng(), day..date(2000, 1, 1)).take(100).all(|d| d == day));
+}
+
+#[test]
+fn date_times_keep_the_fraction() {
+    let start = date(2000, 1, 1).and_hms_milli_opt(0, 0, 0, 250).unwrap();
+    let end = date(2000, 1, 1).and_hms_milli_opt(0, 0, 2, 500).unwrap();
+    let mut seen = [false; 3];
+    for t in gen_iter::<NaiveDateTime, _, _>(rng(), start..end).take(100) {
+        assert!(start <= t && t < end);
+        assert_eq!(t.nanosecond(), 250_000_000);
+        seen[t.second() as usize] = true;
+    }
+    // 0.25, 1.25 and 2.25 are below 2.5.
+    assert_eq!(seen, [true; 3]);
+}
+
+#[test]
+fn zoned() {
+    let zone = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+    let start = zone.with_ymd_and_hms(2020, 3, 1, 12, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2020, 3, 8, 0, 0, 0).unwrap().with_timezone(&zone);
+    for t in gen_iter::<DateTime<FixedOffset>, _, _>(rng(), start..end).take(1000) {
+        assert!(start <= t && t < end);
+        assert_eq!(t.offset(), &zone);
+    }
+}
+
+#[test]
+#[should_panic]
+fn empty() {
+    NaiveDate::rand(date(2000, 1, 1)..date(2000, 1, 1));
+}
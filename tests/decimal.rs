@@ -0,0 +1,73 @@
+//! Decimals with a fixed number of places.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{Decimal, Rand, RandStream};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn prices() {
+    let (units, prices) = (i64::rand(Decimal(0.01..=99.99, 2)), f64::rand(Decimal(0.01..=99.99, 2)));
+    let mut rng = rng();
+    let (mut saw_min, mut saw_max) = (false, false);
+    for _ in 0..100_000 {
+        let cents: i64 = units.next(&mut rng);
+        assert!((1..=9999).contains(&cents), "{}", cents);
+        saw_min |= cents == 1;
+        saw_max |= cents == 9999;
+
+        // Exactly the nearest `f64` to a whole number of cents.
+        let price: f64 = prices.next(&mut rng);
+        assert!((0.01..=99.99).contains(&price), "{}", price);
+        assert_eq!(price, (price * 100.0).round() / 100.0);
+    }
+    assert!(saw_min && saw_max);
+}
+
+#[test]
+fn uniform_steps() {
+    // 0.0, 0.1, ..., 1.0: eleven values.
+    let stream = f64::rand(Decimal(0.0..=1.0, 1));
+    let mut rng = rng();
+    let result = stats::uniformity(11, 11_000, || {
+        let v: f64 = stream.next(&mut rng);
+        (v * 10.0).round() as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn negative_and_whole() {
+    let stream = i64::rand(Decimal(-3.0..=-1.0, 0));
+    let mut rng = rng();
+    let mut seen = [false; 3];
+    for _ in 0..100 {
+        let v: i64 = stream.next(&mut rng);
+        seen[(v + 3) as usize] = true;
+    }
+    assert_eq!(seen, [true; 3]);
+}
+
+#[test]
+#[should_panic(expected = "decimal range must be non-empty")]
+fn empty() {
+    f64::rand(Decimal(2.0..=1.0, 2));
+}
+
+#[test]
+#[should_panic(expected = "at most 15 decimal places are supported")]
+fn too_many_places() {
+    f64::rand(Decimal(0.0..=1.0, 16));
+}
+
+#[test]
+#[should_panic(expected = "decimal range must be finite and fit in 53 bits")]
+fn too_wide() {
+    f64::rand(Decimal(0.0..=1e10, 10));
+}
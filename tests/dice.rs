@@ -0,0 +1,64 @@
+//! Dice notation.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::dice::Dice;
+use rand_sketch::stream::{RandStream, RngExt};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn dice(s: &str) -> Dice {
+    s.parse().unwrap_or_else(|e| panic!("{:?}: {}", s, e))
+}
+
+#[test]
+fn bounds() {
+    for &(s, min, max) in &[("3d6+2", 5, 20), ("d20", 1, 20), ("2d8-1d4", -2, 15),
+                            ("d%", 1, 100), ("-d6", -6, -1), ("4", 4, 4),
+                            (" 1D1 + 2d3 - 7 ", -4, 0), ("1d6-2+1d6", 0, 10)] {
+        let dice = dice(s);
+        assert_eq!((dice.min(), dice.max()), (min, max), "{:?}", s);
+        let mut rng = rng();
+        let (mut saw_min, mut saw_max) = (false, false);
+        for _ in 0..10_000 {
+            let v = dice.next(&mut rng);
+            assert!(min <= v && v <= max, "{:?} gave {}", s, v);
+            saw_min |= v == min;
+            saw_max |= v == max;
+        }
+        assert!(saw_min && saw_max, "{:?}", s);
+    }
+}
+
+#[test]
+fn two_dice() {
+    // The totals of `2d6` are triangular: 1, 2, ..., 6, ..., 2, 1 out
+    // of 36.
+    let mut rng = rng();
+    let dice = dice("2d6");
+    let mut counts = vec![0; 11];
+    for _ in 0..36_000 {
+        counts[rng.random::<i64, _>(dice.clone()) as usize - 2] += 1;
+    }
+    let expected = (2..13).map(|t: i32| 1000.0 * (6 - (t - 7).abs()) as f64).collect::<Vec<_>>();
+    let result = stats::chi_squared(&counts, &expected);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn invalid() {
+    for &(s, message) in &[("", "no dice given"), ("  ", "no dice given"),
+                           ("3d", "invalid dice notation"), ("d", "invalid dice notation"),
+                           ("3d6+", "invalid dice notation"), ("3d6++2", "invalid dice notation"),
+                           ("3 d6", "invalid dice notation"), ("2x6", "invalid dice notation"),
+                           ("3d0", "dice must have at least one side"),
+                           ("99999999999d6", "number too large in dice notation")] {
+        let error = s.parse::<Dice>().unwrap_err();
+        assert_eq!(error.to_string(), message, "{:?}", s);
+    }
+}
@@ -0,0 +1,167 @@
+//! Continuous distributions, checked against their moments.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{Gev, LogitNormal, Normal, SkewNormal, TruncatedNormal};
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::RunningStats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// The statistics of 100,000 values from `dist`.
+fn stats<Dist>(dist: Dist) -> RunningStats where f64: rand_sketch::stream::Rand<Dist> {
+    gen_iter::<f64, _, _>(rng(), dist).take(100_000).collect()
+}
+
+/// Asserts that `stats` is within `tolerance` of the mean and
+/// standard deviation expected.
+fn check(stats: &RunningStats, mean: f64, sd: f64, tolerance: f64) {
+    assert!((stats.mean() - mean).abs() < tolerance, "mean {} not {}", stats.mean(), mean);
+    assert!((stats.std_dev() - sd).abs() < tolerance, "sd {} not {}", stats.std_dev(), sd);
+}
+
+#[test]
+fn normal() {
+    check(&stats(Normal { mean: 10.0, sd: 2.0 }), 10.0, 2.0, 0.02);
+}
+
+#[test]
+#[should_panic]
+fn normal_invalid() {
+    stats(Normal { mean: 0.0, sd: -1.0 });
+}
+
+/// The mean and standard deviation of a standard normal truncated to
+/// `[a, b]`, from the densities `phi` at, and probability `mass`
+/// between, the ends.
+fn truncated_moments(a: f64, b: f64, phi_a: f64, phi_b: f64, mass: f64) -> (f64, f64) {
+    // `x phi(x)` tends to zero at infinity.
+    let a_phi_a = if a.is_finite() { a * phi_a } else { 0.0 };
+    let b_phi_b = if b.is_finite() { b * phi_b } else { 0.0 };
+    let mean = (phi_a - phi_b) / mass;
+    let variance = 1.0 + (a_phi_a - b_phi_b) / mass - mean * mean;
+    (mean, variance.sqrt())
+}
+
+#[test]
+fn truncated_containing_mean() {
+    // Narrow enough for uniform rejection, and wide enough for normal
+    // rejection. `phi(1) = 0.24197`, `phi(2) = 0.05399`, `Phi(2) -
+    // Phi(-1) = 0.81859`, `Phi(0.5) - Phi(-0.5) = 0.38292`.
+    let (mean, sd) = truncated_moments(-1.0, 2.0, 0.241971, 0.053991, 0.818595);
+    let stats = stats(TruncatedNormal { mean: 0.0, sd: 1.0, low: -1.0, high: 2.0 });
+    check(&stats, mean, sd, 0.01);
+    assert!(stats.min() >= -1.0 && stats.max() <= 2.0);
+    let (mean, sd) = truncated_moments(-0.5, 0.5, 0.352065, 0.352065, 0.382925);
+    check(&stats_scaled(-0.5, 0.5), mean, sd, 0.01);
+}
+
+/// The statistics of a truncated `N(3, 2^2)`, with the interval `[a,
+/// b]` in standard deviations, mapped back to standard units.
+fn stats_scaled(a: f64, b: f64) -> RunningStats {
+    gen_iter::<f64, _, _>(rng(), TruncatedNormal { mean: 3.0, sd: 2.0, low: 3.0 + 2.0 * a, high: 3.0 + 2.0 * b })
+        .take(100_000)
+        .map(|x| (x - 3.0) / 2.0)
+        .collect()
+}
+
+#[test]
+fn truncated_far_tail() {
+    // Beyond 5σ, where rejecting normals would take 3.5 million tries
+    // each. `phi(5) = 1.4867e-6`, `1 - Phi(5) = 2.8665e-7`.
+    let (mean, sd) = truncated_moments(5.0, f64::INFINITY, 1.486720e-6, 0.0, 2.866516e-7);
+    let stats = stats(TruncatedNormal { mean: 0.0, sd: 1.0, low: 5.0, high: f64::INFINITY });
+    check(&stats, mean, sd, 0.005);
+    assert!(stats.min() >= 5.0);
+}
+
+#[test]
+fn truncated_far_tail_reflected() {
+    let (mean, sd) = truncated_moments(5.0, f64::INFINITY, 1.486720e-6, 0.0, 2.866516e-7);
+    let stats = stats_scaled(-f64::INFINITY, -5.0);
+    check(&stats, -mean, sd, 0.005);
+    assert!(stats.max() <= -5.0);
+}
+
+#[test]
+fn truncated_narrow_tail() {
+    // Narrow enough, this far out, for uniform rejection: the density
+    // falls by a factor of `e^-0.505` across it, so the mean is a
+    // little below the midpoint. `phi(5) = 1.4867e-6`, `phi(5.1) =
+    // 8.9724e-7`, `Phi(5.1) - Phi(5) = 1.1682e-7`.
+    let (mean, sd) = truncated_moments(5.0, 5.1, 1.486720e-6, 8.972435e-7, 1.168248e-7);
+    let stats = stats_scaled(5.0, 5.1);
+    check(&stats, mean, sd, 0.002);
+    assert!(stats.min() >= 5.0 && stats.max() <= 5.1);
+}
+
+#[test]
+fn truncated_wide_tail() {
+    // Exponential rejection, with values beyond `b` rejected too.
+    // `phi(1) = 0.24197`, `phi(3) = 0.0044318`, `Phi(3) - Phi(1) =
+    // 0.15731`.
+    let (mean, sd) = truncated_moments(1.0, 3.0, 0.241971, 0.004432, 0.157305);
+    let stats = stats_scaled(1.0, 3.0);
+    check(&stats, mean, sd, 0.01);
+    assert!(stats.min() >= 1.0 && stats.max() <= 3.0);
+}
+
+#[test]
+#[should_panic]
+fn truncated_empty() {
+    stats(TruncatedNormal { mean: 0.0, sd: 1.0, low: 1.0, high: 1.0 });
+}
+
+#[test]
+fn skew_normal() {
+    // `δ = 3 / sqrt(10)`: the mean is `location + scale δ sqrt(2/π)`,
+    // and the variance `scale^2 (1 - 2δ^2/π)`.
+    let delta = 3.0 / 10f64.sqrt();
+    let mean = 1.0 + 2.0 * delta * (2.0 / std::f64::consts::PI).sqrt();
+    let sd = 2.0 * (1.0 - 2.0 * delta * delta / std::f64::consts::PI).sqrt();
+    check(&stats(SkewNormal { location: 1.0, scale: 2.0, shape: 3.0 }), mean, sd, 0.02);
+    let mirrored = stats(SkewNormal { location: 1.0, scale: 2.0, shape: -3.0 });
+    check(&mirrored, 2.0 - mean, sd, 0.02);
+    check(&stats(SkewNormal { location: 1.0, scale: 2.0, shape: 0.0 }), 1.0, 2.0, 0.02);
+}
+
+#[test]
+fn logit_normal() {
+    let values = gen_iter::<f64, _, _>(rng(), LogitNormal { mean: 0.5, sd: 1.5 }).take(100_000).collect::<Vec<_>>();
+    assert!(values.iter().all(|&x| 0.0 < x && x < 1.0));
+    let logits = values.iter().map(|&x| (x / (1.0 - x)).ln()).collect::<RunningStats>();
+    check(&logits, 0.5, 1.5, 0.02);
+}
+
+#[test]
+fn gev_gumbel() {
+    // The mean is `location + scale γ`, and the standard deviation
+    // `scale π / sqrt(6)`.
+    check(&stats(Gev { location: 2.0, scale: 3.0, shape: 0.0 }), 3.731647, 3.847649, 0.04);
+}
+
+#[test]
+fn gev_frechet() {
+    // The mean is `location + scale (Γ(1 - ξ) - 1) / ξ`, and the
+    // variance `scale^2 (Γ(1 - 2ξ) - Γ(1 - ξ)^2) / ξ^2`.
+    let stats = stats(Gev { location: 0.0, scale: 1.0, shape: 0.1 });
+    check(&stats, 0.686287, 1.492059, 0.03);
+    assert!(stats.min() >= -10.0);
+}
+
+#[test]
+fn gev_weibull() {
+    let stats = stats(Gev { location: 1.0, scale: 2.0, shape: -0.5 });
+    check(&stats, 1.455092, 1.853006, 0.02);
+    assert!(stats.max() <= 5.0);
+}
+
+#[test]
+fn gev_small_shape() {
+    // Close to Gumbel, with no cancellation.
+    check(&stats(Gev { location: 2.0, scale: 3.0, shape: 1e-12 }), 3.731647, 3.847649, 0.04);
+}
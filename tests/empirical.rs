@@ -0,0 +1,96 @@
+//! Resampling from observed data, exactly or smoothed.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{Empirical, Kde};
+use rand_sketch::stream::{gen_iter, Rand, RandStream};
+use rand_sketch::testing::{stats, RunningStats};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn samples_sorted() {
+    let dist = Empirical::from_samples(&[3.0, -1.0, 2.5, -1.0]);
+    assert_eq!(dist.samples(), [-1.0, -1.0, 2.5, 3.0]);
+}
+
+#[test]
+fn resamples_each_equally() {
+    let stream = f64::rand(Empirical::from_samples(&[40.0, 10.0, 30.0, 20.0]));
+    let mut rng = rng();
+    let result = stats::uniformity(4, 2000, || {
+        let x = stream.next(&mut rng);
+        assert_eq!(x % 10.0, 0.0);
+        x as usize / 10 - 1
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn interpolated_between_samples() {
+    // Evenly spaced samples interpolate to a uniform distribution.
+    let samples = (0..=10).map(f64::from).collect::<Vec<_>>();
+    let stream = f64::rand(Empirical::interpolated(&samples));
+    let mut rng = rng();
+    let result = stats::uniformity(20, 1000, || {
+        let x = stream.next(&mut rng);
+        assert!((0.0..10.0).contains(&x));
+        (x * 2.0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn interpolated_quantiles() {
+    // A quarter of the probability lies between each consecutive pair
+    // of five samples, however far apart they are.
+    let samples = [0.0, 1.0, 2.0, 10.0, 100.0];
+    let values = gen_iter::<f64, _, _>(rng(), Empirical::interpolated(&samples)).take(40_000);
+    let mut counts = [0; 4];
+    let summary = values.inspect(|&x| counts[samples.iter().rposition(|&s| s <= x).unwrap()] += 1)
+        .collect::<RunningStats>();
+    assert!(summary.min() >= 0.0 && summary.max() < 100.0);
+    let result = stats::chi_squared_uniform(&counts);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn single_sample() {
+    let values = gen_iter::<f64, _, _>(rng(), Empirical::interpolated(&[7.5])).take(10).collect::<Vec<_>>();
+    assert_eq!(values, [7.5; 10]);
+}
+
+#[test]
+#[should_panic]
+fn no_samples() {
+    Empirical::from_samples(&[]);
+}
+
+#[test]
+#[should_panic]
+fn nan_sample() {
+    Empirical::from_samples(&[1.0, f64::NAN]);
+}
+
+#[test]
+fn kde_moments() {
+    // An equal mixture of `N(0, 1)` and `N(10, 1)`: the mean is 5, and
+    // the variance `25 + 1`.
+    let values = gen_iter::<f64, _, _>(rng(), Kde::new(&[0.0, 10.0], 1.0)).take(100_000).collect::<Vec<_>>();
+    let summary = values.iter().cloned().collect::<RunningStats>();
+    assert!((summary.mean() - 5.0).abs() < 0.05, "{}", summary.mean());
+    assert!((summary.variance() - 26.0).abs() < 0.2, "{}", summary.variance());
+    // Hardly any values fall between the two bumps.
+    let between = values.iter().filter(|&&x| (4.0..6.0).contains(&x)).count();
+    assert!(between < 100, "{}", between);
+}
+
+#[test]
+#[should_panic]
+fn kde_zero_bandwidth() {
+    Kde::new(&[1.0, 2.0], 0.0);
+}
@@ -0,0 +1,89 @@
+//! Seeding generators from an entropy source.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{ChaChaRng, Rng, SeedableRng, XorShiftRng};
+use rand_sketch::entropy::{EntropySource, FromEntropy};
+use rand_sketch::pcg::Pcg32;
+
+/// A stand-in for a hardware generator: counts up from 1, failing
+/// once it has given `limit` bytes.
+struct Counter {
+    next: u8,
+    limit: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Exhausted;
+
+impl EntropySource for Counter {
+    type Error = Exhausted;
+    fn fill(&mut self, dest: &mut [u8]) -> Result<(), Exhausted> {
+        if dest.len() > self.limit {
+            return Err(Exhausted)
+        }
+        self.limit -= dest.len();
+        for b in dest {
+            *b = self.next;
+            self.next = self.next.wrapping_add(1);
+        }
+        Ok(())
+    }
+}
+
+fn counter() -> Counter {
+    Counter { next: 1, limit: 1000 }
+}
+
+#[test]
+fn seeds_little_endian() {
+    let mut pcg = Pcg32::from_entropy(&mut counter()).unwrap();
+    let mut expected = Pcg32::new(0x0807060504030201, 0x100f0e0d0c0b0a09);
+    assert_eq!(pcg.next_u64(), expected.next_u64());
+
+    let mut xorshift = XorShiftRng::from_entropy(&mut counter()).unwrap();
+    let mut expected = XorShiftRng::from_seed([0x04030201, 0x08070605, 0x0c0b0a09, 0x100f0e0d]);
+    assert_eq!(xorshift.next_u64(), expected.next_u64());
+
+    let mut source = counter();
+    let mut chacha = ChaChaRng::from_entropy(&mut source).unwrap();
+    assert_eq!(source.limit, 1000 - 32);
+    let mut expected = ChaChaRng::from_seed(&[0x04030201, 0x08070605, 0x0c0b0a09, 0x100f0e0d,
+                                               0x14131211, 0x18171615, 0x1c1b1a19, 0x201f1e1d]);
+    assert_eq!(chacha.next_u64(), expected.next_u64());
+}
+
+#[test]
+fn xorshift_skips_zero() {
+    // The first 16 bytes are zeros, which isn't a valid seed.
+    struct Zeros(usize);
+    impl EntropySource for Zeros {
+        type Error = ();
+        fn fill(&mut self, dest: &mut [u8]) -> Result<(), ()> {
+            for b in dest {
+                *b = if self.0 < 16 { 0 } else { 1 };
+                self.0 += 1;
+            }
+            Ok(())
+        }
+    }
+    let mut rng = XorShiftRng::from_entropy(&mut Zeros(0)).unwrap();
+    let mut expected = XorShiftRng::from_seed([0x01010101; 4]);
+    assert_eq!(rng.next_u64(), expected.next_u64());
+}
+
+#[test]
+fn failure() {
+    let mut source = Counter { next: 1, limit: 20 };
+    assert_eq!(ChaChaRng::from_entropy(&mut source).err(), Some(Exhausted));
+    assert_eq!(Pcg32::from_entropy(&mut source).err(), Some(Exhausted));
+}
+
+#[test]
+fn os() {
+    let mut os = rand::OsRng::new().unwrap();
+    let mut a = Pcg32::from_entropy(&mut os).unwrap();
+    let mut b = Pcg32::from_entropy(&mut os).unwrap();
+    assert!(a.next_u64() != b.next_u64());
+}
@@ -0,0 +1,39 @@
+//! `Result`s with a given probability of failure.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen_iter, Fallible};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn arms_use_their_constraints() {
+    for r in gen_iter::<Result<u32, i64>, _, _>(rng(), Fallible(0.5, 4..321, -10i64..0)).take(1000) {
+        match r {
+            Ok(x) => assert!((4..321).contains(&x)),
+            Err(e) => assert!((-10..0).contains(&e)),
+        }
+    }
+}
+
+#[test]
+fn error_rate() {
+    let n = 100_000;
+    let errors = gen_iter::<Result<(), ()>, _, _>(rng(), Fallible(0.1, .., ..))
+        .take(n)
+        .filter(Result::is_err)
+        .count();
+    // 5 standard deviations of the binomial.
+    let sd = (n as f64 * 0.1 * 0.9).sqrt();
+    assert!((errors as f64 - n as f64 * 0.1).abs() < 5.0 * sd, "{} errors", errors);
+}
+
+#[test]
+fn never_and_always() {
+    assert!(gen_iter::<Result<u8, u8>, _, _>(rng(), Fallible(0.0, .., ..)).take(1000).all(|r| r.is_ok()));
+    assert!(gen_iter::<Result<u8, u8>, _, _>(rng(), Fallible(1.0, .., ..)).take(1000).all(|r| r.is_err()));
+}
@@ -0,0 +1,56 @@
+//! The C interface, called from Rust.
+
+#![cfg(feature = "ffi")]
+
+extern crate rand_sketch;
+
+use rand_sketch::ffi::*;
+use rand_sketch::pcg::Pcg32;
+use rand_sketch::stream::gen_iter;
+
+#[test]
+fn fills_like_gen_iter() {
+    unsafe {
+        let rng = rand_sketch_rng_new(42, 54);
+        let stream = rand_sketch_u32_range_new(4, 321);
+        let mut out = [0; 100];
+        rand_sketch_u32_range_fill(stream, rng, out.as_mut_ptr(), out.len());
+        let expected = gen_iter::<u32, _, _>(Pcg32::new(42, 54), 4..321).take(100).collect::<Vec<_>>();
+        assert_eq!(&out[..], &expected[..]);
+
+        let stream_i64 = rand_sketch_i64_range_new(-5, 5);
+        let stream_f64 = rand_sketch_f64_range_new(-1.0, 1.0);
+        let mut ints = [0; 100];
+        let mut floats = [0.0; 100];
+        rand_sketch_i64_range_fill(stream_i64, rng, ints.as_mut_ptr(), ints.len());
+        rand_sketch_f64_range_fill(stream_f64, rng, floats.as_mut_ptr(), floats.len());
+        assert!(ints.iter().all(|x| (-5..5).contains(x)));
+        assert!(floats.iter().all(|x| (-1.0..1.0).contains(x)));
+
+        rand_sketch_u32_range_free(stream);
+        rand_sketch_i64_range_free(stream_i64);
+        rand_sketch_f64_range_free(stream_f64);
+        rand_sketch_rng_free(rng);
+    }
+}
+
+#[test]
+fn invalid_ranges_are_null() {
+    assert!(rand_sketch_u32_range_new(5, 5).is_null());
+    assert!(rand_sketch_i64_range_new(5, -5).is_null());
+    assert!(rand_sketch_f64_range_new(0.0, f64::INFINITY).is_null());
+    assert!(rand_sketch_f64_range_new(f64::NAN, 1.0).is_null());
+}
+
+#[test]
+fn empty_fill_and_null_free() {
+    unsafe {
+        let rng = rand_sketch_rng_new(1, 2);
+        let stream = rand_sketch_u32_range_new(0, 10);
+        rand_sketch_u32_range_fill(stream, rng, std::ptr::null_mut(), 0);
+        rand_sketch_u32_range_free(stream);
+        rand_sketch_u32_range_free(std::ptr::null_mut());
+        rand_sketch_rng_free(rng);
+        rand_sketch_rng_free(std::ptr::null_mut());
+    }
+}
@@ -0,0 +1,71 @@
+//! File names and relative paths that are safe everywhere.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen_iter, Rand, RandStream};
+use rand_sketch::text::{FileName, RelativePath};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, PathBuf};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn safe(name: &str) -> bool {
+    let first = name.chars().next().unwrap();
+    first.is_ascii_alphanumeric()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+#[test]
+fn names() {
+    for name in gen_iter::<String, _, _>(rng(), FileName(1..9)).take(1000) {
+        assert!((1..9).contains(&name.len()), "{}", name);
+        assert!(safe(&name), "{}", name);
+    }
+}
+
+#[test]
+fn no_reserved_names() {
+    // Every three-character name is likely to come up.
+    let names = gen_iter::<String, _, _>(rng(), FileName(3..4)).take(1_000_000)
+        .collect::<HashSet<_>>();
+    assert!(names.len() > 36 * 38 * 38 * 9 / 10);
+    for reserved in ["con", "prn", "aux", "nul"] {
+        assert!(!names.contains(reserved), "{}", reserved);
+    }
+}
+
+#[test]
+fn paths() {
+    let stream = PathBuf::rand(RelativePath(1..4, 1..6));
+    let mut rng = rng();
+    let mut depths = [0; 4];
+    for _ in 0..1000 {
+        let path = stream.next(&mut rng);
+        assert!(path.is_relative());
+        let mut depth = 0;
+        for component in path.components() {
+            match component {
+                Component::Normal(name) => assert!(safe(name.to_str().unwrap()), "{:?}", path),
+                other => panic!("{:?} in {:?}", other, path),
+            }
+            depth += 1;
+        }
+        depths[depth] += 1;
+    }
+    assert_eq!(depths[0], 0);
+    assert!(depths[1..].iter().all(|&n| n > 250), "{:?}", depths);
+}
+
+#[test]
+fn create_tree() {
+    let root = std::env::temp_dir().join(format!("rand-sketch-file-names-{}", std::process::id()));
+    for path in gen_iter::<PathBuf, _, _>(rng(), RelativePath(1..4, 1..9)).take(50) {
+        fs::create_dir_all(root.join(path)).unwrap();
+    }
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,48 @@
+//! Filling uninitialized buffers, and appending to vectors.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{fill_uninit, gen_iter, sample_n_into, Rand};
+use std::mem::MaybeUninit;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn same_as_iter() {
+    let mut buffer = [MaybeUninit::uninit(); 100];
+    let filled = fill_uninit(&u32::rand(4..321), &mut rng(), &mut buffer);
+    let expected = gen_iter::<u32, _, _>(rng(), 4..321).take(100).collect::<Vec<_>>();
+    assert_eq!(filled, &expected[..]);
+}
+
+#[test]
+fn spare_capacity() {
+    let mut rng = rng();
+    let mut values = Vec::<f64>::with_capacity(1 << 16);
+    let len = fill_uninit(&f64::rand(..), &mut rng, values.spare_capacity_mut()).len();
+    unsafe { values.set_len(len) };
+    assert!(values.len() >= 1 << 16);
+    assert!(values.iter().all(|v| (0.0..1.0).contains(v)));
+}
+
+#[test]
+fn empty() {
+    let filled = fill_uninit(&u8::rand(..), &mut rng(), &mut []);
+    assert!(filled.is_empty());
+}
+
+#[test]
+fn append() {
+    let expected = gen_iter::<u32, _, _>(rng(), 4..321).take(150);
+    let stream = u32::rand(4..321);
+    let mut rng = rng();
+    let mut values = vec![0, 1];
+    sample_n_into(&stream, &mut rng, &mut values, 100);
+    sample_n_into(&stream, &mut rng, &mut values, 0);
+    sample_n_into(&stream, &mut rng, &mut values, 50);
+    assert_eq!(values, [0, 1].iter().cloned().chain(expected).collect::<Vec<_>>());
+}
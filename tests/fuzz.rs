@@ -0,0 +1,69 @@
+//! Values from fuzzer input bytes.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use rand_sketch::fuzz::{Budgeted, SliceRng};
+use rand_sketch::stream::gen;
+
+#[test]
+fn slice_draws() {
+    let bytes = [1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0xaa, 0xbb];
+    let mut rng = SliceRng::new(&bytes);
+    assert_eq!(rng.next_u32(), 1);
+    assert_eq!(rng.next_u64(), 2);
+    assert_eq!(rng.remaining(), 2);
+    let mut dest = [0; 2];
+    rng.fill_bytes(&mut dest);
+    assert_eq!(dest, [0xaa, 0xbb]);
+    assert!(rng.is_exhausted());
+}
+
+#[test]
+fn exhausted_still_terminates() {
+    // Too short for a draw, so everything comes from the fallback,
+    // which still gets through rejection sampling.
+    let mut rng = SliceRng::new(&[7, 7]);
+    let a = (0..100).map(|_| gen::<u32, _, _>(&mut rng, 0..3)).collect::<Vec<_>>();
+    assert!(rng.is_exhausted());
+    assert!(a.iter().all(|&x| x < 3));
+    assert!((0..3).all(|x| a.contains(&x)));
+
+    // And the same input gives the same values.
+    let mut rng = SliceRng::new(&[7, 7]);
+    let b = (0..100).map(|_| gen::<u32, _, _>(&mut rng, 0..3)).collect::<Vec<_>>();
+    assert_eq!(a, b);
+}
+
+/// A tree with `len` deciding the number of children, the way a
+/// recursive structure would be built from input.
+fn tree_size(rng: &mut Budgeted) -> usize {
+    let children = rng.len(0..10);
+    1 + (0..children).map(|_| tree_size(rng)).sum::<usize>()
+}
+
+#[test]
+fn sizes_shrink() {
+    let data = (0..1000u32).map(|i| i.wrapping_mul(2654435761) as u8).collect::<Vec<_>>();
+    for len in [0, 10, 100, 1000] {
+        let mut rng = Budgeted::new(&data[..len]);
+        // Without a budget, a branching factor of 4.5 would almost
+        // always run forever. With one, each node with children uses
+        // a draw of four bytes.
+        let size = tree_size(&mut rng);
+        assert!(size <= 1 + (len / 4 + 1) * 9, "{} {}", len, size);
+    }
+}
+
+#[test]
+fn len_scales() {
+    let data = [0xff; 80];
+    let mut rng = Budgeted::new(&data);
+    // All-ones draws pick the top of what's allowed, which falls as
+    // the input is used up.
+    let lens = (0..12).map(|_| rng.len(10..111)).collect::<Vec<_>>();
+    assert_eq!(lens[0], 110);
+    assert!(lens.windows(2).all(|w| w[0] >= w[1]), "{:?}", lens);
+    assert_eq!(lens[11], 10);
+}
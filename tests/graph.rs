@@ -0,0 +1,72 @@
+//! Random graph generators.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::graph::{barabasi_albert, gnp};
+use std::collections::HashSet;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// Check that `edges` are distinct, with `u < v < n`.
+fn check_simple(n: u32, edges: &[(u32, u32)]) {
+    let mut seen = HashSet::new();
+    for &(u, v) in edges {
+        assert!(u < v && v < n, "bad edge {:?}", (u, v));
+        assert!(seen.insert((u, v)), "duplicate edge {:?}", (u, v));
+    }
+}
+
+#[test]
+fn gnp_edges() {
+    let mut rng = rng();
+    let edges = gnp(1000, 0.01, &mut rng);
+    check_simple(1000, &edges);
+    // About 4995 edges, with standard deviation about 70.
+    assert!((edges.len() as f64 - 4995.0).abs() < 300.0, "{}", edges.len());
+}
+
+#[test]
+fn gnp_extremes() {
+    let mut rng = rng();
+    assert_eq!(gnp(100, 0.0, &mut rng), []);
+    let complete = gnp(5, 1.0, &mut rng);
+    assert_eq!(complete, [(0, 1), (0, 2), (1, 2), (0, 3), (1, 3), (2, 3),
+                          (0, 4), (1, 4), (2, 4), (3, 4)]);
+    assert_eq!(gnp(0, 1.0, &mut rng), []);
+    assert_eq!(gnp(1, 1.0, &mut rng), []);
+}
+
+#[test]
+#[should_panic(expected = "probability must be in [0, 1]")]
+fn gnp_bad_probability() {
+    gnp(10, 1.5, &mut rng());
+}
+
+#[test]
+fn barabasi_albert_edges() {
+    let mut rng = rng();
+    let (n, m) = (2000, 3);
+    let edges = barabasi_albert(n, m, &mut rng);
+    check_simple(n, &edges);
+    assert_eq!(edges.len(), (m * (n - m)) as usize);
+
+    let mut degrees = vec![0; n as usize];
+    for &(u, v) in &edges {
+        degrees[u as usize] += 1;
+        degrees[v as usize] += 1;
+    }
+    assert!(degrees.iter().all(|&d| d >= m));
+    // Preferential attachment gives hubs far above the mean degree of
+    // about 6.
+    assert!(*degrees.iter().max().unwrap() > 50);
+}
+
+#[test]
+#[should_panic(expected = "need 1 <= m < n")]
+fn barabasi_albert_too_few_vertices() {
+    barabasi_albert(3, 3, &mut rng());
+}
@@ -0,0 +1,73 @@
+//! Random identifiers.
+
+#![cfg(feature = "ids")]
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{ChaChaRng, SeedableRng, XorShiftRng};
+use rand_sketch::ids::{uuid_v4, uuid_v4_string, Ulid};
+use rand_sketch::stream::RngExt;
+use std::collections::HashSet;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn crypto_rng() -> ChaChaRng {
+    ChaChaRng::from_seed(&[1, 2, 3, 4])
+}
+
+#[test]
+fn uuid() {
+    let mut rng = crypto_rng();
+    let mut seen = HashSet::new();
+    for _ in 0..1000 {
+        let bytes = uuid_v4(&mut rng);
+        assert_eq!(bytes[6] >> 4, 4, "version");
+        assert_eq!(bytes[8] >> 6, 0b10, "variant");
+        assert!(seen.insert(bytes));
+    }
+}
+
+#[test]
+fn uuid_string() {
+    let mut rng = crypto_rng();
+    for _ in 0..100 {
+        let s = uuid_v4_string(&mut rng);
+        let groups = s.split('-').map(str::len).collect::<Vec<_>>();
+        assert_eq!(groups, [8, 4, 4, 4, 12], "{}", s);
+        assert!(s.chars().all(|c| c == '-' || c.is_ascii_digit() || ('a'..='f').contains(&c)), "{}", s);
+        assert_eq!(&s[14..15], "4", "{}", s);
+        assert!("89ab".contains(&s[19..20]), "{}", s);
+    }
+}
+
+#[test]
+fn ulid_fixed_time() {
+    let mut rng = rng();
+    // The timestamp of the spec's example, `01ARYZ6S41TSV4RRFFQ69G5FAV`.
+    let millis = 1_469_918_176_385;
+    let bytes = rng.random::<[u8; 16], _>(Ulid::at(millis));
+    assert_eq!(bytes[..6], [0x01, 0x56, 0x3d, 0xf3, 0x64, 0x81]);
+    let s = rng.random::<String, _>(Ulid::at(millis));
+    assert_eq!(s.len(), 26);
+    assert!(s.starts_with("01ARYZ6S41"), "{}", s);
+    assert!(s.chars().all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() && !"ILOU".contains(c)));
+}
+
+#[test]
+fn ulid_sorts_by_time() {
+    let mut rng = rng();
+    let earlier = rng.random::<String, _>(Ulid::at(1000));
+    let later = rng.random::<String, _>(Ulid::at(1001));
+    assert!(earlier < later);
+    let now = rng.random::<String, _>(Ulid::now());
+    assert!(later < now);
+}
+
+#[test]
+#[should_panic(expected = "ULID timestamp must fit in 48 bits")]
+fn ulid_too_late() {
+    Ulid::at(1 << 48);
+}
@@ -0,0 +1,52 @@
+//! Words imitating a corpus, from a character Markov model.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen_iter, Rand, RandStream};
+use rand_sketch::text::{Imitation, TextModel};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+const NAMES: &str = "
+    amelia oliver isla jack ava harry mia george isabella noah sophia
+    leo grace arthur lily oscar freya charlie emily henry ivy theo
+";
+
+#[test]
+fn lengths_and_characters() {
+    let stream = String::rand(Imitation(TextModel::train(NAMES, 2), 4..8));
+    let mut rng = rng();
+    for _ in 0..1000 {
+        let name = stream.next(&mut rng);
+        assert!((4..8).contains(&name.chars().count()), "{}", name);
+        assert!(name.chars().all(|c| NAMES.contains(c)), "{}", name);
+    }
+}
+
+#[test]
+fn novel_at_low_order() {
+    let model = TextModel::train(NAMES, 1);
+    assert_eq!(model.order(), 1);
+    let novel = gen_iter::<String, _, _>(rng(), Imitation(model, 3..10)).take(100)
+        .filter(|name| !NAMES.split_whitespace().any(|n| n == name))
+        .count();
+    assert!(novel > 50, "{}", novel);
+}
+
+#[test]
+fn high_order_repeats_the_corpus() {
+    let model = TextModel::train(NAMES, 9);
+    for name in gen_iter::<String, _, _>(rng(), Imitation(model, 1..20)).take(1000) {
+        assert!(NAMES.split_whitespace().any(|n| n == name), "{}", name);
+    }
+}
+
+#[test]
+#[should_panic(expected = "no word of 30 to 39 characters")]
+fn unreachable_lengths() {
+    String::rand(Imitation(TextModel::train("ab ba", 1), 30..40)).next(&mut rng());
+}
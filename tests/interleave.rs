@@ -0,0 +1,48 @@
+//! Interleaving streams.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::interleave::{round_robin, weighted};
+use rand_sketch::stream::{gen_iter, Rand};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn round_robin_order() {
+    let streams = vec![u32::rand(0..10), u32::rand(100..110), u32::rand(1000..1010)];
+    let values = round_robin(rng(), streams).take(300).collect::<Vec<_>>();
+    for (i, &(index, value)) in values.iter().enumerate() {
+        assert_eq!(index, i % 3);
+        assert!((0..10).contains(&(value - [0, 100, 1000][index])));
+    }
+    // Each value is the stream's next, from the one generator.
+    let expected = gen_iter::<u32, _, _>(rng(), 0..10).take(3).collect::<Vec<_>>();
+    assert_eq!(round_robin(rng(), vec![u32::rand(0..10)]).take(3).map(|p| p.1).collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn weighted_proportions() {
+    let streams = vec![(u32::rand(0..1_000_000), 0.9), (u32::rand(0..1_000), 0.1)];
+    let mut pairs = weighted(rng(), streams);
+    let mut observed = [0; 2];
+    for _ in 0..10_000 {
+        let (index, value) = pairs.next().unwrap();
+        assert!(value < [1_000_000, 1_000][index]);
+        observed[index] += 1;
+    }
+    let result = stats::chi_squared(&observed, &[9_000.0, 1_000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+#[should_panic(expected = "at least one stream")]
+fn round_robin_empty() {
+    let mut streams = vec![u32::rand(0..10)];
+    streams.clear();
+    round_robin(rng(), streams);
+}
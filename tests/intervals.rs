@@ -0,0 +1,108 @@
+//! Which endpoints float constraints can produce, checked with
+//! generators that return all zero bits and all one bits (the
+//! smallest and largest draws).
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+
+/// A generator that returns `bits` forever.
+struct ConstRng(u64);
+
+impl Rng for ConstRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0 as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+}
+
+/// For each `$name: $t, $constraint => $zeros, $ones`, a test that
+/// `$design::gen` with `$constraint` gives `$zeros` from all zero bits
+/// and `$ones` from all one bits, and for each `$invalid: $it,
+/// $bad`, a test that `$bad` is rejected.
+macro_rules! intervals {
+    ($design: ident { $($name: ident: $t: ident, $constraint: expr => $zeros: expr, $ones: expr;)* }
+     invalid { $($invalid: ident: $it: ident, $bad: expr;)* }) => {
+        mod $design {
+            use super::ConstRng;
+            use rand_sketch::$design::gen;
+            use rand_sketch::{Closed01, HighPrecision01, Open01, OpenClosed01};
+
+            $(
+                #[test]
+                fn $name() {
+                    let zeros = gen::<$t, _, _>(&mut ConstRng(0), $constraint);
+                    let ones = gen::<$t, _, _>(&mut ConstRng(!0), $constraint);
+                    assert_eq!((zeros, ones), ($zeros, $ones));
+                }
+            )*
+
+            $(
+                #[test]
+                #[should_panic]
+                fn $invalid() {
+                    gen::<$it, _, _>(&mut ConstRng(0), $bad);
+                }
+            )*
+        }
+    }
+}
+
+// `1.0 + EPSILON` is the next float after `1.0`, so the scaled
+// largest draw rounds to it unless the width is adjusted, and
+// `-MAX..MAX` is wider than `MAX`.
+macro_rules! all_intervals {
+    ($($design: ident),*) => {$(
+        intervals!($design {
+            f32_full: f32, .. => 0.0, 1.0 - f32::EPSILON / 2.0;
+            f32_open: f32, Open01 => f32::EPSILON / 2.0, 1.0 - f32::EPSILON / 2.0;
+            f32_closed: f32, Closed01 => 0.0, 1.0;
+            f32_open_closed: f32, OpenClosed01 => 1.0, f32::EPSILON / 2.0;
+            f32_high_precision: f32, HighPrecision01 => 0.0, 1.0 - f32::EPSILON / 2.0;
+            f32_range: f32, 1.0..1.0 + f32::EPSILON => 1.0, 1.0;
+            f32_range_to_inclusive: f32, ..=3.0 => 0.0, 3.0;
+            f32_widest: f32, -f32::MAX..f32::MAX => -f32::MAX, f32::MAX * (1.0 - f32::EPSILON);
+            f64_full: f64, .. => 0.0, 1.0 - f64::EPSILON / 2.0;
+            f64_open: f64, Open01 => f64::EPSILON / 2.0, 1.0 - f64::EPSILON / 2.0;
+            f64_closed: f64, Closed01 => 0.0, 1.0;
+            f64_open_closed: f64, OpenClosed01 => 1.0, f64::EPSILON / 2.0;
+            f64_high_precision: f64, HighPrecision01 => 0.0, 1.0 - f64::EPSILON / 2.0;
+            f64_range: f64, 1.0..1.0 + f64::EPSILON => 1.0, 1.0;
+            f64_range_to_inclusive: f64, ..=3.0 => 0.0, 3.0;
+            f64_widest: f64, -f64::MAX..f64::MAX => -f64::MAX, f64::MAX * (1.0 - f64::EPSILON);
+        } invalid {
+            f64_empty: f64, 1.0..1.0;
+            f64_reversed: f64, 1.0..0.0;
+            f64_nan_start: f64, f64::NAN..1.0;
+            f64_nan_end: f64, 0.0..f64::NAN;
+            f64_infinite_start: f64, f64::NEG_INFINITY..0.0;
+            f64_infinite_end: f64, 0.0..f64::INFINITY;
+            f64_to_zero: f64, ..0.0;
+            f64_to_nan: f64, ..f64::NAN;
+            f64_to_inclusive_negative: f64, ..=-1.0;
+            f64_to_inclusive_nan: f64, ..=f64::NAN;
+            f64_to_inclusive_infinite: f64, ..=f64::INFINITY;
+            f32_reversed: f32, 1.0..0.0;
+            f32_nan_start: f32, f32::NAN..1.0;
+            f32_infinite_end: f32, 0.0..f32::INFINITY;
+        });
+    )*}
+}
+all_intervals!(assoc, typeparam, stream);
+
+#[test]
+fn high_precision_near_zero() {
+    use rand_sketch::stream::gen;
+    use rand_sketch::HighPrecision01;
+
+    // A single one bit gives the power of two it represents, with no
+    // mantissa bits (which come from the next draw), where `..` would
+    // round it to zero.
+    assert_eq!(gen::<f32, _, _>(&mut ConstRng(1), HighPrecision01), 2f32.powi(-32));
+    assert_eq!(gen::<f64, _, _>(&mut ConstRng(1), HighPrecision01), 2f64.powi(-64));
+    assert_eq!(gen::<f64, _, _>(&mut ConstRng(1), ..), 0.0);
+}
@@ -0,0 +1,70 @@
+//! Randomized backoff delays.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::jitter::{equal_jitter, full_jitter, Decorrelated};
+use rand_sketch::testing::stats;
+use std::time::Duration;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+const BASE: Duration = Duration::from_millis(100);
+const CAP: Duration = Duration::from_secs(10);
+
+#[test]
+fn full() {
+    let mut rng = rng();
+    // `100ms * 2^3`, uniform.
+    let result = stats::uniformity(10, 10_000, || {
+        let delay = full_jitter(BASE, CAP, 3, &mut rng);
+        assert!(delay < Duration::from_millis(800), "{:?}", delay);
+        (delay.as_secs_f64() / 0.08) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn capped() {
+    let mut rng = rng();
+    for &attempt in &[7, 31, 32, 1000] {
+        for _ in 0..100 {
+            assert!(full_jitter(BASE, CAP, attempt, &mut rng) < CAP);
+            let delay = equal_jitter(BASE, CAP, attempt, &mut rng);
+            assert!(CAP / 2 <= delay && delay < CAP, "{:?}", delay);
+        }
+    }
+    let huge = Duration::from_secs(u64::MAX);
+    assert!(full_jitter(huge, huge, 40, &mut rng) < huge);
+}
+
+#[test]
+fn equal() {
+    let mut rng = rng();
+    let result = stats::uniformity(10, 10_000, || {
+        let delay = equal_jitter(BASE, CAP, 0, &mut rng);
+        assert!(Duration::from_millis(50) <= delay && delay < BASE, "{:?}", delay);
+        ((delay.as_secs_f64() - 0.05) / 0.005) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn decorrelated() {
+    let mut rng = rng();
+    let mut delays = Decorrelated::new(BASE, CAP);
+    let mut last = BASE;
+    let mut reached_cap = false;
+    for _ in 0..100 {
+        let delay = delays.next(&mut rng);
+        assert!(BASE <= delay && delay <= CAP && delay <= last * 3, "{:?} after {:?}", delay, last);
+        reached_cap |= delay > CAP * 9 / 10;
+        last = delay;
+    }
+    assert!(reached_cap);
+    delays.reset();
+    assert!(delays.next(&mut rng) < BASE * 3);
+}
@@ -0,0 +1,76 @@
+//! Random JSON documents.
+#![cfg(feature = "json")]
+
+extern crate rand;
+extern crate rand_sketch;
+extern crate serde_json;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::json::Json;
+use rand_sketch::stream::{gen_iter, Rand};
+use rand_sketch::text::{Charset, Text};
+use serde_json::Value;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn json(depth: usize, len: std::ops::Range<usize>, size: usize) -> Json {
+    Json { depth, len, size, strings: Text(Charset::new("abc"), 0..4) }
+}
+
+/// The depth of containers, and the number of values.
+fn shape(value: &Value) -> (usize, usize) {
+    let children: Vec<&Value> = match *value {
+        Value::Array(ref a) => a.iter().collect(),
+        Value::Object(ref o) => o.values().collect(),
+        _ => return (0, 1),
+    };
+    children.into_iter().map(shape).fold((1, 1), |(d, n), (cd, cn)| (d.max(cd + 1), n + cn))
+}
+
+#[test]
+fn bounds() {
+    let mut deepest = 0;
+    let mut largest = 0;
+    for value in gen_iter::<Value, _, _>(rng(), json(4, 0..6, 200)).take(1000) {
+        let (depth, size) = shape(&value);
+        assert!(depth <= 4 && size <= 200, "{} {}", depth, size);
+        deepest = deepest.max(depth);
+        largest = largest.max(size);
+        if let Value::Array(ref a) = value {
+            assert!(a.len() < 6);
+        }
+        // Always valid to serialize (so no NaNs or infinities). Floats
+        // may not parse back exactly, without `float_roundtrip`.
+        let text = serde_json::to_string(&value).unwrap();
+        assert_eq!(shape(&serde_json::from_str(&text).unwrap()), (depth, size));
+    }
+    assert_eq!(deepest, 4);
+    assert!(largest > 30, "{}", largest);
+}
+
+#[test]
+fn size_budget() {
+    // Deep and wide enough for far more than 10 values, if not cut
+    // short.
+    for value in gen_iter::<Value, _, _>(rng(), json(10, 5..10, 10)).take(1000) {
+        assert!(shape(&value).1 <= 10);
+    }
+}
+
+#[test]
+fn scalars_only() {
+    for value in gen_iter::<Value, _, _>(rng(), json(0, 1..5, 100)).take(1000) {
+        assert!(!value.is_array() && !value.is_object(), "{}", value);
+        if let Value::String(ref s) = value {
+            assert!(s.len() < 4 && s.chars().all(|c| "abc".contains(c)));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "size must be positive")]
+fn zero_size() {
+    Value::rand(json(2, 0..2, 0));
+}
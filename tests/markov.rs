@@ -0,0 +1,57 @@
+//! Markov chains over transition matrices.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::markov::MarkovChain;
+use rand_sketch::stream::RandStream;
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn transitions() {
+    let matrix = [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.5, 0.0, 0.5]];
+    let chain = MarkovChain::new(&matrix, 0);
+    let mut rng = rng();
+
+    // Count transitions out of state 2, which go to 0 or 2 equally.
+    let mut counts = [0; 3];
+    let mut previous = chain.current();
+    for _ in 0..30_000 {
+        let state = chain.next(&mut rng);
+        assert!(matrix[previous][state] > 0.0, "{} -> {}", previous, state);
+        if previous == 2 {
+            counts[state] += 1;
+        }
+        previous = state;
+    }
+    assert_eq!(counts[1], 0);
+    let result = stats::chi_squared_uniform(&[counts[0], counts[2]]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn stationary() {
+    // Two states, with stationary distribution `(1/3, 2/3)`.
+    let chain = MarkovChain::new(&[vec![0.6, 0.4], vec![0.2, 0.8]], 1);
+    assert_eq!(chain.states(), 2);
+    let mut rng = rng();
+    let ones = (0..30_000).filter(|_| chain.next(&mut rng) == 1).count();
+    assert!((ones as f64 / 30_000.0 - 2.0 / 3.0).abs() < 0.02, "{}", ones);
+}
+
+#[test]
+#[should_panic(expected = "row 1 sums to 0.9, not 1")]
+fn not_stochastic() {
+    MarkovChain::new(&[[1.0, 0.0], [0.4, 0.5]], 0);
+}
+
+#[test]
+#[should_panic(expected = "transition matrix must be square")]
+fn not_square() {
+    MarkovChain::new(&[vec![1.0], vec![0.5, 0.5]], 0);
+}
@@ -0,0 +1,49 @@
+//! Markov chain Monte Carlo samplers.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::mcmc::MetropolisHastings;
+use rand_sketch::stream::{Rand, RandStream};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn standard_normal() {
+    let mut rng = rng();
+    let chain = MetropolisHastings::new(5.0, |x: &f64| -x * x / 2.0, f64::rand(-2.0..2.0));
+    for _ in 0..1000 {
+        chain.next(&mut rng);
+    }
+    let n = 100_000;
+    let values = (0..n).map(|_| chain.next(&mut rng)).collect::<Vec<_>>();
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let variance = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n as f64;
+    // The values are correlated, so these are looser than for
+    // independent samples.
+    assert!(mean.abs() < 0.05, "{}", mean);
+    assert!((variance - 1.0).abs() < 0.05, "{}", variance);
+
+    let rate = chain.acceptance_rate();
+    assert!(0.3 < rate && rate < 0.8, "{}", rate);
+}
+
+#[test]
+fn stays_in_support() {
+    // The uniform distribution on `[0, 1]`, which has zero density
+    // outside it.
+    let mut rng = rng();
+    let log_density = |x: &f64| if (0.0..=1.0).contains(x) { 0.0 } else { f64::NEG_INFINITY };
+    let chain = MetropolisHastings::new(0.5, log_density, f64::rand(-0.5..0.5));
+    assert!((0..10_000).all(|_| (0.0..=1.0).contains(&chain.next(&mut rng))));
+    assert!((0.0..=1.0).contains(&chain.current()));
+}
+
+#[test]
+#[should_panic(expected = "initial state must have a finite log-density")]
+fn impossible_start() {
+    MetropolisHastings::new(-1.0, |x: &f64| x.ln(), f64::rand(-0.5..0.5));
+}
@@ -0,0 +1,72 @@
+//! `gen_memo`, which reuses streams between calls.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen, gen_memo, Rand, RandStream};
+use std::cell::Cell;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn same_values_as_gen() {
+    let (mut a, mut b) = (rng(), rng());
+    for i in 0..1000 {
+        // More constraints than the cache holds, so entries are
+        // evicted and rebuilt.
+        let low = i % 13;
+        assert_eq!(gen_memo::<u32, _, _>(&mut a, low..321), gen::<u32, _, _>(&mut b, low..321));
+        assert_eq!(gen_memo::<u64, _, _>(&mut a, low as u64..321), gen::<u64, _, _>(&mut b, low as u64..321));
+        assert_eq!(gen_memo::<f64, _, _>(&mut a, ..), gen::<f64, _, _>(&mut b, ..));
+    }
+}
+
+/// A constraint counting how many streams are built for it.
+#[derive(Clone, Debug)]
+struct Counted(u32);
+
+thread_local!(static BUILT: Cell<u32> = const { Cell::new(0) });
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Counted) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CountedStream(u32);
+
+impl Rand<Counted> for u32 {
+    type Stream = CountedStream;
+    fn rand(dist: Counted) -> CountedStream {
+        BUILT.with(|b| b.set(b.get() + 1));
+        CountedStream(dist.0)
+    }
+}
+
+impl RandStream<u32> for CountedStream {
+    fn next<R: rand::Rng>(&self, _: &mut R) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn reuses_recent_streams() {
+    let mut rng = rng();
+    for _ in 0..10 {
+        for i in 0..8 {
+            assert_eq!(gen_memo::<u32, _, _>(&mut rng, Counted(i)), i);
+        }
+    }
+    assert_eq!(BUILT.with(Cell::get), 8);
+
+    // A ninth evicts the least recently used.
+    gen_memo::<u32, _, _>(&mut rng, Counted(8));
+    gen_memo::<u32, _, _>(&mut rng, Counted(7));
+    assert_eq!(BUILT.with(Cell::get), 9);
+    gen_memo::<u32, _, _>(&mut rng, Counted(0));
+    assert_eq!(BUILT.with(Cell::get), 10);
+}
@@ -0,0 +1,85 @@
+//! Coherent noise.
+
+#![cfg(feature = "noise")]
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::noise::{Perlin, ValueNoise};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// Points on a grid of spacing `1/8` (so including lattice points)
+/// with coordinates in `[-8, 8)`.
+fn grid() -> Vec<f64> {
+    (-64..64).map(|i| f64::from(i) / 8.0 + 1e-3).collect()
+}
+
+#[test]
+fn perlin_zero_on_lattice() {
+    let noise = Perlin::new(&mut rng());
+    for i in -20..20 {
+        let x = f64::from(i);
+        assert_eq!(noise.noise1(x), 0.0);
+        assert_eq!(noise.noise2(x, -x / 2.0 + 0.5 * f64::from(i % 2)), 0.0);
+        assert_eq!(noise.noise3(x, 3.0, -x), 0.0);
+    }
+}
+
+#[test]
+fn bounded_and_varied() {
+    let mut rng = rng();
+    let (perlin, value) = (Perlin::new(&mut rng), ValueNoise::new(&mut rng));
+    let grid = grid();
+    let mut values = Vec::new();
+    for &x in &grid {
+        values.push(perlin.noise1(x));
+        values.push(value.noise1(x));
+        for &y in grid.iter().step_by(3) {
+            values.push(perlin.noise2(x, y));
+            values.push(value.noise2(x, y));
+            values.push(perlin.noise3(x, y, x - y));
+            values.push(value.noise3(x, y, x - y));
+        }
+    }
+    assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    assert!(mean.abs() < 0.1, "{}", mean);
+    assert!(values.iter().any(|&v| v > 0.4) && values.iter().any(|&v| v < -0.4));
+}
+
+#[test]
+fn smooth() {
+    let mut rng = rng();
+    let (perlin, value) = (Perlin::new(&mut rng), ValueNoise::new(&mut rng));
+    let h = 1e-4;
+    for &x in &grid() {
+        let y = 0.37 - x;
+        // The gradients are bounded by a few units, so small steps
+        // make small changes, including across lattice cells.
+        assert!((perlin.noise1(x + h) - perlin.noise1(x)).abs() < 10.0 * h);
+        assert!((perlin.noise2(x + h, y) - perlin.noise2(x, y)).abs() < 10.0 * h);
+        assert!((perlin.noise3(x, y + h, 1.5) - perlin.noise3(x, y, 1.5)).abs() < 10.0 * h);
+        assert!((value.noise1(x + h) - value.noise1(x)).abs() < 10.0 * h);
+        assert!((value.noise2(x + h, y) - value.noise2(x, y)).abs() < 10.0 * h);
+        assert!((value.noise3(x, y + h, 1.5) - value.noise3(x, y, 1.5)).abs() < 10.0 * h);
+    }
+}
+
+#[test]
+fn seeded() {
+    let (a, b) = (Perlin::new(&mut rng()), Perlin::new(&mut rng()));
+    let c = Perlin::new(&mut XorShiftRng::from_seed([5, 6, 7, 8]));
+    let points = [(0.5, 0.5), (1.25, -3.5), (100.1, 7.7)];
+    assert!(points.iter().all(|&(x, y)| a.noise2(x, y) == b.noise2(x, y)));
+    assert!(points.iter().any(|&(x, y)| a.noise2(x, y) != c.noise2(x, y)));
+}
+
+#[test]
+fn periodic() {
+    let noise = ValueNoise::new(&mut rng());
+    assert_eq!(noise.noise2(0.25, 0.5), noise.noise2(256.25, -255.5));
+}
@@ -0,0 +1,143 @@
+//! Big integers and complex numbers.
+
+#![cfg(feature = "num")]
+
+extern crate num_bigint;
+extern crate num_complex;
+extern crate rand;
+extern crate rand_sketch;
+
+use num_bigint::BigUint;
+use num_complex::Complex;
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::num::{random_unitary, Disc, StandardComplexNormal};
+use rand_sketch::stream::{Rand, RandStream};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn small_range() {
+    // Straddling a digit boundary, with a range of 6.
+    let low = BigUint::from(u32::MAX) - 2u32;
+    let stream = BigUint::rand(low.clone()..&low + 6u32);
+    let mut rng = rng();
+    let result = stats::uniformity(6, 6000, || {
+        let v = stream.next(&mut rng) - &low;
+        v.to_u32_digits().first().cloned().unwrap_or(0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn huge_range() {
+    // About `3 * 2^199` values, so the top digit is neither full nor a
+    // power of two, and two thirds of them are below `2^200`.
+    let end = (BigUint::from(3u32) << 199usize) + 1u32;
+    let half = BigUint::from(1u32) << 200usize;
+    let stream = BigUint::rand(BigUint::from(1u32)..end.clone());
+    let mut rng = rng();
+    let mut below = 0;
+    for _ in 0..3000 {
+        let v = stream.next(&mut rng);
+        assert!(v >= BigUint::from(1u32) && v < end);
+        below += (v < half) as u64;
+    }
+    let result = stats::chi_squared(&[below, 3000 - below], &[2000.0, 1000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn singleton() {
+    let v = BigUint::from(1u32) << 100usize;
+    let stream = BigUint::rand(v.clone()..&v + 1u32);
+    let mut rng = rng();
+    assert!((0..10).all(|_| stream.next(&mut rng) == v));
+}
+
+#[test]
+#[should_panic]
+fn empty_range() {
+    BigUint::rand(BigUint::from(5u32)..BigUint::from(5u32));
+}
+
+#[test]
+fn complex_rectangle() {
+    let stream = Complex::rand(Complex::new(-1.0, 2.0)..Complex::new(3.0, 2.5));
+    let mut rng = rng();
+    let result = stats::uniformity(16, 16_000, || {
+        let z = stream.next(&mut rng);
+        assert!((-1.0..3.0).contains(&z.re) && (2.0..2.5).contains(&z.im), "{}", z);
+        (z.re + 1.0) as usize * 4 + ((z.im - 2.0) * 8.0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn complex_disc() {
+    let center = Complex::new(1.0, -1.0);
+    let stream = Complex::rand(Disc { center, radius: 2.0 });
+    let mut rng = rng();
+    // Equal-area rings and quadrants.
+    let result = stats::uniformity(16, 16_000, || {
+        let d = stream.next(&mut rng) - center;
+        assert!(d.norm() < 2.0, "{}", d);
+        // `|d|^2` is uniform in `[0, 4)`.
+        let ring = d.norm_sqr() as usize;
+        let quadrant = (d.re < 0.0) as usize * 2 + (d.im < 0.0) as usize;
+        ring * 4 + quadrant
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn complex_normal() {
+    let stream = Complex::rand(StandardComplexNormal);
+    let mut rng = rng();
+    let n = 100_000;
+    let values = (0..n).map(|_| stream.next(&mut rng)).collect::<Vec<_>>();
+    let mean = values.iter().sum::<Complex<f64>>() / n as f64;
+    assert!(mean.norm() < 0.01, "{}", mean);
+    let re2 = values.iter().map(|z| z.re * z.re).sum::<f64>() / n as f64;
+    let im2 = values.iter().map(|z| z.im * z.im).sum::<f64>() / n as f64;
+    let cross = values.iter().map(|z| z.re * z.im).sum::<f64>() / n as f64;
+    assert!((re2 - 0.5).abs() < 0.01 && (im2 - 0.5).abs() < 0.01, "{} {}", re2, im2);
+    assert!(cross.abs() < 0.01, "{}", cross);
+    // Normal parts have kurtosis 3, i.e. `E[re^4] = 3/4`.
+    let re4 = values.iter().map(|z| z.re.powi(4)).sum::<f64>() / n as f64;
+    assert!((re4 - 0.75).abs() < 0.05, "{}", re4);
+}
+
+#[test]
+#[should_panic(expected = "radius must be positive and finite")]
+fn complex_disc_zero_radius() {
+    Complex::rand(Disc { center: Complex::new(0.0, 0.0), radius: 0.0 });
+}
+
+#[test]
+fn unitary() {
+    let mut rng = rng();
+    for n in 0..6 {
+        let u = random_unitary(n, &mut rng);
+        assert_eq!(u.len(), n);
+        for (i, a) in u.iter().enumerate() {
+            for (j, b) in u.iter().enumerate() {
+                let dot = a.iter().zip(b).map(|(x, y)| x.conj() * y).sum::<Complex<f64>>();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).norm() < 1e-14, "{} {} {}: {}", n, i, j, dot);
+            }
+        }
+    }
+    // The trace of a Haar unitary matrix has mean 0 and `E[|tr|^2] =
+    // 1`.
+    let traces = (0..20_000).map(|_| {
+        let u = random_unitary(4, &mut rng);
+        (0..4).map(|i| u[i][i]).sum::<Complex<f64>>()
+    }).collect::<Vec<_>>();
+    let mean = traces.iter().sum::<Complex<f64>>() / 20_000.0;
+    assert!(mean.norm() < 0.02, "{}", mean);
+    let square = traces.iter().map(|t| t.norm_sqr()).sum::<f64>() / 20_000.0;
+    assert!((square - 1.0).abs() < 0.03, "{}", square);
+}
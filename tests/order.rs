@@ -0,0 +1,53 @@
+//! Order statistics of uniforms, checked against their moments and
+//! distribution.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::OrderStatistic;
+use rand_sketch::stream::{gen_iter, Rand, RandStream};
+use rand_sketch::testing::{stats, RunningStats};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn moments() {
+    for &(n, k) in &[(1, 1), (2, 1), (10, 3), (1000, 1000), (1_000_000, 500_000)] {
+        let stats = gen_iter::<f64, _, _>(rng(), OrderStatistic { n, k })
+            .take(100_000)
+            .collect::<RunningStats>();
+        assert!(stats.min() >= 0.0 && stats.max() <= 1.0);
+        // The mean and variance of `Beta(k, n - k + 1)`.
+        let (n, k) = (n as f64, k as f64);
+        let mean = k / (n + 1.0);
+        let sd = (k * (n - k + 1.0) / ((n + 1.0) * (n + 1.0) * (n + 2.0))).sqrt();
+        assert!((stats.mean() - mean).abs() < sd / 50.0, "{} {}: mean {} not {}", n, k, stats.mean(), mean);
+        assert!((stats.std_dev() / sd - 1.0).abs() < 0.02, "{} {}: sd {} not {}", n, k, stats.std_dev(), sd);
+    }
+}
+
+#[test]
+fn distribution() {
+    // The second smallest of five, bucketed by its CDF, `P(x <= t) =
+    // 1 - (1 - t)^5 - 5t(1 - t)^4`, is uniform.
+    let cdf = |t: f64| 1.0 - (1.0 - t).powi(5) - 5.0 * t * (1.0 - t).powi(4);
+    let stream = f64::rand(OrderStatistic { n: 5, k: 2 });
+    let mut rng = rng();
+    let result = stats::uniformity(20, 1000, || (cdf(stream.next(&mut rng)) * 20.0) as usize);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+#[should_panic(expected = "k must be")]
+fn k_too_large() {
+    f64::rand(OrderStatistic { n: 3, k: 4 });
+}
+
+#[test]
+#[should_panic(expected = "k must be")]
+fn k_zero() {
+    f64::rand(OrderStatistic { n: 3, k: 0 });
+}
@@ -0,0 +1,75 @@
+//! Piecewise constant and linear densities.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{PiecewiseConstant, PiecewiseLinear};
+use rand_sketch::stream::{Rand, RandStream};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn constant() {
+    // Bins `[0, 1)`, `[1, 10)` and `[10, 12)`, weighted 3:0:1, and
+    // uniform within each: so `[0, 0.5)`, `[0.5, 1)` and `[10, 12)`
+    // have weights 1.5:1.5:1.
+    let stream = f64::rand(PiecewiseConstant {
+        bin_edges: vec![0.0, 1.0, 10.0, 12.0],
+        weights: vec![3.0, 0.0, 1.0],
+    });
+    let mut rng = rng();
+    let mut counts = [0; 3];
+    for _ in 0..8000 {
+        let x = stream.next(&mut rng);
+        assert!((0.0..1.0).contains(&x) || (10.0..12.0).contains(&x), "{}", x);
+        counts[if x < 0.5 { 0 } else if x < 1.0 { 1 } else { 2 }] += 1;
+    }
+    let result = stats::chi_squared(&counts, &[3000.0, 3000.0, 2000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn linear_triangle() {
+    // A triangle on `[0, 3)` peaking at 1: the CDF is `x^2 / 3` up to
+    // 1, and `1 - (3 - x)^2 / 6` after. Buckets of equal probability
+    // (a sixth each) end at these points.
+    let stream = f64::rand(PiecewiseLinear {
+        points: vec![0.0, 1.0, 3.0],
+        densities: vec![0.0, 5.0, 0.0],
+    });
+    let ends = [0.5f64.sqrt(), 1.0, 3.0 - 3f64.sqrt(), 3.0 - 2f64.sqrt(), 2.0, 3.0];
+    let mut rng = rng();
+    let result = stats::uniformity(6, 1000, || {
+        let x = stream.next(&mut rng);
+        assert!((0.0..3.0).contains(&x), "{}", x);
+        ends.iter().position(|&end| x < end).unwrap()
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn linear_flat_is_uniform() {
+    let stream = f64::rand(PiecewiseLinear {
+        points: vec![-1.0, 0.0, 1.0],
+        densities: vec![2.0, 2.0, 2.0],
+    });
+    let mut rng = rng();
+    let result = stats::uniformity(10, 1000, || ((stream.next(&mut rng) + 1.0) * 5.0) as usize);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+#[should_panic]
+fn edges_decreasing() {
+    f64::rand(PiecewiseConstant { bin_edges: vec![0.0, 2.0, 1.0], weights: vec![1.0, 1.0] });
+}
+
+#[test]
+#[should_panic]
+fn wrong_number_of_densities() {
+    f64::rand(PiecewiseLinear { points: vec![0.0, 1.0], densities: vec![1.0] });
+}
@@ -0,0 +1,48 @@
+//! Values shared between threads from one generator.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+use rand_sketch::pool::Pool;
+use std::collections::HashSet;
+use std::thread;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn in_order_on_one_thread() {
+    // One handle at a time claims consecutive chunks, across blocks.
+    let pool = Pool::new(rng());
+    let mut expected = rng();
+    let mut handle = pool.rng();
+    for _ in 0..10_000 {
+        assert_eq!(handle.next_u64(), expected.next_u64());
+    }
+    // A new handle starts on a fresh chunk, skipping the rest of the
+    // last one.
+    let mut fresh = pool.rng();
+    let skipped = (10_000 / 32 + 1) * 32 - 10_000;
+    for _ in 0..skipped {
+        expected.next_u64();
+    }
+    assert_eq!(fresh.next_u64(), expected.next_u64());
+}
+
+#[test]
+fn never_shared() {
+    let pool = Pool::new(rng());
+    let values = thread::scope(|s| {
+        let threads = (0..8).map(|_| s.spawn(|| {
+            let mut rng = pool.rng();
+            (0..20_000).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        })).collect::<Vec<_>>();
+        threads.into_iter().flat_map(|t| t.join().unwrap()).collect::<Vec<_>>()
+    });
+    // 160,000 distinct 64-bit values: a repeat means a value was handed
+    // out twice (a collision by chance is vanishingly unlikely).
+    let distinct = values.iter().collect::<HashSet<_>>();
+    assert_eq!(distinct.len(), values.len());
+}
@@ -0,0 +1,36 @@
+//! Everything needed for everyday use comes from the prelude.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::XorShiftRng;
+use rand_sketch::prelude::*;
+
+#[test]
+fn functions() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let x: u32 = gen(&mut rng, 4..321);
+    assert!((4..321).contains(&x));
+    assert!(gen_iter::<f64, _, _>(rng, Open01).take(100).all(|y| 0.0 < y && y < 1.0));
+}
+
+#[test]
+fn methods() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let x: u32 = rng.random(4..321);
+    assert!((4..321).contains(&x));
+    assert!(rng.random_iter::<f32, _>(Closed01).take(100).all(|y| (0.0..=1.0).contains(&y)));
+
+    // The same values as the functions, and `Rng`'s own methods still
+    // work alongside.
+    let mut other = XorShiftRng::new_unseeded();
+    assert_eq!(gen::<u32, _, _>(&mut other, 4..321), x);
+    let _: u64 = rng.gen();
+}
+
+#[test]
+fn streams() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let stream = u8::rand(..=9);
+    assert!((0..100).all(|_| stream.next(&mut rng) <= 9));
+}
@@ -0,0 +1,54 @@
+//! Event times from Poisson processes.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::process::{non_homogeneous_poisson, poisson};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn homogeneous() {
+    let times = poisson(4.0, rng()).take(40_000).collect::<Vec<_>>();
+    assert!(times[0] > 0.0);
+    assert!(times.windows(2).all(|w| w[0] < w[1]));
+
+    // 40,000 events at rate 4 take about 10,000 units of time, and the
+    // number in each unit is Poisson with mean 4.
+    let mean_gap = times[times.len() - 1] / times.len() as f64;
+    assert!((mean_gap - 0.25).abs() < 0.005, "{}", mean_gap);
+    let mut counts = vec![0; 5];
+    for &t in times.iter().take_while(|&&t| t < 5000.0) {
+        counts[(t / 1000.0) as usize] += 1;
+    }
+    let result = stats::chi_squared_uniform(&counts);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn non_homogeneous() {
+    // Events only in the second half of each unit interval, at rate 8.
+    let rate = |t: f64| if t.fract() < 0.5 { 0.0 } else { 8.0 };
+    let times = non_homogeneous_poisson(rate, 8.0, rng()).take(10_000).collect::<Vec<_>>();
+    assert!(times.windows(2).all(|w| w[0] < w[1]));
+    assert!(times.iter().all(|t| t.fract() >= 0.5));
+    // That's 4 events per unit time on average.
+    let mean_gap = times[times.len() - 1] / times.len() as f64;
+    assert!((mean_gap - 0.25).abs() < 0.01, "{}", mean_gap);
+}
+
+#[test]
+#[should_panic(expected = "rate must be positive and finite")]
+fn zero_rate() {
+    poisson(0.0, rng());
+}
+
+#[test]
+#[should_panic(expected = "rate 2 at time")]
+fn exceeds_max_rate() {
+    non_homogeneous_poisson(|_| 2.0, 1.0, rng()).next();
+}
@@ -0,0 +1,45 @@
+//! Distributions from quantile functions.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::Distribution;
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::RunningStats;
+use std::f64::consts::PI;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn exponential() {
+    let rate = 4.0;
+    let stats = gen_iter::<f64, _, _>(rng(), Distribution::from_quantile(|u: f64| -(-u).ln_1p() / rate))
+        .take(100_000)
+        .collect::<RunningStats>();
+    assert!(stats.min() > 0.0 && stats.max().is_finite());
+    assert!((stats.mean() - 0.25).abs() < 0.005, "{}", stats.mean());
+    assert!((stats.std_dev() - 0.25).abs() < 0.005, "{}", stats.std_dev());
+}
+
+#[test]
+fn cauchy_median() {
+    // Unbounded both ways, but `u` is never 0 or 1, so always finite.
+    let values = gen_iter::<f64, _, _>(rng(), Distribution::from_quantile(|u: f64| (PI * (u - 0.5)).tan()))
+        .take(10_001)
+        .collect::<Vec<_>>();
+    assert!(values.iter().all(|x| x.is_finite()));
+    let below = values.iter().filter(|&&x| x < 1.0).count() as f64 / values.len() as f64;
+    // `P(x < 1) = 3/4`.
+    assert!((below - 0.75).abs() < 0.02, "{}", below);
+}
+
+#[test]
+fn open_interval() {
+    let stats = gen_iter::<f64, _, _>(rng(), Distribution::from_quantile(|u| u))
+        .take(100_000)
+        .collect::<RunningStats>();
+    assert!(stats.min() > 0.0 && stats.max() < 1.0);
+}
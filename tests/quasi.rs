@@ -0,0 +1,98 @@
+//! Low-discrepancy sequences.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::XorShiftRng;
+use rand_sketch::quasi::{Halton, Sobol};
+use rand_sketch::stream::RandStream;
+
+#[test]
+fn halton_points() {
+    let halton = Halton::new(2);
+    let points = (0..6).map(|i| halton.point(i)).collect::<Vec<_>>();
+    assert_eq!(points, [[0.0, 0.0], [0.5, 1.0 / 3.0], [0.25, 2.0 / 3.0],
+                        [0.75, 1.0 / 9.0], [0.125, 4.0 / 9.0], [0.625, 7.0 / 9.0]]);
+    assert_eq!(Halton::new(5).coordinate(1, 4), 1.0 / 11.0);
+}
+
+#[test]
+fn sobol_points() {
+    // From Joe and Kuo's reference implementation.
+    let sobol = Sobol::new(2);
+    let points = (0..8).map(|i| sobol.point(i)).collect::<Vec<_>>();
+    assert_eq!(points, [[0.0, 0.0], [0.5, 0.5], [0.75, 0.25], [0.25, 0.75],
+                        [0.375, 0.375], [0.875, 0.875], [0.625, 0.125], [0.125, 0.625]]);
+}
+
+#[test]
+fn sobol_stratified() {
+    // The first `2^k` points of each dimension hit each interval of
+    // width `2^-k` exactly once.
+    let sobol = Sobol::new(Sobol::MAX_DIMS);
+    for dim in 0..sobol.dims() {
+        let mut hits = [0; 64];
+        for i in 0..64 {
+            hits[(sobol.coordinate(i, dim) * 64.0) as usize] += 1;
+        }
+        assert!(hits.iter().all(|&h| h == 1), "dimension {}", dim);
+    }
+}
+
+#[test]
+fn streams_flatten_points() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let halton = Halton::new(3);
+    let values = (0..9).map(|_| halton.next(&mut rng)).collect::<Vec<_>>();
+    assert_eq!(&values[6..], &halton.point(2)[..]);
+    let sobol = Sobol::new(2);
+    let values = (0..8).map(|_| sobol.next(&mut rng)).collect::<Vec<_>>();
+    assert_eq!(&values[6..], &sobol.point(3)[..]);
+}
+
+#[test]
+#[should_panic(expected = "at most 16 dimensions are supported")]
+fn sobol_too_many_dims() {
+    Sobol::new(17);
+}
+
+#[test]
+fn stratified() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let values = rand_sketch::quasi::stratified(10, &mut rng);
+    for (i, &x) in values.iter().enumerate() {
+        assert!(i as f64 / 10.0 <= x && x < (i + 1) as f64 / 10.0, "{} in stratum {}", x, i);
+    }
+}
+
+#[test]
+fn latin_hypercube() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let (dims, n) = (3, 50);
+    let points = rand_sketch::quasi::latin_hypercube(dims, n, &mut rng);
+    assert_eq!(points.len(), n);
+    for dim in 0..dims {
+        let mut hits = vec![0; n];
+        for point in &points {
+            assert_eq!(point.len(), dims);
+            hits[(point[dim] * n as f64) as usize] += 1;
+        }
+        assert!(hits.iter().all(|&h| h == 1), "dimension {}", dim);
+    }
+    // The pairing between dimensions is random, not the diagonal.
+    assert!(points.iter().any(|p| (p[0] * n as f64) as usize != (p[1] * n as f64) as usize));
+}
+
+#[test]
+fn latin_hypercube_pairings_uniform() {
+    // With two strata in two dimensions, the point in the first
+    // stratum of dimension 0 is equally likely to be in either stratum
+    // of dimension 1.
+    let mut rng = XorShiftRng::new_unseeded();
+    let result = rand_sketch::testing::stats::uniformity(2, 10_000, || {
+        let points = rand_sketch::quasi::latin_hypercube(2, 2, &mut rng);
+        let first = points.iter().find(|p| p[0] < 0.5).unwrap();
+        (first[1] * 2.0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
@@ -0,0 +1,69 @@
+//! The `randgen` binary, end to end.
+
+#![cfg(feature = "std")]
+
+use std::process::{Command, Output};
+
+fn randgen(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_randgen")).args(args).output().unwrap()
+}
+
+fn lines(output: &Output) -> Vec<String> {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap().lines().map(String::from).collect()
+}
+
+#[test]
+fn range_and_count() {
+    let values = lines(&randgen(&["u32", "4..321", "--count", "1000", "--seed", "abc"]));
+    assert_eq!(values.len(), 1000);
+    for v in &values {
+        let v: u32 = v.parse().unwrap();
+        assert!((4..321).contains(&v), "{}", v);
+    }
+}
+
+#[test]
+fn seeds_reproduce() {
+    let a = lines(&randgen(&["f64", "--seed", "abc"]));
+    assert_eq!(a, lines(&randgen(&["f64", "..", "--seed", "abc", "--count", "10"])));
+    assert!(a != lines(&randgen(&["f64", "--seed", "abd"])));
+    for v in &a {
+        let v: f64 = v.parse().unwrap();
+        assert!((0.0..1.0).contains(&v), "{}", v);
+    }
+}
+
+#[test]
+fn constraints() {
+    for v in lines(&randgen(&["i64", "-3..", "--count", "100"])) {
+        assert!(v.parse::<i64>().unwrap() >= -3, "{}", v);
+    }
+    for v in lines(&randgen(&["u8", "..=3", "--count", "100"])) {
+        assert!(v.parse::<u8>().unwrap() <= 3, "{}", v);
+    }
+    for v in lines(&randgen(&["f32", "..2.5", "--count", "100"])) {
+        let v: f32 = v.parse().unwrap();
+        assert!((0.0..2.5).contains(&v), "{}", v);
+    }
+}
+
+#[test]
+fn errors() {
+    for args in &[&["u16"][..], &["u32", "5..5"], &["f64", "1.."], &["u32", "--count"],
+                  &["u32", "4..x"], &["u32", "..", "extra"], &["--seed", "abc"]] {
+        let output = randgen(args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("usage: randgen"));
+    }
+}
+
+#[test]
+fn bytes() {
+    let output = randgen(&["--bytes", "1000", "--seed", "abc"]);
+    assert!(output.status.success());
+    assert_eq!(output.stdout.len(), 1000);
+    assert_eq!(output.stdout, randgen(&["--seed", "abc", "--bytes", "1000"]).stdout);
+    assert_eq!(randgen(&["--bytes", "10", "u32"]).status.code(), Some(2));
+}
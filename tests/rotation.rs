@@ -0,0 +1,91 @@
+//! Uniformly random rotations, and orthogonal matrices.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::rotation::{random_orthogonal, Angle, UnitQuaternion};
+use rand_sketch::stream::{gen, Rand, RandStream};
+use rand_sketch::testing::{stats, RunningStats};
+use std::f64::consts::TAU;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn angles() {
+    let mut rng = rng();
+    let result = stats::uniformity(36, 36_000, || {
+        let angle = gen::<f64, _, _>(&mut rng, Angle);
+        assert!((0.0..TAU).contains(&angle), "{}", angle);
+        (angle / TAU * 36.0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn quaternions() {
+    let mut rng = rng();
+    let stream = <[f64; 4]>::rand(UnitQuaternion);
+    let n = 100_000;
+    let (mut sums, mut squares, mut fourths) = ([0.0; 4], [0.0; 4], [0.0; 4]);
+    for _ in 0..n {
+        let q = stream.next(&mut rng);
+        let norm = q.iter().map(|c| c * c).sum::<f64>();
+        assert!((norm - 1.0).abs() < 1e-12, "{:?}", q);
+        for (i, &c) in q.iter().enumerate() {
+            sums[i] += c;
+            squares[i] += c * c;
+            fourths[i] += c.powi(4);
+        }
+    }
+    // On the uniform 3-sphere, each coordinate has mean 0, `E[c^2] =
+    // 1/4` and `E[c^4] = 1/8`, where uniform Euler angles are biased.
+    for i in 0..4 {
+        let (mean, square, fourth) = (sums[i] / n as f64, squares[i] / n as f64, fourths[i] / n as f64);
+        assert!(mean.abs() < 0.01, "{}: {}", i, mean);
+        assert!((square - 0.25).abs() < 0.005, "{}: {}", i, square);
+        assert!((fourth - 0.125).abs() < 0.005, "{}: {}", i, fourth);
+    }
+}
+
+#[test]
+fn orthogonal() {
+    let mut rng = rng();
+    for n in 0..6 {
+        let q = random_orthogonal(n, &mut rng);
+        assert_eq!(q.len(), n);
+        for (i, a) in q.iter().enumerate() {
+            for (j, b) in q.iter().enumerate() {
+                let dot = a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-14, "{} {} {}: {}", n, i, j, dot);
+            }
+        }
+    }
+}
+
+#[test]
+fn orthogonal_haar() {
+    // Each entry of a Haar orthogonal 3 by 3 matrix is uniform in
+    // `[-1, 1]` (a coordinate of a uniform point on the sphere), and
+    // the trace has mean 0 and variance 1, with rotations and
+    // reflections equally likely.
+    let mut rng = rng();
+    let mut traces = RunningStats::new();
+    let mut rotations = 0;
+    let result = stats::uniformity(20, 20_000, || {
+        let q = random_orthogonal(3, &mut rng);
+        traces.push(q[0][0] + q[1][1] + q[2][2]);
+        let det = q[0][0] * (q[1][1] * q[2][2] - q[1][2] * q[2][1])
+            - q[0][1] * (q[1][0] * q[2][2] - q[1][2] * q[2][0])
+            + q[0][2] * (q[1][0] * q[2][1] - q[1][1] * q[2][0]);
+        rotations += (det > 0.0) as u32;
+        ((q[1][2] + 1.0) * 10.0) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+    assert!(traces.mean().abs() < 0.02, "{}", traces.mean());
+    assert!((traces.variance() - 1.0).abs() < 0.03, "{}", traces.variance());
+    assert!((f64::from(rotations) / 20_000.0 - 0.5).abs() < 0.01, "{}", rotations);
+}
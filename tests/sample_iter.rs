@@ -0,0 +1,47 @@
+//! Iterating over a borrowed stream, and in blocks.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen_chunks, gen_iter, Rand, RandStream};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn bursts_continue_the_sequence() {
+    let stream = u32::rand(4..321);
+    let mut rng = rng();
+    let mut values = vec![];
+    for burst in 1..10 {
+        values.extend(stream.sample_iter(&mut rng).take(burst));
+    }
+    let expected = gen_iter::<u32, _, _>(self::rng(), 4..321).take(values.len()).collect::<Vec<_>>();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn shared_stream() {
+    // The stream is only borrowed, so several generators can share it.
+    let stream = f64::rand(-1.0..1.0);
+    let (mut a, mut b) = (rng(), XorShiftRng::from_seed([5, 6, 7, 8]));
+    for (x, y) in stream.sample_iter(&mut a).zip(stream.sample_iter(&mut b)).take(100) {
+        assert!((-1.0..1.0).contains(&x) && (-1.0..1.0).contains(&y));
+    }
+}
+
+#[test]
+fn chunks_continue_the_sequence() {
+    let chunks = gen_chunks::<u32, 8, _, _>(rng(), 4..321).take(10).collect::<Vec<_>>();
+    let values = chunks.iter().flat_map(|chunk| chunk.iter().cloned()).collect::<Vec<_>>();
+    let expected = gen_iter::<u32, _, _>(rng(), 4..321).take(80).collect::<Vec<_>>();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn empty_chunks() {
+    let chunk: [f64; 0] = gen_chunks::<f64, 0, _, _>(rng(), ..).next().unwrap();
+    assert!(chunk.is_empty());
+}
@@ -0,0 +1,61 @@
+//! Sampling events at a fixed probability or a target rate.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::sampler::Sampler;
+use std::time::{Duration, Instant};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn fixed() {
+    let mut rng = rng();
+    let sampler = Sampler::fixed(0.1);
+    assert_eq!(sampler.probability(), 0.1);
+    let kept = (0..100_000).filter(|_| sampler.should_sample(&mut rng)).count();
+    // `10,000 ± 95`.
+    assert!((kept as f64 - 10_000.0).abs() < 400.0, "{}", kept);
+    assert!((0..1000).all(|_| Sampler::fixed(1.0).should_sample(&mut rng)));
+    assert!(!(0..1000).any(|_| Sampler::fixed(0.0).should_sample(&mut rng)));
+}
+
+#[test]
+fn target_rate() {
+    let mut rng = rng();
+    let sampler = Sampler::target_rate(50.0, Duration::from_secs(1));
+    let start = Instant::now();
+    // 10,000 events a second for ten seconds.
+    let mut kept = [0; 10];
+    for i in 0..100_000 {
+        let now = start + Duration::from_micros(100 * i);
+        if sampler.should_sample_at(now, &mut rng) {
+            kept[i as usize / 10_000] += 1;
+        }
+    }
+    // Everything in the first window, and then about 50 a second.
+    assert!(kept[0] > 9_900, "{:?}", kept);
+    let rest = kept[1..].iter().sum::<u32>();
+    assert!((rest as f64 - 450.0).abs() < 90.0, "{:?}", kept);
+    assert!((sampler.probability() - 0.005).abs() < 0.0005, "{}", sampler.probability());
+}
+
+#[test]
+fn target_rate_quiet() {
+    // With fewer events than the target, everything is kept.
+    let mut rng = rng();
+    let sampler = Sampler::target_rate(50.0, Duration::from_millis(100));
+    let start = Instant::now();
+    for i in 0..1000 {
+        assert!(sampler.should_sample_at(start + Duration::from_millis(20 * i), &mut rng));
+    }
+}
+
+#[test]
+#[should_panic(expected = "probability")]
+fn fixed_invalid() {
+    Sampler::fixed(-0.5);
+}
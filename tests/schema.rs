@@ -0,0 +1,68 @@
+//! Rows of tabular data from a schema.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::schema::{Column, Schema, Value};
+use rand_sketch::stream::Bernoulli;
+use rand_sketch::text::{Charset, Text};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+fn users() -> Schema {
+    Schema::new(vec![
+        Column::new::<u64, _>("id", ..),
+        Column::new::<String, _>("name", Text(Charset::new("abcdefghijklmnopqrstuvwxyz"), 3..10)),
+        Column::nullable::<u8, _>("age", 18..90, 0.25),
+        Column::nullable::<f64, _>("score", 0.0..1.0, 1.0),
+        Column::new::<bool, _>("active", Bernoulli(0.9)),
+    ])
+}
+
+#[test]
+fn rows() {
+    let schema = users();
+    assert_eq!(schema.index("age"), Some(2));
+    assert_eq!(schema.index("email"), None);
+    assert_eq!(schema.columns()[2].null_probability(), 0.25);
+
+    let mut null_ages = 0;
+    for row in schema.rows(rng()).take(10_000) {
+        assert_eq!(row.len(), 5);
+        assert!(matches!(row[0], Value::UInt(_)));
+        match row[1] {
+            Value::Text(ref s) => {
+                assert!((3..10).contains(&s.chars().count()));
+                assert!(s.chars().all(|c| c.is_ascii_lowercase()));
+            }
+            ref v => panic!("{:?}", v),
+        }
+        match row[2] {
+            Value::Null => null_ages += 1,
+            Value::UInt(age) => assert!((18..90).contains(&age)),
+            ref v => panic!("{:?}", v),
+        }
+        assert_eq!(row[3], Value::Null);
+        assert!(matches!(row[4], Value::Bool(_)));
+    }
+    assert!((2300..2700).contains(&null_ages), "{}", null_ages);
+}
+
+#[test]
+fn deterministic() {
+    let a = users().rows(rng()).take(10).collect::<Vec<_>>();
+    let b = users().rows(rng()).take(10).collect::<Vec<_>>();
+    assert_eq!(a, b);
+}
+
+#[test]
+#[should_panic(expected = "duplicate column name")]
+fn duplicate_names() {
+    Schema::new(vec![
+        Column::new::<u32, _>("id", ..),
+        Column::new::<i64, _>("id", -5..5),
+    ]);
+}
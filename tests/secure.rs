@@ -0,0 +1,78 @@
+//! Constant-time sampling.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{ChaChaRng, Rng, SeedableRng};
+use rand_sketch::secure::{key, token, uniform_ct, CryptoRng};
+use rand_sketch::testing::stats;
+
+fn rng() -> ChaChaRng {
+    ChaChaRng::from_seed(&[1, 2, 3, 4])
+}
+
+/// A generator counting its draws, returning `bits` for each. It's
+/// anything but secure, but claims to be, to be accepted.
+struct CountingRng {
+    bits: u64,
+    draws: usize,
+}
+
+impl Rng for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 1;
+        self.bits
+    }
+}
+impl CryptoRng for CountingRng {}
+
+#[test]
+fn uniform() {
+    let mut rng = rng();
+    let result = stats::uniformity(17, 17_000, || {
+        let v = uniform_ct(10..27, &mut rng);
+        assert!((10..27).contains(&v), "{}", v);
+        (v - 10) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn fixed_draws() {
+    // Whatever the draws (including the extremes), there are exactly
+    // two, and the value is in range.
+    for &bits in &[0, 1, 1 << 63, !0] {
+        for range in [0..1, 5..6, 0..3, 0..u64::MAX, 1 << 63..u64::MAX] {
+            let mut rng = CountingRng { bits, draws: 0 };
+            let v = uniform_ct(range.clone(), &mut rng);
+            assert!(range.contains(&v), "{} not in {:?}", v, range);
+            assert_eq!(rng.draws, 2);
+        }
+    }
+    let mut rng = CountingRng { bits: !0, draws: 0 };
+    assert_eq!(uniform_ct(0..u64::MAX, &mut rng), u64::MAX - 1);
+}
+
+#[test]
+#[should_panic(expected = "range must be non-empty")]
+fn empty() {
+    uniform_ct(3..3, &mut rng());
+}
+
+#[test]
+fn tokens_and_keys() {
+    let mut rng = rng();
+    let t = token(16, &mut rng);
+    assert_eq!(t.len(), 32);
+    assert!(t.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()), "{}", t);
+    assert_ne!(t, token(16, &mut rng));
+    assert_eq!(token(0, &mut rng), "");
+
+    let (a, b) = (key::<32, _>(&mut rng), key::<32, _>(&mut rng));
+    assert_ne!(a, b);
+    assert!(a.iter().any(|&x| x != 0));
+}
@@ -0,0 +1,358 @@
+//! Random selections from slices and iterators.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::seq;
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn bernoulli_certainties() {
+    let values = (0..100).collect::<Vec<u32>>();
+    assert!(seq::sample_bernoulli(&values, 0.0, &mut rng()).is_empty());
+    let all = seq::sample_bernoulli(&values, 1.0, &mut rng());
+    assert_eq!(all.into_iter().cloned().collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn bernoulli_in_order() {
+    let values = (0..1000).collect::<Vec<u32>>();
+    let kept = seq::sample_bernoulli(&values, 0.1, &mut rng());
+    assert!(kept.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn bernoulli_each_equally_likely() {
+    let (n, trials, p) = (20, 10_000, 0.3);
+    let mut rng = rng();
+    let mut counts = vec![0; n];
+    for _ in 0..trials {
+        for i in seq::bernoulli_iter(0..n, p, &mut rng) {
+            counts[i] += 1;
+        }
+    }
+    let result = stats::chi_squared_uniform(&counts);
+    assert!(result.passes(0.001), "{:?}", result);
+
+    // The number kept is binomial, so within five standard deviations
+    // of the mean.
+    let total = (n * trials) as f64;
+    let kept = counts.iter().sum::<u64>() as f64;
+    assert!((kept - total * p).abs() < 5.0 * (total * p * (1.0 - p)).sqrt(), "{}", kept);
+}
+
+#[test]
+fn bernoulli_tiny_probability() {
+    // Skips much longer than the slice just end it.
+    let values = [0; 10];
+    let kept = (0..1000).map(|_| seq::sample_bernoulli(&values, 1e-300, &mut rng()).len());
+    assert_eq!(kept.sum::<usize>(), 0);
+}
+
+#[test]
+#[should_panic(expected = "probability must be in [0, 1]")]
+fn bernoulli_invalid() {
+    seq::sample_bernoulli(&[1, 2, 3], -0.5, &mut rng());
+}
+
+#[test]
+fn partial_shuffle_splits() {
+    let mut values = (0..10).collect::<Vec<u32>>();
+    {
+        let (chosen, rest) = seq::partial_shuffle(&mut values, 3, &mut rng());
+        assert_eq!((chosen.len(), rest.len()), (3, 7));
+    }
+    // Still a permutation.
+    values.sort();
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+
+    let mut short = [1, 2];
+    assert_eq!(seq::partial_shuffle(&mut short, 5, &mut rng()).0.len(), 2);
+    assert!(seq::partial_shuffle(&mut [0u8; 0], 1, &mut rng()).0.is_empty());
+}
+
+#[test]
+fn partial_shuffle_uniform() {
+    // Every ordered pair of distinct elements of `0..5` is equally
+    // likely to be chosen.
+    let mut rng = rng();
+    let result = stats::uniformity(20, 20_000, || {
+        let mut values = [0, 1, 2, 3, 4];
+        let (chosen, _) = seq::partial_shuffle(&mut values, 2, &mut rng);
+        let (a, b) = (chosen[0], chosen[1]);
+        a * 4 + if b > a { b - 1 } else { b }
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn index_sample_distinct() {
+    let mut rng = rng();
+    // Each algorithm: Floyd's, the partial shuffle, and rejection.
+    for &(n, k) in &[(10, 0), (10, 10), (1000, 5), (40, 30), (100_000, 50)] {
+        let indices = seq::index::sample(n, k, &mut rng);
+        assert_eq!(indices.len(), k);
+        let mut sorted = indices.clone().into_vec();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), k, "{} from {}", k, n);
+        assert!(indices.iter().all(|i| i < n));
+    }
+}
+
+/// Check that the ordered pair of the `i`th and `j`th of `k` indices
+/// from `0..n` is uniform.
+fn check_index_pairs(n: usize, k: usize, i: usize, j: usize) {
+    let mut rng = rng();
+    let result = stats::uniformity(n * (n - 1), 20 * n * (n - 1), || {
+        let indices = seq::index::sample(n, k, &mut rng);
+        let (a, b) = (indices.index(i), indices.index(j));
+        a * (n - 1) + if b > a { b - 1 } else { b }
+    });
+    assert!(result.passes(0.001), "{} from {}: {:?}", k, n, result);
+}
+
+#[test]
+fn index_sample_uniform() {
+    // Floyd's algorithm, whose order needs care.
+    check_index_pairs(6, 2, 0, 1);
+    check_index_pairs(6, 6, 4, 5);
+    check_index_pairs(20, 3, 2, 0);
+    // The partial shuffle.
+    check_index_pairs(20, 17, 0, 16);
+    // Rejection.
+    check_index_pairs(70, 17, 16, 3);
+}
+
+#[test]
+fn choose_multiple() {
+    let values = (0..100).collect::<Vec<u32>>();
+    let chosen = seq::choose_multiple(&values, 10, &mut rng());
+    assert_eq!(chosen.len(), 10);
+    assert_eq!(seq::choose_multiple(&values[..3], 10, &mut rng()).len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "cannot sample 5 indices from 4")]
+fn index_sample_too_many() {
+    seq::index::sample(4, 5, &mut rng());
+}
+
+#[test]
+fn unique_in_range_all() {
+    let values = seq::index::unique_in_range(10..110, rng());
+    assert_eq!(values.len(), 100);
+    let mut values = values.collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, (10..110).collect::<Vec<_>>());
+    assert_eq!(seq::index::unique_in_range(5..5, rng()).count(), 0);
+}
+
+#[test]
+fn unique_in_range_uniform() {
+    // Each of the 24 orders of `0..4` is equally likely.
+    let mut rng = rng();
+    let result = stats::uniformity(24, 24 * 200, || {
+        let order = seq::index::unique_in_range(0..4, &mut rng).collect::<Vec<_>>();
+        // The order's index in the factorial number system.
+        (0..4).fold(0, |code, i| {
+            let smaller_after = order[i + 1..].iter().filter(|&&x| x < order[i]).count();
+            code * (4 - i) + smaller_after
+        })
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn unique_in_range_huge() {
+    let mut values = seq::index::unique_in_range(0..usize::MAX, rng()).take(10_000).collect::<Vec<_>>();
+    values.sort();
+    values.dedup();
+    assert_eq!(values.len(), 10_000);
+}
+
+#[test]
+fn latin_square_valid() {
+    let mut rng = rng();
+    for n in 0..8 {
+        let square = seq::random_latin_square(n, &mut rng);
+        assert_eq!(square.len(), n);
+        let all = (0..n).collect::<Vec<_>>();
+        for i in 0..n {
+            let mut row = square[i].clone();
+            let mut column = square.iter().map(|row| row[i]).collect::<Vec<_>>();
+            row.sort();
+            column.sort();
+            assert_eq!((&row, &column), (&all, &all), "{:?}", square);
+        }
+    }
+}
+
+#[test]
+fn latin_square_cells_uniform() {
+    // Each cell is equally likely to hold each symbol.
+    let mut rng = rng();
+    let result = stats::uniformity(5, 2000, || seq::random_latin_square(5, &mut rng)[1][3]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn permutation_matrix_uniform() {
+    // Each of the 3! permutations is equally likely.
+    let mut rng = rng();
+    let result = stats::uniformity(6, 2000, || {
+        let matrix = seq::random_permutation_matrix(3, &mut rng);
+        for row in &matrix {
+            assert_eq!(row.iter().sum::<u8>(), 1);
+        }
+        let column = |i: usize| matrix[i].iter().position(|&x| x == 1).unwrap();
+        let (a, b) = (column(0), column(1));
+        a * 2 + if b > a { b - 1 } else { b }
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn composition_uniform() {
+    // The C(5, 2) = 10 compositions of 6 into 3 parts are equally
+    // likely.
+    let mut rng = rng();
+    let result = stats::uniformity(10, 2000, || {
+        let parts = seq::random_composition(6, 3, &mut rng);
+        assert!(parts.iter().all(|&p| p > 0) && parts.iter().sum::<usize>() == 6, "{:?}", parts);
+        // The two cut points `a < b` in `1..6`, as an index.
+        let (a, b) = (parts[0], parts[0] + parts[1]);
+        (1..a).map(|c| 5 - c).sum::<usize>() + b - a - 1
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn composition_edges() {
+    let mut rng = rng();
+    assert_eq!(seq::random_composition(0, 0, &mut rng), Vec::<usize>::new());
+    assert_eq!(seq::random_composition(4, 1, &mut rng), [4]);
+    assert_eq!(seq::random_composition(4, 4, &mut rng), [1, 1, 1, 1]);
+}
+
+#[test]
+#[should_panic]
+fn composition_too_many_parts() {
+    seq::random_composition(3, 4, &mut rng());
+}
+
+#[test]
+fn set_partition_uniform() {
+    // The B_4 = 15 partitions of `0..4` are equally likely.
+    let mut rng = rng();
+    let mut seen = Vec::new();
+    let result = stats::uniformity(15, 1000, || {
+        let partition = seq::random_set_partition(4, &mut rng);
+        let mut elements = partition.concat();
+        elements.sort();
+        assert_eq!(elements, [0, 1, 2, 3]);
+        assert!(partition.windows(2).all(|w| w[0][0] < w[1][0]), "{:?}", partition);
+        match seen.iter().position(|p| *p == partition) {
+            Some(i) => i,
+            None => {
+                seen.push(partition);
+                seen.len() - 1
+            }
+        }
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn set_partition_large() {
+    let partition = seq::random_set_partition(1000, &mut rng());
+    assert_eq!(partition.iter().map(Vec::len).sum::<usize>(), 1000);
+    // Around `n / ln n` blocks.
+    assert!((100..250).contains(&partition.len()), "{}", partition.len());
+}
+
+#[test]
+fn shuffle_bag_epochs() {
+    let mut bag = seq::shuffle_bag(vec!['a', 'b', 'c', 'd', 'e'], rng());
+    for epoch in 0..100 {
+        assert_eq!(bag.epoch(), epoch);
+        assert_eq!(bag.remaining(), 5);
+        let mut drawn = bag.by_ref().take(5).collect::<Vec<_>>();
+        drawn.sort();
+        assert_eq!(drawn, ['a', 'b', 'c', 'd', 'e']);
+    }
+    assert_eq!(seq::shuffle_bag(Vec::<u8>::new(), rng()).next(), None);
+}
+
+#[test]
+fn shuffle_bag_uniform() {
+    // Each element is equally likely in each position of each epoch,
+    // including after the first.
+    let mut bag = seq::shuffle_bag(vec![0, 1, 2, 3], rng());
+    let mut position = 0;
+    let result = stats::uniformity(16, 16_000, || {
+        let value = bag.next().unwrap();
+        let bucket = position * 4 + value;
+        position = (position + 1) % 4;
+        bucket
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn crossover_points_valid() {
+    let mut rng = rng();
+    for &(len, k) in &[(2, 1), (10, 2), (10, 9), (1000, 5), (5, 0)] {
+        let points = seq::crossover_points(len, k, &mut rng);
+        assert_eq!(points.len(), k);
+        assert!(points.windows(2).all(|w| w[0] < w[1]), "{:?}", points);
+        assert!(points.iter().all(|&p| 0 < p && p < len), "{:?}", points);
+    }
+}
+
+#[test]
+fn crossover_points_uniform() {
+    // The 10 pairs of cuts in `1..6`.
+    let mut rng = rng();
+    let result = stats::uniformity(10, 5000, || {
+        let points = seq::crossover_points(6, 2, &mut rng);
+        let (a, b) = (points[0] - 1, points[1] - 1);
+        // The index of `(a, b)` among the pairs in lexicographic order.
+        a * (9 - a) / 2 + b - a - 1
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+#[should_panic(expected = "cannot choose 3 crossover points in 3 genes")]
+fn crossover_points_too_many() {
+    seq::crossover_points(3, 3, &mut rng());
+}
+
+#[test]
+fn mutation_mask_rate() {
+    let mut rng = rng();
+    let mask = seq::mutation_mask(100_000, 0.01, &mut rng);
+    assert_eq!(mask.len(), 1563);
+    // The bits past the length are never set.
+    assert_eq!(mask[1562] >> 32, 0);
+    let set = mask.iter().map(|w| w.count_ones()).sum::<u32>();
+    // `1000 ± 31`.
+    assert!((set as f64 - 1000.0).abs() < 130.0, "{}", set);
+    let mut counts = [0; 64];
+    for word in &mask {
+        for (bit, count) in counts.iter_mut().enumerate() {
+            *count += (word >> bit) & 1;
+        }
+    }
+    let expected = [set as f64 / 64.0; 64];
+    let result = stats::chi_squared(&counts, &expected);
+    assert!(result.passes(0.001), "{:?}", result);
+    assert!(seq::mutation_mask(130, 1.0, &mut rng) == [u64::MAX, u64::MAX, 3]);
+    assert!(seq::mutation_mask(0, 0.5, &mut rng).is_empty());
+}
@@ -0,0 +1,66 @@
+//! Shrinking values towards simpler ones.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::shrink::{minimize, Shrink};
+use rand_sketch::stream::gen_iter;
+use rand_sketch::text::{Charset, Sequence, Text};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn integers() {
+    assert_eq!((4..321u32).shrink(&100).collect::<Vec<_>>(), [4, 52, 76, 88, 94, 97, 99]);
+    assert_eq!((4..321u32).shrink(&4).count(), 0);
+    assert_eq!((..).shrink(&-10i64).collect::<Vec<_>>(), [0, -5, -8, -9]);
+    assert_eq!((..-5i64).shrink(&-10i64).collect::<Vec<_>>(), [-6, -8, -9]);
+    assert_eq!((10u8..).shrink(&255).next(), Some(10));
+    // Across the whole range.
+    assert_eq!((i64::MIN..i64::MAX).shrink(&i64::MAX).next(), Some(i64::MIN));
+    assert_eq!((..).shrink(&u64::MAX).nth(1), Some(1 << 63));
+}
+
+#[test]
+fn minimize_integers() {
+    for x in gen_iter::<u32, _, _>(rng(), 37..1000).take(100) {
+        assert_eq!(minimize(&(4..1000), x, |&x| x >= 37), 37);
+    }
+    for x in gen_iter::<i64, _, _>(rng(), ..).take(100) {
+        let expected = x.clamp(-1000, 1000);
+        assert_eq!(minimize(&.., x, |&x| x.abs() >= 1000), expected);
+    }
+}
+
+#[test]
+fn floats() {
+    let candidates = (1.0..9.0f64).shrink(&5.0).collect::<Vec<_>>();
+    assert_eq!(candidates[..4], [1.0, 3.0, 4.0, 4.5]);
+    assert!(candidates.len() < 60 && candidates.iter().all(|&x| (1.0..5.0).contains(&x)));
+    assert_eq!(Shrink::<f64>::shrink(&.., &f64::NAN).count(), 0);
+
+    let x = minimize(&(0.0..1.0), 0.9, |&x| x > 0.5);
+    assert!(x > 0.5 && x < 0.5 + 1e-12, "{}", x);
+}
+
+#[test]
+fn text() {
+    let text = Text(Charset::new("abcx"), 2..20);
+    let candidates = text.shrink(&String::from("abxc")).collect::<Vec<_>>();
+    // Removing two (as far as the minimum length), then one, then
+    // simplifying characters.
+    assert_eq!(candidates, ["xc", "ab", "bxc", "axc", "abc", "abx", "aaxc", "abac", "abxa"]);
+
+    let minimal = minimize(&text, String::from("cabbaxcaxb"), |s| s.contains('x'));
+    assert_eq!(minimal, "xa");
+}
+
+#[test]
+fn sequence() {
+    let sequence = Sequence(Charset::dna(), 0..100);
+    let minimal = minimize(&sequence, b"ACGTTGCAGG".to_vec(), |s| s.iter().filter(|&&b| b == b'G').count() >= 2);
+    assert_eq!(minimal, b"GG");
+}
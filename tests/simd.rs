@@ -0,0 +1,97 @@
+//! The vectorized streams, which should agree with the scalar ones on
+//! what values are possible, if not on which are drawn.
+
+#![cfg(feature = "simd")]
+
+extern crate rand;
+extern crate rand_sketch;
+extern crate wide;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::simd::fill_simd;
+use rand_sketch::stream::{Rand, RandStream};
+use rand_sketch::testing::stats::uniformity;
+use wide::{f32x8, u32x8};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn u32_range() {
+    let mut rng = rng();
+    let stream = u32x8::rand(4..321);
+    for _ in 0..1000 {
+        for v in stream.next(&mut rng).to_array().iter() {
+            assert!((4..321).contains(v), "{}", v);
+        }
+    }
+}
+
+#[test]
+fn u32_range_uniform() {
+    let mut rng = rng();
+    let stream = u32x8::rand(0..10);
+    let mut values = vec![];
+    uniformity(10, 100_000, || {
+        if values.is_empty() {
+            values.extend_from_slice(&stream.next(&mut rng).to_array());
+        }
+        values.pop().unwrap() as usize
+    }).passes(0.001);
+}
+
+#[test]
+fn u32_widest_range() {
+    let mut rng = rng();
+    let stream = u32x8::rand(0..u32::MAX);
+    for _ in 0..1000 {
+        assert!(stream.next(&mut rng).to_array().iter().all(|&v| v != u32::MAX));
+    }
+}
+
+#[test]
+fn f32_range() {
+    let mut rng = rng();
+    let stream = f32x8::rand(-4.0..321.0);
+    for _ in 0..1000 {
+        for &v in stream.next(&mut rng).to_array().iter() {
+            assert!((-4.0..321.0).contains(&v), "{}", v);
+        }
+    }
+}
+
+#[test]
+fn f32_full_uniform() {
+    let mut rng = rng();
+    let stream = f32x8::rand(..);
+    let mut values = vec![];
+    uniformity(16, 100_000, || {
+        if values.is_empty() {
+            values.extend_from_slice(&stream.next(&mut rng).to_array());
+        }
+        let v: f32 = values.pop().unwrap();
+        assert!((0.0..1.0).contains(&v));
+        (v * 16.0) as usize
+    }).passes(0.001);
+}
+
+#[test]
+fn fill_partial() {
+    let mut rng = rng();
+    for &len in &[0, 5, 8, 19] {
+        let mut dest = vec![u32::MAX; len];
+        fill_simd(&u32x8::rand(0..6), &mut rng, &mut dest);
+        assert!(dest.iter().all(|&v| v < 6), "{:?}", dest);
+
+        let mut dest = vec![-1.0; len];
+        fill_simd(&f32x8::rand(..), &mut rng, &mut dest);
+        assert!(dest.iter().all(|&v| (0.0..1.0).contains(&v)), "{:?}", dest);
+    }
+}
+
+#[test]
+#[should_panic]
+fn empty_range() {
+    u32x8::rand(5..5);
+}
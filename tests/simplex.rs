@@ -0,0 +1,89 @@
+//! Probability vectors and stochastic matrices.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::{random_probability_vector, random_stochastic_matrix, Dirichlet};
+use rand_sketch::markov::MarkovChain;
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::RunningStats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// Asserts that `values` is a probability vector.
+fn check_simplex(values: &[f64]) {
+    assert!(values.iter().all(|&x| x >= 0.0), "{:?}", values);
+    let total = values.iter().sum::<f64>();
+    assert!((total - 1.0).abs() < 1e-12, "{:?} sums to {}", values, total);
+}
+
+#[test]
+fn probability_vector() {
+    let mut rng = rng();
+    let n = 4;
+    let stats = (0..100_000).map(|_| {
+        let values = random_probability_vector(n, &mut rng);
+        assert_eq!(values.len(), n);
+        check_simplex(&values);
+        values[n - 1]
+    }).collect::<RunningStats>();
+    // Each value is `Beta(1, n - 1)`: mean `1/4`, variance `3/80`.
+    assert!((stats.mean() - 0.25).abs() < 0.005, "{}", stats.mean());
+    assert!((stats.variance() - 0.0375).abs() < 0.001, "{}", stats.variance());
+    assert_eq!(random_probability_vector(1, &mut rng), [1.0]);
+}
+
+#[test]
+fn stochastic_matrix() {
+    let mut rng = rng();
+    let matrix = random_stochastic_matrix(5, &mut rng);
+    assert_eq!(matrix.len(), 5);
+    for row in &matrix {
+        assert_eq!(row.len(), 5);
+        check_simplex(row);
+    }
+    MarkovChain::new(&matrix, 0);
+}
+
+#[test]
+fn dirichlet() {
+    let vectors = gen_iter::<Vec<f64>, _, _>(rng(), Dirichlet(vec![2.0, 1.0, 1.0]))
+        .take(100_000)
+        .collect::<Vec<_>>();
+    for values in &vectors {
+        assert_eq!(values.len(), 3);
+        check_simplex(values);
+    }
+    // `alpha_i / alpha_0`, and variance `alpha_i (alpha_0 - alpha_i) /
+    // (alpha_0^2 (alpha_0 + 1))`.
+    for &(i, mean, variance) in &[(0, 0.5, 0.05), (1, 0.25, 0.0375)] {
+        let stats = vectors.iter().map(|v| v[i]).collect::<RunningStats>();
+        assert!((stats.mean() - mean).abs() < 0.005, "{}: {}", i, stats.mean());
+        assert!((stats.variance() - variance).abs() < 0.001, "{}: {}", i, stats.variance());
+    }
+}
+
+#[test]
+fn dirichlet_sparse() {
+    // Tiny concentrations put nearly all the weight on one value, but
+    // never underflow all of them.
+    let vectors = gen_iter::<Vec<f64>, _, _>(rng(), Dirichlet(vec![0.001; 4]))
+        .take(10_000)
+        .collect::<Vec<_>>();
+    for values in &vectors {
+        check_simplex(values);
+    }
+    let concentrated = vectors.iter()
+        .filter(|values| values.iter().cloned().fold(0.0, f64::max) > 0.99)
+        .count();
+    assert!(concentrated > 9_800, "{}", concentrated);
+}
+
+#[test]
+#[should_panic(expected = "concentrations")]
+fn dirichlet_invalid() {
+    gen_iter::<Vec<f64>, _, _>(rng(), Dirichlet(vec![1.0, 0.0]));
+}
@@ -0,0 +1,64 @@
+//! Sparse vectors and matrices, by geometric skipping.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::sparse::sparse;
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn density() {
+    let len = 1_000_000;
+    let entries = sparse::<u32, _, _>(rng(), len, 0.01, 5..10).collect::<Vec<_>>();
+    // `10,000 ± 100` (one standard deviation).
+    assert!((entries.len() as f64 - 10_000.0).abs() < 400.0, "{}", entries.len());
+    assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(entries.iter().all(|&(i, v)| i < len && (5..10).contains(&v)));
+    let mut iter = entries.iter();
+    let result = stats::uniformity(20, entries.len(), || iter.next().unwrap().0 * 20 / len);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn gaps() {
+    // With density one half, each index after an entry is the next
+    // entry half the time, a quarter the one after, and so on.
+    let indices = sparse::<u8, _, _>(rng(), 100_000, 0.5, ..).map(|(i, _)| i).collect::<Vec<_>>();
+    let mut observed = [0; 4];
+    for w in indices.windows(2) {
+        observed[(w[1] - w[0] - 1).min(3)] += 1;
+    }
+    let n = (indices.len() - 1) as f64;
+    let result = stats::chi_squared(&observed, &[n / 2.0, n / 4.0, n / 8.0, n / 8.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn extremes() {
+    assert_eq!(sparse::<u8, _, _>(rng(), 1000, 0.0, ..).count(), 0);
+    let all = sparse::<u8, _, _>(rng(), 1000, 1.0, ..).map(|(i, _)| i).collect::<Vec<_>>();
+    assert_eq!(all, (0..1000).collect::<Vec<_>>());
+    assert_eq!(sparse::<u8, _, _>(rng(), 0, 0.5, ..).count(), 0);
+    // Hardly any entries of a huge vector, found quickly.
+    let entries = sparse::<u8, _, _>(rng(), usize::MAX, 1e-15, ..).take(100).collect::<Vec<_>>();
+    assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+#[test]
+fn matrix() {
+    let entries = sparse::<f64, _, _>(rng(), 300 * 500, 0.05, 0.0..1.0).matrix(300, 500).collect::<Vec<_>>();
+    assert!(entries.len() > 7000 && entries.len() < 8000, "{}", entries.len());
+    assert!(entries.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(entries.iter().all(|&((r, c), _)| r < 300 && c < 500));
+}
+
+#[test]
+#[should_panic(expected = "density")]
+fn invalid_density() {
+    sparse::<u8, _, _>(rng(), 10, 1.5, ..);
+}
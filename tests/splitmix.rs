@@ -0,0 +1,39 @@
+//! Hash seeds from SplitMix64.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use rand_sketch::splitmix::{hash_seeds, SplitMix64};
+
+#[test]
+fn reproducible() {
+    assert_eq!(hash_seeds(7, 5), hash_seeds(7, 5));
+    assert_eq!(hash_seeds(7, 3), hash_seeds(7, 5)[..3]);
+    let mut rng = SplitMix64::new(7);
+    assert_eq!(hash_seeds(7, 2), [rng.next_u64(), rng.next_u64()]);
+    assert!(hash_seeds(7, 0).is_empty());
+}
+
+#[test]
+fn decorrelated() {
+    // Consecutive user seeds give seeds differing in about half their
+    // bits, as do the seeds from one user seed.
+    let seeds = (0..1000).map(|s| hash_seeds(s, 8)).collect::<Vec<_>>();
+    let mut flips = 0;
+    let mut pairs = 0;
+    for (a, b) in seeds.iter().zip(&seeds[1..]) {
+        for (x, y) in a.iter().zip(b) {
+            flips += (x ^ y).count_ones();
+            pairs += 1;
+        }
+    }
+    for seed in &seeds {
+        for w in seed.windows(2) {
+            flips += (w[0] ^ w[1]).count_ones();
+            pairs += 1;
+        }
+    }
+    let mean = f64::from(flips) / f64::from(pairs);
+    assert!((mean - 32.0).abs() < 0.2, "{}", mean);
+}
@@ -0,0 +1,78 @@
+//! Stable distributions, checked against their closed forms, both
+//! directly and through the general method near them.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::Stable;
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::RunningStats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// 100,000 values from `Stable { alpha, beta, scale, location }`.
+fn values(alpha: f64, beta: f64, scale: f64, location: f64) -> Vec<f64> {
+    let values = gen_iter::<f64, _, _>(rng(), Stable { alpha, beta, scale, location })
+        .take(100_000)
+        .collect::<Vec<_>>();
+    assert!(values.iter().all(|x| x.is_finite()));
+    values
+}
+
+/// The fraction of `values` below `x`.
+fn below(values: &[f64], x: f64) -> f64 {
+    values.iter().filter(|&&v| v < x).count() as f64 / values.len() as f64
+}
+
+#[test]
+fn normal() {
+    for &alpha in &[2.0, 2.0 - 1e-9] {
+        let stats = values(alpha, 0.5, 3.0, 1.0).into_iter().collect::<RunningStats>();
+        assert!((stats.mean() - 1.0).abs() < 0.05, "{}: {}", alpha, stats.mean());
+        // `sqrt(2) * 3`.
+        assert!((stats.std_dev() - 4.242641).abs() < 0.05, "{}: {}", alpha, stats.std_dev());
+    }
+}
+
+#[test]
+fn cauchy() {
+    for &alpha in &[1.0, 1.0 + 1e-9] {
+        let values = values(alpha, 0.0, 2.0, -1.0);
+        assert!((below(&values, -1.0) - 0.5).abs() < 0.01);
+        // The quartiles are a half-width either side.
+        assert!((below(&values, -3.0) - 0.25).abs() < 0.01);
+        assert!((below(&values, 1.0) - 0.75).abs() < 0.01);
+    }
+}
+
+#[test]
+fn levy() {
+    for &alpha in &[0.5, 0.5 + 1e-9] {
+        let values = values(alpha, 1.0, 1.0, 0.0);
+        assert!(values.iter().all(|&x| x >= 0.0));
+        // `P(x < 1) = 2 (1 - Φ(1))`.
+        assert!((below(&values, 1.0) - 0.317311).abs() < 0.01, "{}", below(&values, 1.0));
+    }
+    let reflected = values(0.5, -1.0, 1.0, 5.0);
+    assert!(reflected.iter().all(|&x| x <= 5.0));
+    assert!((below(&reflected, 4.0) - 0.682689).abs() < 0.01);
+}
+
+#[test]
+fn skewed() {
+    for &alpha in &[0.8, 1.0, 1.5] {
+        let right = values(alpha, 1.0, 1.0, 0.0);
+        let far_right = 1.0 - below(&right, 20.0);
+        let far_left = below(&right, -20.0);
+        assert!(far_right > 0.003 && far_left < far_right / 10.0, "{}: {} {}", alpha, far_left, far_right);
+    }
+}
+
+#[test]
+#[should_panic(expected = "alpha")]
+fn alpha_out_of_range() {
+    values(2.5, 0.0, 1.0, 0.0);
+}
@@ -0,0 +1,52 @@
+//! Stepped ranges, `(start..end).step_by(step)`.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen, Rand, RandStream};
+use rand_sketch::testing::stats;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn multiples() {
+    let mut rng = rng();
+    // 0, 7, ..., 98: the step doesn't divide the width.
+    let result = stats::uniformity(15, 15_000, || {
+        let v = gen::<u32, _, _>(&mut rng, (0..100).step_by(7));
+        assert_eq!(v % 7, 0);
+        (v / 7) as usize
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn extremes() {
+    let mut rng = rng();
+    let stream = i64::rand((i64::MIN..i64::MAX).step_by(1 << 62));
+    let mut seen = [false; 4];
+    for _ in 0..100 {
+        let v = stream.next(&mut rng);
+        seen[((v >> 62) + 2) as usize] = true;
+        assert_eq!(v % (1 << 62), 0);
+    }
+    assert_eq!(seen, [true; 4]);
+
+    let stream = u8::rand((3..255).step_by(84));
+    let mut values = (0..100).map(|_| stream.next(&mut rng)).collect::<Vec<_>>();
+    values.sort();
+    values.dedup();
+    assert_eq!(values, [3, 87, 171]);
+
+    let top = u64::rand((u64::MAX - 1..u64::MAX).step_by(10)).next(&mut rng);
+    assert_eq!(top, u64::MAX - 1);
+}
+
+#[test]
+#[should_panic(expected = "stepped range must be non-empty")]
+fn empty() {
+    u32::rand((5..5).step_by(2));
+}
@@ -0,0 +1,136 @@
+//! The helpers for checking generators and streams by hand.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+use rand_sketch::stream::gen_iter;
+use rand_sketch::testing::{byte_entropy, emit_bytes, entropy, Histogram, RunningStats};
+use std::io::{self, Write};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn emit_bytes_matches_fill_bytes() {
+    // More than one buffer's worth, and not a multiple of it.
+    let n = 200_003;
+    let mut out = vec![];
+    emit_bytes(&mut rng(), &mut out, n).unwrap();
+    let mut expected = vec![0; n as usize];
+    rng().fill_bytes(&mut expected);
+    // `XorShiftRng` fills four bytes at a time, and the buffer is a
+    // multiple of four, so the joins don't change anything.
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn emit_bytes_stops_on_error() {
+    /// Accepts 100 bytes, then fails.
+    struct Closed(usize);
+    impl Write for Closed {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+            }
+            let n = buf.len().min(self.0);
+            self.0 -= n;
+            Ok(n)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let err = emit_bytes(&mut rng(), Closed(100), u64::MAX).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn running_stats() {
+    let stats = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].iter().cloned().collect::<RunningStats>();
+    assert_eq!(stats.count(), 8);
+    assert_eq!(stats.mean(), 5.0);
+    assert_eq!(stats.variance(), 32.0 / 7.0);
+    assert_eq!((stats.min(), stats.max()), (2.0, 9.0));
+
+    let empty = RunningStats::new();
+    assert!(empty.mean().is_nan() && empty.variance().is_nan());
+}
+
+#[test]
+fn running_stats_stable() {
+    // Summing squares would lose everything to cancellation here.
+    let stats = (0..1000).map(|i| 1e9 + (i % 2) as f64).collect::<RunningStats>();
+    assert_eq!(stats.mean(), 1e9 + 0.5);
+    assert!((stats.variance() - 0.25 * 1000.0 / 999.0).abs() < 1e-9, "{}", stats.variance());
+}
+
+#[test]
+fn running_stats_of_gen_iter() {
+    let stats = gen_iter::<u32, _, _>(rng(), 0..101).take(100_000).collect::<RunningStats>();
+    assert!((stats.mean() - 50.0).abs() < 0.5, "{}", stats);
+    // The variance of a discrete uniform over `n` values is `(n^2 - 1) / 12`.
+    assert!((stats.variance() / 850.0 - 1.0).abs() < 0.02, "{}", stats);
+    assert_eq!((stats.min(), stats.max()), (0.0, 100.0));
+}
+
+#[test]
+fn histogram() {
+    let mut histogram = Histogram::new(0.0, 1.0, 4);
+    histogram.extend(vec![-1.0, 0.0, 0.2, 0.25, 0.5, 0.99, 0.9999999999999999, 1.0, f64::NAN]);
+    assert_eq!(histogram.counts(), &[2, 1, 1, 2]);
+    assert_eq!((histogram.below(), histogram.above()), (1, 2));
+
+    let text = histogram.to_string();
+    assert_eq!(text.lines().count(), 6);
+    assert!(text.contains("[0.7500, 1.0000)        2 ##################################################"),
+            "{}", text);
+}
+
+#[test]
+fn histogram_of_gen_iter() {
+    let mut histogram = Histogram::new(0.0, 1.0, 10);
+    histogram.extend(gen_iter::<f32, _, _>(rng(), ..).take(10_000));
+    assert_eq!(histogram.counts().iter().sum::<u64>(), 10_000);
+    assert!(histogram.counts().iter().all(|&c| (900..1100).contains(&c)), "{}", histogram);
+}
+
+#[test]
+fn entropy_of_good_generators() {
+    let e = byte_entropy(&mut rng(), 1 << 20);
+    assert_eq!((e.values, e.distinct), (1 << 20, 256));
+    assert!(e.is_plausible(8.0), "{:?}", e);
+    assert!(e.shannon > 7.99 && e.min_entropy > 7.8, "{:?}", e);
+
+    let e = entropy(gen_iter::<u32, _, _>(rng(), 0..6).take(100_000));
+    assert!(e.is_plausible(6f64.log2()), "{:?}", e);
+    assert!(!e.is_plausible(3.0), "{:?}", e);
+}
+
+#[test]
+fn entropy_of_broken_generators() {
+    /// A generator stuck with its low bits clear.
+    struct Stuck(XorShiftRng);
+    impl Rng for Stuck {
+        fn next_u32(&mut self) -> u32 {
+            self.0.next_u32() & 0xf0f0_f0f0
+        }
+    }
+    let e = byte_entropy(&mut Stuck(rng()), 1 << 16);
+    assert_eq!(e.distinct, 16);
+    assert!(!e.is_plausible(8.0), "{:?}", e);
+    assert!((e.shannon - 4.0).abs() < 0.01, "{:?}", e);
+
+    // A stream over `0..10` that's off by one at the top.
+    let e = entropy(gen_iter::<u32, _, _>(rng(), 0..9).take(100_000));
+    assert!(!e.is_plausible(10f64.log2()), "{:?}", e);
+}
+
+#[test]
+fn entropy_edge_cases() {
+    let e = entropy(Vec::<u8>::new());
+    assert_eq!((e.values, e.shannon, e.min_entropy), (0, 0.0, 0.0));
+    let e = entropy(vec![7; 100]);
+    assert_eq!((e.distinct, e.shannon, e.min_entropy), (1, 0.0, 0.0));
+}
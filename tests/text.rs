@@ -0,0 +1,102 @@
+//! Characters, strings and byte sequences from a character set.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen, Rand, RandStream};
+use rand_sketch::testing::stats;
+use rand_sketch::text::{Charset, Sequence, Text};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn uniform() {
+    let stream = char::rand(Charset::new("0123456789abcdef"));
+    let mut rng = rng();
+    let result = stats::uniformity(16, 1000, || stream.next(&mut rng).to_digit(16).unwrap() as usize);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn weighted() {
+    let charset = Charset::weighted(&[('a', 1), ('b', 3), ('c', 0)]);
+    assert_eq!(charset.chars(), ['a', 'b', 'c']);
+    let mut rng = rng();
+    let mut counts = [0; 3];
+    for _ in 0..4000 {
+        counts[(gen::<char, _, _>(&mut rng, charset.clone()) as u8 - b'a') as usize] += 1;
+    }
+    assert_eq!(counts[2], 0);
+    let result = stats::chi_squared(&counts[..2], &[1000.0, 3000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn english() {
+    let text: String = gen(&mut rng(), Text(Charset::english(), 100_000..100_001));
+    assert_eq!(text.chars().count(), 100_000);
+    assert!(text.chars().all(|c| c == ' ' || c.is_ascii_lowercase()));
+    let count = |c| text.chars().filter(|&d| d == c).count();
+    // 18% spaces, 10% `e`s and 0.06% `z`s.
+    assert!((17_000..19_000).contains(&count(' ')), "{}", count(' '));
+    assert!(count('e') > count('t') && count('t') > count('z'));
+    assert!((9_500..11_000).contains(&count('e')), "{}", count('e'));
+}
+
+#[test]
+fn lengths() {
+    let stream = String::rand(Text(Charset::new("αβγ"), 3..6));
+    let mut rng = rng();
+    let mut seen = [false; 6];
+    for _ in 0..1000 {
+        let s = stream.next(&mut rng);
+        assert!(s.chars().all(|c| "αβγ".contains(c)));
+        seen[s.chars().count()] = true;
+    }
+    assert_eq!(seen, [false, false, false, true, true, true]);
+}
+
+#[test]
+#[should_panic]
+fn empty_charset() {
+    Charset::new("");
+}
+
+#[test]
+fn dna_gc_content() {
+    let stream = Vec::<u8>::rand(Sequence(Charset::dna_gc(0.6), 1000..1001));
+    let mut rng = rng();
+    let mut counts = [0; 4];
+    for _ in 0..20 {
+        let read = stream.next(&mut rng);
+        assert_eq!(read.len(), 1000);
+        for base in read {
+            counts[b"ACGT".iter().position(|&b| b == base).unwrap()] += 1;
+        }
+    }
+    let result = stats::chi_squared(&counts, &[4000.0, 6000.0, 6000.0, 4000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+    assert!(gen::<Vec<u8>, _, _>(&mut rng, Sequence(Charset::dna_gc(1.0), 50..51)).iter().all(|b| b"GC".contains(b)));
+}
+
+#[test]
+fn protein_kmers() {
+    let stream = Vec::<u8>::rand(Sequence(Charset::protein(), 5..6));
+    let mut rng = rng();
+    let result = stats::uniformity(20, 2000, || {
+        let kmer = stream.next(&mut rng);
+        assert_eq!(kmer.len(), 5);
+        b"ACDEFGHIKLMNPQRSTVWY".iter().position(|&b| b == kmer[0]).unwrap()
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+    assert_eq!(Charset::dna().chars(), ['A', 'C', 'G', 'T']);
+}
+
+#[test]
+#[should_panic(expected = "ASCII")]
+fn sequence_not_ascii() {
+    Vec::<u8>::rand(Sequence(Charset::new("aé"), 1..2));
+}
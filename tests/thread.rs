@@ -0,0 +1,69 @@
+//! The per-thread generator.
+
+#[cfg(unix)]
+extern crate libc;
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use rand_sketch::secure::token;
+use rand_sketch::thread::thread_rng;
+use std::thread;
+
+#[test]
+fn shared_within_thread() {
+    // Two handles share one generator, so they continue each other's
+    // output rather than repeating it.
+    let (mut a, mut b) = (thread_rng(), thread_rng());
+    let first = (0..4).map(|_| a.next_u64()).collect::<Vec<_>>();
+    let second = (0..4).map(|_| b.next_u64()).collect::<Vec<_>>();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn distinct_between_threads() {
+    let tokens = (0..4).map(|_| thread::spawn(|| token(16, &mut thread_rng())))
+                       .collect::<Vec<_>>()
+                       .into_iter()
+                       .map(|t| t.join().unwrap())
+                       .collect::<Vec<_>>();
+    for (i, t) in tokens.iter().enumerate() {
+        assert!(!tokens[..i].contains(t), "{:?}", tokens);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn reseeds_after_fork() {
+    use std::io::Read;
+    use std::fs::File;
+    use std::os::unix::io::FromRawFd;
+
+    // Seed this process's generator first, so the child inherits it.
+    let mut rng = thread_rng();
+    rng.next_u32();
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    match unsafe { libc::fork() } {
+        -1 => panic!("fork failed"),
+        0 => {
+            // The child: report its next values, then exit without
+            // running anything else of the test harness.
+            let child = token(16, &mut thread_rng());
+            unsafe {
+                libc::write(fds[1], child.as_ptr() as *const _, child.len());
+                libc::_exit(0);
+            }
+        }
+        pid => {
+            unsafe { libc::close(fds[1]) };
+            let parent = token(16, &mut thread_rng());
+            let mut child = String::new();
+            unsafe { File::from_raw_fd(fds[0]) }.read_to_string(&mut child).unwrap();
+            unsafe { libc::waitpid(pid, std::ptr::null_mut(), 0) };
+            assert_eq!(child.len(), 32);
+            assert_ne!(child, parent);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Synthetic time series.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::distributions::Normal;
+use rand_sketch::stream::{gen, Rand};
+use rand_sketch::testing::RunningStats;
+use rand_sketch::timeseries::{Season, TimeSeries};
+use std::f64::consts::TAU;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[test]
+fn components() {
+    let seasons = vec![
+        Season { period: 24.0, amplitude: 20.0, phase: 0.0 },
+        Season { period: 168.0, amplitude: 10.0, phase: 0.25 },
+    ];
+    let series = gen::<Vec<f64>, _, _>(&mut rng(), TimeSeries {
+        len: 10_000,
+        level: 100.0,
+        trend: 0.05,
+        seasons: seasons.clone(),
+        noise: f64::rand(Normal { mean: 0.0, sd: 5.0 }),
+    });
+    assert_eq!(series.len(), 10_000);
+    // What's left after the trend and seasons is the noise.
+    let residuals = series.iter().enumerate().map(|(t, x)| {
+        let t = t as f64;
+        let seasonal = seasons.iter()
+            .map(|s| s.amplitude * (TAU * (t / s.period + s.phase)).sin())
+            .sum::<f64>();
+        x - 100.0 - 0.05 * t - seasonal
+    }).collect::<RunningStats>();
+    assert!(residuals.mean().abs() < 0.15, "{}", residuals.mean());
+    assert!((residuals.std_dev() - 5.0).abs() < 0.15, "{}", residuals.std_dev());
+}
+
+#[test]
+fn trend_only() {
+    // With noise too narrow to matter, just a line.
+    let series = gen::<Vec<f64>, _, _>(&mut rng(), TimeSeries {
+        len: 5,
+        level: 1.0,
+        trend: -2.0,
+        seasons: vec![],
+        noise: f64::rand(Normal { mean: 0.0, sd: 1e-300 }),
+    });
+    assert_eq!(series, [1.0, -1.0, -3.0, -5.0, -7.0]);
+}
+
+#[test]
+#[should_panic(expected = "periods")]
+fn zero_period() {
+    Vec::<f64>::rand(TimeSeries {
+        len: 5,
+        level: 0.0,
+        trend: 0.0,
+        seasons: vec![Season { period: 0.0, amplitude: 1.0, phase: 0.0 }],
+        noise: f64::rand(Normal { mean: 0.0, sd: 1.0 }),
+    });
+}
@@ -0,0 +1,37 @@
+//! Types with a single value, which shouldn't draw anything.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use std::marker::{PhantomData, PhantomPinned};
+
+/// A generator that must not be used.
+struct Unused;
+
+impl Rng for Unused {
+    fn next_u32(&mut self) -> u32 {
+        panic!("drew from the generator")
+    }
+}
+
+macro_rules! trivial {
+    ($($design: ident),*) => {$(
+        mod $design {
+            use super::*;
+            use rand_sketch::$design::{gen, gen_iter};
+
+            #[test]
+            fn no_draws() {
+                let mut rng = Unused;
+                let () = gen(&mut rng, ..);
+                let _: PhantomData<str> = gen(&mut rng, ..);
+                let _: PhantomPinned = gen(&mut rng, ..);
+                let empty: [String; 0] = gen(&mut rng, ..);
+                assert!(empty.is_empty());
+                assert_eq!(gen_iter::<(), _, _>(Unused, ..).take(3).count(), 3);
+            }
+        }
+    )*}
+}
+trivial!(assoc, typeparam, stream);
@@ -0,0 +1,88 @@
+//! Chi-squared tests that ranged integer generation is uniform, in
+//! each design.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::testing::stats;
+
+/// Check that `bucket` (which draws a value and returns its bucket)
+/// fills `buckets` buckets uniformly, with a fixed seed so that the
+/// test is deterministic.
+fn check<F: FnMut(&mut XorShiftRng) -> usize>(buckets: usize, mut bucket: F) {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let result = stats::uniformity(buckets, 1000 * buckets, || bucket(&mut rng));
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+/// Check that `gen` gives `true` with probability `p`, with a fixed
+/// seed.
+fn check_bool<F: FnMut(&mut XorShiftRng) -> bool>(p: f64, mut gen: F) {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let n = 10_000;
+    let trues = (0..n).filter(|_| gen(&mut rng)).count() as u64;
+    let expected = [n as f64 * (1.0 - p), n as f64 * p];
+    let result = stats::chi_squared(&[n - trues, trues], &expected);
+    assert!(result.passes(0.001), "{}: {:?}", p, result);
+}
+
+/// For each `$name: $t, $range, |$v| $bucket`, a test that the values
+/// from `$design::gen` in `$range` are uniform over `$buckets`
+/// buckets, with `$bucket` computing the bucket of `$v`.
+macro_rules! uniformity {
+    ($design: ident { $($name: ident: $t: ty, $range: expr, $buckets: expr, |$v: ident| $bucket: expr;)* }) => {
+        mod $design {
+            use super::check;
+            use rand_sketch::$design::gen;
+
+            $(
+                #[test]
+                fn $name() {
+                    check($buckets, |rng| {
+                        let $v = gen::<$t, _, _>(rng, $range);
+                        $bucket
+                    })
+                }
+            )*
+        }
+    }
+}
+
+// Each design gets narrow ranges, and ranges just above a power of two
+// (17 * 2^k), which is the worst case for rejection.
+macro_rules! all_uniformity {
+    ($($design: ident),*) => {$(
+        uniformity!($design {
+            u8_narrow: u8, 4..21, 17, |v| (v - 4) as usize;
+            u8_worst: u8, 0..129, 129, |v| v as usize;
+            u32_narrow: u32, 4..321, 317, |v| (v - 4) as usize;
+            u32_worst: u32, 0..17 << 27, 17, |v| (v >> 27) as usize;
+            u64_narrow: u64, 4u64..321, 317, |v| (v - 4) as usize;
+            u64_worst: u64, 0u64..17 << 59, 17, |v| (v >> 59) as usize;
+            i64_narrow: i64, -160i64..161, 321, |v| (v + 160) as usize;
+            i64_worst: i64, i64::MIN..i64::MIN + (17 << 58), 17,
+                |v| (v.wrapping_sub(i64::MIN) as u64 >> 58) as usize;
+        });
+    )*}
+}
+all_uniformity!(assoc, typeparam, stream);
+
+#[test]
+fn high_precision() {
+    use rand_sketch::stream::gen;
+    use rand_sketch::HighPrecision01;
+
+    check(16, |rng| (gen::<f32, _, _>(rng, HighPrecision01) * 16.0) as usize);
+    check(16, |rng| (gen::<f64, _, _>(rng, HighPrecision01) * 16.0) as usize);
+}
+
+#[test]
+fn bool() {
+    use rand_sketch::stream::RngExt;
+
+    check_bool(0.25, |rng| rng.gen_bool(0.25));
+    check_bool(0.999, |rng| rng.gen_bool(0.999));
+    check_bool(2.0 / 3.0, |rng| rng.gen_ratio(2, 3));
+    check_bool(1.0 / 1000.0, |rng| rng.gen_ratio(1, 1000));
+}
@@ -0,0 +1,172 @@
+//! Pins the exact values produced by every `Random`/`Rand` impl for a
+//! fixed sequence of RNG output.
+//!
+//! A failure here means an algorithm change altered the values users
+//! get from a seeded generator. That may well be intended (e.g. a
+//! faster sampling method), but it's a breaking change: update the
+//! expected values deliberately, and bump the version accordingly.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::Rng;
+use rand_sketch::{Closed01, HighPrecision01, Open01, OpenClosed01};
+use rand_sketch::stream::{Bernoulli, ConstRange, Decimal, Ratio};
+
+/// A deterministic stand-in for a real generator: a Weyl sequence
+/// with the golden-ratio increment.
+struct MockRng {
+    state: u64,
+}
+
+impl MockRng {
+    fn new() -> MockRng {
+        MockRng { state: 0 }
+    }
+}
+
+impl Rng for MockRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.state
+    }
+}
+
+/// For each `$name: $t, $constraint => $expected`, a test in `mod
+/// $module` (by default, `$design`) that the first values from
+/// `$design::gen` are `$expected`.
+macro_rules! stability {
+    ($design: ident $rest: tt) => {
+        stability!($design: $design $rest);
+    };
+    ($module: ident: $design: ident { $($name: ident: $t: ty, $constraint: expr => $expected: expr;)* }) => {
+        mod $module {
+            use super::*;
+            use rand_sketch::$design::gen;
+
+            $(
+                #[test]
+                fn $name() {
+                    let mut rng = MockRng::new();
+                    let values = (0..5).map(|_| gen::<$t, _, _>(&mut rng, $constraint))
+                                       .collect::<Vec<_>>();
+                    let expected: [$t; 5] = $expected;
+                    assert_eq!(values, expected);
+                }
+            )*
+        }
+    }
+}
+
+// The designs share their algorithms, so give the same values. The
+// literals are suffixed where `assoc` needs them, since its
+// conversions into constraints are generic.
+macro_rules! all_stability {
+    ($($design: ident),*) => {$(
+        stability!($design {
+            u8_full: u8, .. => [185, 114, 44, 229, 159];
+            u8_range: u8, 4..221 => [138, 55, 189, 106, 23];
+            u8_range_from: u8, 200.. => [234, 213, 247, 226, 205];
+            u8_range_to: u8, ..100 => [61, 23, 85, 47, 9];
+            u32_full: u32, .. => [2654435769, 1013904242, 3668340012, 2027808485, 387276959];
+            u32_range: u32, 4..321 => [199, 78, 274, 153, 32];
+            u32_range_from: u32, 4_000_000_000.. =>
+                [4182299814, 4069632332, 4251932147,
+                 4139264665, 4026597184];
+            u32_range_to: u32, ..100 => [61, 23, 85, 47, 9];
+            u32_range_to_inclusive: u32, ..=100 => [62, 23, 86, 47, 9];
+            u64_full: u64, .. =>
+                [11400714819323198485, 4354685564936845354, 15755400384260043839,
+                 8709371129873690708, 1663341875487337577];
+            u64_range: u64, 4u64..321 => [199, 78, 274, 153, 32];
+            u64_range_pow2: u64, 16u64..16 + (1 << 40) =>
+                [679535557007, 259559486222, 939095043213,
+                 519118972429, 99142901644];
+            u64_range_from: u64, 1u64 << 63.. =>
+                [14923729446516375050, 11400714819323198485, 17101072228984797727,
+                 13578057601791621162, 10055042974598444596];
+            u64_range_to: u64, ..100u64 => [61, 23, 85, 47, 9];
+            i64_full: i64, .. =>
+                [-7046029254386353131, 4354685564936845354, -2691343689449507777,
+                 8709371129873690708, 1663341875487337577];
+            i64_range: i64, -160i64..161 => [38, -85, 114, -9, -132];
+            i64_range_pow2: i64, -8i64..8 => [1, -5, 5, -1, -7];
+            i64_range_from: i64, -5i64.. =>
+                [5700357409661599240, 7877700192130021918, 831670937743668783,
+                 6532028347405268029, 8709371129873690707];
+            i64_range_to: i64, ..-5i64 =>
+                [-3523014627193176569, -7046029254386353133, -1345671844724753893,
+                 -4868686471917930457, -8391701099111107020];
+            i64_range_to_inclusive: i64, ..=-5i64 =>
+                [-3523014627193176568, -7046029254386353132, -1345671844724753892,
+                 -4868686471917930456, -8391701099111107020];
+            f32_full: f32, .. => [0.61803395, 0.23606795, 0.85410196, 0.4721359, 0.09016991];
+            f32_open: f32, Open01 => [0.61803395, 0.23606795, 0.85410196, 0.47213596, 0.090169966];
+            f32_closed: f32, Closed01 => [0.618034, 0.23606797, 0.854102, 0.47213593, 0.090169914];
+            f32_open_closed: f32, OpenClosed01 =>
+                [0.38196605, 0.76393205, 0.14589804,
+                 0.5278641, 0.9098301];
+            f32_high_precision: f32, HighPrecision01 =>
+                [0.61803395, 0.73606795, 0.106762744,
+                 0.48606795, 0.5901699];
+            f32_range: f32, -4.0..321.0 => [196.86104, 72.722084, 273.58313, 149.44417, 25.30522];
+            f32_range_to: f32, ..321.0 => [198.3889, 75.77781, 274.16672, 151.55562, 28.94454];
+            f64_full: f64, .. =>
+                [0.6180339887498948, 0.2360679774997897, 0.8541019662496845,
+                 0.4721359549995794, 0.09016994374947418];
+            f64_open: f64, Open01 =>
+                [0.6180339887498948, 0.2360679774997897, 0.8541019662496846,
+                 0.4721359549995795, 0.09016994374947418];
+            f64_closed: f64, Closed01 =>
+                [0.6180339887498949, 0.23606797749978972, 0.8541019662496846,
+                 0.47213595499957944, 0.0901699437494742];
+            f64_open_closed: f64, OpenClosed01 =>
+                [0.3819660112501052, 0.7639320225002103, 0.14589803375031551,
+                 0.5278640450004206, 0.9098300562505258];
+            f64_high_precision: f64, HighPrecision01 =>
+                [0.6180339887498948, 0.7360679774997897, 0.10676274578121056,
+                 0.4860679774997897, 0.5901699437494742];
+            f64_range: f64, -4.0..321.0 =>
+                [196.8610463437158, 72.72209268743165, 273.5831390311475,
+                 149.4441853748633, 25.30523171857911];
+            f64_range_to: f64, ..321.0 =>
+                [198.38891038871623, 75.7778207774325, 274.16673116614874,
+                 151.555641554865, 28.94455194358121];
+            f64_range_to_inclusive: f64, ..=321.0 =>
+                [198.38891038871625, 75.7778207774325, 274.16673116614874,
+                 151.555641554865, 28.944551943581217];
+        });
+    )*}
+}
+all_stability!(assoc, typeparam, stream);
+
+stability!(stream_only: stream {
+    bool_bernoulli: bool, Bernoulli(0.5) => [false, true, false, true, true];
+    bool_ratio: bool, Ratio(2, 3) => [true, true, false, true, true];
+    u32_const_range: u32, ConstRange::<4, 321> => [199, 78, 274, 153, 32];
+    u32_stepped: u32, (0..100).step_by(7) => [63, 21, 84, 49, 7];
+    i64_stepped: i64, (-100i64..100).step_by(7) => [19, -58, 68, -9, -86];
+    i64_decimal: i64, Decimal(0.01..=99.99, 2) => [6180, 2361, 8541, 4721, 902];
+    f64_decimal: f64, Decimal(0.01..=99.99, 2) => [61.8, 23.61, 85.41, 47.21, 9.02];
+});
+
+#[test]
+fn splitmix64() {
+    // From the reference implementation, seeded with 1234567.
+    let mut rng = rand_sketch::splitmix::SplitMix64::new(1234567);
+    let values = (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>();
+    assert_eq!(values, [6457827717110365317, 3203168211198807973, 9817491932198370423,
+                        4593380528125082431, 16408922859458223821]);
+}
+
+#[test]
+fn pcg32() {
+    // From the reference implementation's `pcg32-demo`.
+    let mut rng = rand_sketch::pcg::Pcg32::new(42, 54);
+    let values = (0..6).map(|_| rng.next_u32()).collect::<Vec<_>>();
+    assert_eq!(values, [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e]);
+}
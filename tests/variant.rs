@@ -0,0 +1,64 @@
+//! Choosing among a subset of an enum's variants.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::stream::{gen, Rand, RandStream};
+use rand_sketch::testing::stats;
+use rand_sketch::variant::VariantMask;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+use Suit::*;
+
+const ALL: [Suit; 4] = [Clubs, Diamonds, Hearts, Spades];
+
+#[test]
+fn only() {
+    let stream = Suit::rand(VariantMask::only([Diamonds, Spades]));
+    let mut rng = rng();
+    let result = stats::uniformity(2, 2000, || {
+        match stream.next(&mut rng) {
+            Diamonds => 0,
+            Spades => 1,
+            other => panic!("{:?}", other),
+        }
+    });
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn weighted() {
+    let mask = VariantMask::weighted([(Clubs, 1.0), (Hearts, 3.0), (Spades, 0.0)]);
+    assert_eq!(mask.variants(), [Clubs, Hearts, Spades]);
+    let mut rng = rng();
+    let mut counts = [0; 4];
+    for _ in 0..4000 {
+        counts[gen::<Suit, _, _>(&mut rng, mask.clone()) as usize] += 1;
+    }
+    assert_eq!((counts[1], counts[3]), (0, 0));
+    let result = stats::chi_squared(&[counts[0], counts[2]], &[1000.0, 3000.0]);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn from_bits() {
+    assert_eq!(VariantMask::from_bits(&ALL, 0b0101).variants(), [Clubs, Hearts]);
+    assert_eq!(VariantMask::from_bits(&ALL, !0).variants(), ALL);
+}
+
+#[test]
+#[should_panic(expected = "no variants allowed")]
+fn from_bits_empty() {
+    VariantMask::from_bits(&ALL, 0b1_0000);
+}
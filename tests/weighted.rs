@@ -0,0 +1,110 @@
+//! Sampling from weighted items.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use rand_sketch::testing::stats;
+use rand_sketch::weighted::{AliasTable, WeightedTree};
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+/// Check that `tree` samples each index in proportion to `weights`.
+fn check(tree: &WeightedTree<char>, weights: &[f64]) {
+    let mut rng = rng();
+    let samples = 20_000;
+    let mut counts = vec![0; weights.len()];
+    for _ in 0..samples {
+        counts[tree.sample(&mut rng).unwrap()] += 1;
+    }
+    // Items that can't be chosen must not be, and the rest are tested.
+    let total = weights.iter().sum::<f64>();
+    let (observed, expected): (Vec<_>, Vec<_>) = counts.iter().zip(weights).filter_map(|(&c, &w)| {
+        if w == 0.0 {
+            assert_eq!(c, 0);
+            None
+        } else {
+            Some((c, samples as f64 * w / total))
+        }
+    }).unzip();
+    let result = stats::chi_squared(&observed, &expected);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+fn proportional() {
+    let weights = [1.0, 2.0, 0.0, 3.0, 4.0, 0.5, 0.0];
+    let tree = "abcdefg".chars().zip(weights.iter().cloned()).collect::<WeightedTree<_>>();
+    assert_eq!(tree.total_weight(), 10.5);
+    check(&tree, &weights);
+}
+
+#[test]
+fn updates() {
+    let mut tree = "abcdefghij".chars().map(|c| (c, 1.0)).collect::<WeightedTree<_>>();
+    tree.set_weight(3, 5.0);
+    tree.set_weight(0, 0.0);
+    assert_eq!(tree.remove(7), Some('h'));
+    assert_eq!(tree.remove(7), None);
+    assert_eq!(tree.get(7), None);
+    let index = tree.push('k', 2.0);
+    assert_eq!((index, tree.get(index)), (10, Some(&'k')));
+
+    let weights = [0.0, 1.0, 1.0, 5.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 2.0];
+    assert_eq!((0..tree.len()).map(|i| tree.weight(i)).collect::<Vec<_>>(), weights);
+    check(&tree, &weights);
+
+    tree.rebuild();
+    assert_eq!(tree.total_weight(), 14.0);
+    check(&tree, &weights);
+}
+
+#[test]
+fn nothing_to_choose() {
+    let mut tree = WeightedTree::new();
+    assert_eq!(tree.sample(&mut rng()), None);
+    tree.push('a', 0.0);
+    assert_eq!(tree.sample(&mut rng()), None);
+    tree.set_weight(0, 1.0);
+    assert_eq!(tree.sample(&mut rng()), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "weight must be finite and non-negative")]
+fn negative_weight() {
+    WeightedTree::new().push('a', -1.0);
+}
+
+#[test]
+#[should_panic(expected = "no item at index 0")]
+fn reweight_removed() {
+    let mut tree = WeightedTree::new();
+    tree.push('a', 1.0);
+    tree.remove(0);
+    tree.set_weight(0, 1.0);
+}
+
+#[test]
+fn alias_table() {
+    let weights = [1.0, 2.0, 0.0, 3.0, 4.0, 0.5, 0.0];
+    let table = AliasTable::new(&weights);
+    assert_eq!(table.len(), 7);
+    let mut rng = rng();
+    let mut counts = vec![0; weights.len()];
+    for _ in 0..20_000 {
+        counts[table.sample(&mut rng)] += 1;
+    }
+    assert_eq!((counts[2], counts[6]), (0, 0));
+    let observed = [counts[0], counts[1], counts[3], counts[4], counts[5]];
+    let expected = [1.0, 2.0, 3.0, 4.0, 0.5].iter().map(|w| 20_000.0 * w / 10.5).collect::<Vec<_>>();
+    let result = stats::chi_squared(&observed, &expected);
+    assert!(result.passes(0.001), "{:?}", result);
+}
+
+#[test]
+#[should_panic(expected = "weights must not all be zero")]
+fn alias_table_all_zero() {
+    AliasTable::new(&[0.0, 0.0]);
+}
@@ -0,0 +1,49 @@
+//! Smart pointers and cells, generated by forwarding the constraint to
+//! their contents.
+
+extern crate rand;
+extern crate rand_sketch;
+
+use rand::{SeedableRng, XorShiftRng};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4])
+}
+
+macro_rules! wrappers {
+    ($($design: ident),*) => {$(
+        mod $design {
+            use super::*;
+            use rand_sketch::$design::gen;
+
+            #[test]
+            fn same_values_as_contents() {
+                let (mut plain, mut wrapped) = (rng(), rng());
+                for _ in 0..100 {
+                    let x: u32 = gen(&mut plain, 4..321);
+                    assert_eq!(*gen::<Box<u32>, _, _>(&mut wrapped, 4..321), x);
+                    let y: f64 = gen(&mut plain, -1.0..1.0);
+                    assert_eq!(*gen::<Rc<f64>, _, _>(&mut wrapped, -1.0..1.0), y);
+                    let z: u64 = gen(&mut plain, ..);
+                    assert_eq!(*gen::<Arc<u64>, _, _>(&mut wrapped, ..), z);
+                    let w: i64 = gen(&mut plain, -5i64..);
+                    assert_eq!(gen::<Cell<i64>, _, _>(&mut wrapped, -5i64..).get(), w);
+                    let v: u8 = gen(&mut plain, ..100);
+                    assert_eq!(gen::<RefCell<u8>, _, _>(&mut wrapped, ..100).into_inner(), v);
+                }
+            }
+
+            #[test]
+            fn nested() {
+                let (mut plain, mut wrapped) = (rng(), rng());
+                let x: u32 = gen(&mut plain, 4..321);
+                let y: Rc<RefCell<u32>> = gen(&mut wrapped, 4..321);
+                assert_eq!(*y.borrow(), x);
+            }
+        }
+    )*}
+}
+wrappers!(assoc, typeparam, stream);
@@ -0,0 +1,59 @@
+//! Wiping generator state.
+
+#![cfg(feature = "zeroize")]
+
+extern crate rand;
+extern crate rand_sketch;
+extern crate zeroize;
+
+use rand::{ChaChaRng, Rng, SeedableRng};
+use rand_sketch::pcg::Pcg32;
+use rand_sketch::secure::ZeroizingRng;
+use rand_sketch::splitmix::SplitMix64;
+use zeroize::Zeroize;
+
+#[test]
+fn pcg32() {
+    let mut rng = Pcg32::new(42, 54);
+    rng.next_u32();
+    rng.zeroize();
+    assert_eq!(format!("{:?}", rng), "Pcg32 { state: 0, increment: 0 }");
+}
+
+#[test]
+fn splitmix64() {
+    let mut rng = SplitMix64::new(42);
+    rng.next_u64();
+    rng.zeroize();
+    assert_eq!(format!("{:?}", rng), "SplitMix64 { state: 0 }");
+}
+
+#[test]
+fn wrapper_delegates() {
+    let key = [1, 2, 3, 4, 5, 6, 7, 8];
+    let (mut plain, mut wrapped) = (ChaChaRng::from_seed(&key[..]),
+                                    ZeroizingRng::new(ChaChaRng::from_seed(&key[..])));
+    let (mut a, mut b) = ([0; 37], [0; 37]);
+    plain.fill_bytes(&mut a);
+    wrapped.fill_bytes(&mut b);
+    assert_eq!(a[..], b[..]);
+    assert_eq!(plain.next_u64(), wrapped.next_u64());
+    assert_eq!(plain.next_u32(), wrapped.next_u32());
+}
+
+#[test]
+fn wrapper_drops_inner() {
+    use std::rc::Rc;
+
+    /// A generator that holds a reference count, to see it dropped.
+    struct Counted(#[allow(dead_code)] Rc<()>);
+    impl Rng for Counted {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+    }
+
+    let count = Rc::new(());
+    drop(ZeroizingRng::new(Counted(count.clone())));
+    assert_eq!(Rc::strong_count(&count), 1);
+}